@@ -0,0 +1,481 @@
+#![deny(missing_docs)]
+// anchor_lang's Error is large, and it's what every CPI helper here returns
+#![allow(clippy::result_large_err)]
+
+//! Anchor definitions for the Solend lending program.
+//!
+//! Anchor programs can hold lending accounts as `Account<'info, Reserve>`, `Account<'info,
+//! Obligation>` and `Account<'info, LendingMarket>`, take the program as `Program<'info, Solend>`,
+//! and invoke it through the typed CPI helpers here, e.g.
+//! `solend_anchor_interface::borrow_obligation_liquidity(CpiContext::new(program, accounts), amount)`.
+//!
+//! The account types and `Solend` are checked against [ID](constant.ID.html): the mainnet
+//! deployment, or the devnet one with the `devnet` feature. The CPI helpers build instructions
+//! with the `solend-sdk` builders against `ctx.program`'s key, so they work with any deployment
+//! when its accounts are passed unchecked.
+//!
+//! Reserves, obligations, lending markets, token accounts, mints and the token program are typed,
+//! so Anchor checks their owners and layouts when the calling program loads its own accounts.
+//! Oracles, the lending market authority and uninitialized accounts have no layout to check.
+//! Owners and transfer authorities are plain accounts too, since they are often PDAs of the
+//! calling program that sign through `CpiContext::new_with_signer`.
+
+pub mod state;
+
+pub use solend_sdk;
+pub use state::{LendingMarket, Obligation, Reserve};
+
+use anchor_lang::{
+    context::CpiContext,
+    prelude::{Account, Program, Rent, Sysvar},
+    solana_program::{account_info::AccountInfo, program::invoke_signed, pubkey::Pubkey},
+    Accounts, Id, Key, Result, ToAccountInfos, ToAccountMetas,
+};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use solend_sdk::instruction;
+
+#[cfg(not(feature = "devnet"))]
+anchor_lang::declare_id!("So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo");
+#[cfg(feature = "devnet")]
+anchor_lang::declare_id!("ALend7Ketfx5bxh6ghsCDXAoDrhvEmsXT3cynB6aPLgx");
+
+/// The lending program, for `Program<'info, Solend>`. Its id is [ID](constant.ID.html).
+#[derive(Clone, Debug)]
+pub struct Solend;
+
+impl Id for Solend {
+    fn id() -> Pubkey {
+        ID
+    }
+}
+
+fn invoke<'info, T: ToAccountMetas + ToAccountInfos<'info>>(
+    ix: &anchor_lang::solana_program::instruction::Instruction,
+    ctx: &CpiContext<'_, '_, '_, 'info, T>,
+) -> Result<()> {
+    invoke_signed(ix, &ctx.to_account_infos(), ctx.signer_seeds).map_err(Into::into)
+}
+
+/// Accounts for [refresh_reserve](fn.refresh_reserve.html)
+#[derive(Accounts)]
+pub struct RefreshReserve<'info> {
+    /// Reserve account
+    pub reserve: Account<'info, Reserve>,
+    /// Pyth price account of the reserve liquidity
+    pub pyth_price: AccountInfo<'info>,
+    /// Switchboard feed account of the reserve liquidity
+    pub switchboard_feed: AccountInfo<'info>,
+}
+
+/// Accrue interest and update the market price of a reserve
+pub fn refresh_reserve<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, RefreshReserve<'info>>,
+) -> Result<()> {
+    let ix = instruction::refresh_reserve(
+        *ctx.program.key,
+        ctx.accounts.reserve.key(),
+        ctx.accounts.pyth_price.key(),
+        ctx.accounts.switchboard_feed.key(),
+    );
+    invoke(&ix, &ctx)
+}
+
+/// Accounts for [refresh_obligation](fn.refresh_obligation.html). The obligation's deposit
+/// reserves then borrow reserves, refreshed and in order, go in `ctx.remaining_accounts`.
+#[derive(Accounts)]
+pub struct RefreshObligation<'info> {
+    /// Obligation account
+    pub obligation: Account<'info, Obligation>,
+}
+
+/// Accrue interest and update the market value of an obligation's deposits and borrows
+pub fn refresh_obligation<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, RefreshObligation<'info>>,
+) -> Result<()> {
+    let ix = instruction::refresh_obligation(
+        *ctx.program.key,
+        ctx.accounts.obligation.key(),
+        ctx.remaining_accounts
+            .iter()
+            .map(|reserve| *reserve.key)
+            .collect(),
+    );
+    invoke(&ix, &ctx)
+}
+
+/// Accounts for [init_obligation](fn.init_obligation.html)
+#[derive(Accounts)]
+pub struct InitObligation<'info> {
+    /// Obligation account, allocated and owned by the lending program but uninitialized
+    pub obligation: AccountInfo<'info>,
+    /// Lending market account
+    pub lending_market: Account<'info, LendingMarket>,
+    /// Obligation owner, a signer
+    pub obligation_owner: AccountInfo<'info>,
+    /// Rent sysvar
+    pub rent: Sysvar<'info, Rent>,
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Initialize an obligation
+pub fn init_obligation<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, InitObligation<'info>>,
+) -> Result<()> {
+    let ix = instruction::init_obligation(
+        *ctx.program.key,
+        ctx.accounts.obligation.key(),
+        ctx.accounts.lending_market.key(),
+        ctx.accounts.obligation_owner.key(),
+    );
+    invoke(&ix, &ctx)
+}
+
+/// Accounts for [deposit_reserve_liquidity](fn.deposit_reserve_liquidity.html)
+#[derive(Accounts)]
+pub struct DepositReserveLiquidity<'info> {
+    /// Source liquidity token account
+    pub source_liquidity: Account<'info, TokenAccount>,
+    /// Destination collateral token account
+    pub destination_collateral: Account<'info, TokenAccount>,
+    /// Reserve account, refreshed
+    pub reserve: Account<'info, Reserve>,
+    /// Reserve liquidity supply token account
+    pub reserve_liquidity_supply: Account<'info, TokenAccount>,
+    /// Reserve collateral mint
+    pub reserve_collateral_mint: Account<'info, Mint>,
+    /// Lending market account
+    pub lending_market: Account<'info, LendingMarket>,
+    /// Derived lending market authority
+    pub lending_market_authority: AccountInfo<'info>,
+    /// Authority over the source liquidity, a signer
+    pub user_transfer_authority: AccountInfo<'info>,
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Deposit liquidity into a reserve in exchange for collateral
+pub fn deposit_reserve_liquidity<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, DepositReserveLiquidity<'info>>,
+    liquidity_amount: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let ix = instruction::deposit_reserve_liquidity(
+        *ctx.program.key,
+        liquidity_amount,
+        accounts.source_liquidity.key(),
+        accounts.destination_collateral.key(),
+        accounts.reserve.key(),
+        accounts.reserve_liquidity_supply.key(),
+        accounts.reserve_collateral_mint.key(),
+        accounts.lending_market.key(),
+        accounts.user_transfer_authority.key(),
+    );
+    invoke(&ix, &ctx)
+}
+
+/// Accounts for [redeem_reserve_collateral](fn.redeem_reserve_collateral.html)
+#[derive(Accounts)]
+pub struct RedeemReserveCollateral<'info> {
+    /// Source collateral token account
+    pub source_collateral: Account<'info, TokenAccount>,
+    /// Destination liquidity token account
+    pub destination_liquidity: Account<'info, TokenAccount>,
+    /// Reserve account, refreshed
+    pub reserve: Account<'info, Reserve>,
+    /// Reserve collateral mint
+    pub reserve_collateral_mint: Account<'info, Mint>,
+    /// Reserve liquidity supply token account
+    pub reserve_liquidity_supply: Account<'info, TokenAccount>,
+    /// Lending market account
+    pub lending_market: Account<'info, LendingMarket>,
+    /// Derived lending market authority
+    pub lending_market_authority: AccountInfo<'info>,
+    /// Authority over the source collateral, a signer
+    pub user_transfer_authority: AccountInfo<'info>,
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Redeem collateral for liquidity from a reserve
+pub fn redeem_reserve_collateral<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, RedeemReserveCollateral<'info>>,
+    collateral_amount: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let ix = instruction::redeem_reserve_collateral(
+        *ctx.program.key,
+        collateral_amount,
+        accounts.source_collateral.key(),
+        accounts.destination_liquidity.key(),
+        accounts.reserve.key(),
+        accounts.reserve_collateral_mint.key(),
+        accounts.reserve_liquidity_supply.key(),
+        accounts.lending_market.key(),
+        accounts.user_transfer_authority.key(),
+    );
+    invoke(&ix, &ctx)
+}
+
+/// Accounts for
+/// [deposit_reserve_liquidity_and_obligation_collateral](fn.deposit_reserve_liquidity_and_obligation_collateral.html)
+#[derive(Accounts)]
+pub struct DepositReserveLiquidityAndObligationCollateral<'info> {
+    /// Source liquidity token account
+    pub source_liquidity: Account<'info, TokenAccount>,
+    /// Collateral token account the reserve mints into before it's deposited
+    pub user_collateral: Account<'info, TokenAccount>,
+    /// Reserve account
+    pub reserve: Account<'info, Reserve>,
+    /// Reserve liquidity supply token account
+    pub reserve_liquidity_supply: Account<'info, TokenAccount>,
+    /// Reserve collateral mint
+    pub reserve_collateral_mint: Account<'info, Mint>,
+    /// Lending market account
+    pub lending_market: Account<'info, LendingMarket>,
+    /// Derived lending market authority
+    pub lending_market_authority: AccountInfo<'info>,
+    /// Reserve collateral supply token account
+    pub destination_deposit_collateral: Account<'info, TokenAccount>,
+    /// Obligation account
+    pub obligation: Account<'info, Obligation>,
+    /// Obligation owner, a signer
+    pub obligation_owner: AccountInfo<'info>,
+    /// Pyth price account of the reserve liquidity
+    pub pyth_price: AccountInfo<'info>,
+    /// Switchboard feed account of the reserve liquidity
+    pub switchboard_feed: AccountInfo<'info>,
+    /// Authority over the source liquidity, a signer
+    pub user_transfer_authority: AccountInfo<'info>,
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Deposit liquidity into a reserve and the collateral it mints into an obligation
+pub fn deposit_reserve_liquidity_and_obligation_collateral<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, DepositReserveLiquidityAndObligationCollateral<'info>>,
+    liquidity_amount: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let ix = instruction::deposit_reserve_liquidity_and_obligation_collateral(
+        *ctx.program.key,
+        liquidity_amount,
+        accounts.source_liquidity.key(),
+        accounts.user_collateral.key(),
+        accounts.reserve.key(),
+        accounts.reserve_liquidity_supply.key(),
+        accounts.reserve_collateral_mint.key(),
+        accounts.lending_market.key(),
+        accounts.destination_deposit_collateral.key(),
+        accounts.obligation.key(),
+        accounts.obligation_owner.key(),
+        accounts.pyth_price.key(),
+        accounts.switchboard_feed.key(),
+        accounts.user_transfer_authority.key(),
+    );
+    invoke(&ix, &ctx)
+}
+
+/// Accounts for
+/// [withdraw_obligation_collateral_and_redeem_reserve_collateral](fn.withdraw_obligation_collateral_and_redeem_reserve_collateral.html)
+#[derive(Accounts)]
+pub struct WithdrawObligationCollateralAndRedeemReserveCollateral<'info> {
+    /// Reserve collateral supply token account
+    pub source_collateral: Account<'info, TokenAccount>,
+    /// Collateral token account the withdrawn collateral lands in before it's redeemed
+    pub destination_collateral: Account<'info, TokenAccount>,
+    /// Withdraw reserve account, refreshed
+    pub withdraw_reserve: Account<'info, Reserve>,
+    /// Obligation account, refreshed
+    pub obligation: Account<'info, Obligation>,
+    /// Lending market account
+    pub lending_market: Account<'info, LendingMarket>,
+    /// Derived lending market authority
+    pub lending_market_authority: AccountInfo<'info>,
+    /// Destination liquidity token account
+    pub destination_liquidity: Account<'info, TokenAccount>,
+    /// Reserve collateral mint
+    pub reserve_collateral_mint: Account<'info, Mint>,
+    /// Reserve liquidity supply token account
+    pub reserve_liquidity_supply: Account<'info, TokenAccount>,
+    /// Obligation owner, a signer
+    pub obligation_owner: AccountInfo<'info>,
+    /// Authority over the destination collateral, a signer
+    pub user_transfer_authority: AccountInfo<'info>,
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraw collateral from an obligation and redeem it for liquidity
+pub fn withdraw_obligation_collateral_and_redeem_reserve_collateral<'info>(
+    ctx: CpiContext<
+        '_,
+        '_,
+        '_,
+        'info,
+        WithdrawObligationCollateralAndRedeemReserveCollateral<'info>,
+    >,
+    collateral_amount: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let ix = instruction::withdraw_obligation_collateral_and_redeem_reserve_collateral(
+        *ctx.program.key,
+        collateral_amount,
+        accounts.source_collateral.key(),
+        accounts.destination_collateral.key(),
+        accounts.withdraw_reserve.key(),
+        accounts.obligation.key(),
+        accounts.lending_market.key(),
+        accounts.destination_liquidity.key(),
+        accounts.reserve_collateral_mint.key(),
+        accounts.reserve_liquidity_supply.key(),
+        accounts.obligation_owner.key(),
+        accounts.user_transfer_authority.key(),
+    );
+    invoke(&ix, &ctx)
+}
+
+/// Accounts for [borrow_obligation_liquidity](fn.borrow_obligation_liquidity.html). A host fee
+/// receiver token account can be passed as the first of `ctx.remaining_accounts`.
+#[derive(Accounts)]
+pub struct BorrowObligationLiquidity<'info> {
+    /// Reserve liquidity supply token account
+    pub source_liquidity: Account<'info, TokenAccount>,
+    /// Destination liquidity token account
+    pub destination_liquidity: Account<'info, TokenAccount>,
+    /// Borrow reserve account, refreshed
+    pub borrow_reserve: Account<'info, Reserve>,
+    /// Borrow reserve liquidity fee receiver token account
+    pub borrow_reserve_liquidity_fee_receiver: Account<'info, TokenAccount>,
+    /// Obligation account, refreshed
+    pub obligation: Account<'info, Obligation>,
+    /// Lending market account
+    pub lending_market: Account<'info, LendingMarket>,
+    /// Derived lending market authority
+    pub lending_market_authority: AccountInfo<'info>,
+    /// Obligation owner, a signer
+    pub obligation_owner: AccountInfo<'info>,
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Borrow liquidity against an obligation's deposits
+pub fn borrow_obligation_liquidity<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, BorrowObligationLiquidity<'info>>,
+    liquidity_amount: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let ix = instruction::borrow_obligation_liquidity(
+        *ctx.program.key,
+        liquidity_amount,
+        accounts.source_liquidity.key(),
+        accounts.destination_liquidity.key(),
+        accounts.borrow_reserve.key(),
+        accounts.borrow_reserve_liquidity_fee_receiver.key(),
+        accounts.obligation.key(),
+        accounts.lending_market.key(),
+        accounts.obligation_owner.key(),
+        ctx.remaining_accounts.first().map(|host| *host.key),
+    );
+    invoke(&ix, &ctx)
+}
+
+/// Accounts for [repay_obligation_liquidity](fn.repay_obligation_liquidity.html)
+#[derive(Accounts)]
+pub struct RepayObligationLiquidity<'info> {
+    /// Source liquidity token account
+    pub source_liquidity: Account<'info, TokenAccount>,
+    /// Reserve liquidity supply token account
+    pub destination_liquidity: Account<'info, TokenAccount>,
+    /// Repay reserve account, refreshed
+    pub repay_reserve: Account<'info, Reserve>,
+    /// Obligation account
+    pub obligation: Account<'info, Obligation>,
+    /// Lending market account
+    pub lending_market: Account<'info, LendingMarket>,
+    /// Authority over the source liquidity, a signer
+    pub user_transfer_authority: AccountInfo<'info>,
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Repay borrowed liquidity, `u64::MAX` for the whole borrow
+pub fn repay_obligation_liquidity<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, RepayObligationLiquidity<'info>>,
+    liquidity_amount: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let ix = instruction::repay_obligation_liquidity(
+        *ctx.program.key,
+        liquidity_amount,
+        accounts.source_liquidity.key(),
+        accounts.destination_liquidity.key(),
+        accounts.repay_reserve.key(),
+        accounts.obligation.key(),
+        accounts.lending_market.key(),
+        accounts.user_transfer_authority.key(),
+    );
+    invoke(&ix, &ctx)
+}
+
+/// Accounts for
+/// [liquidate_obligation_and_redeem_reserve_collateral](fn.liquidate_obligation_and_redeem_reserve_collateral.html)
+#[derive(Accounts)]
+pub struct LiquidateObligationAndRedeemReserveCollateral<'info> {
+    /// Source liquidity token account
+    pub source_liquidity: Account<'info, TokenAccount>,
+    /// Collateral token account the seized collateral lands in before it's redeemed
+    pub destination_collateral: Account<'info, TokenAccount>,
+    /// Destination liquidity token account
+    pub destination_liquidity: Account<'info, TokenAccount>,
+    /// Repay reserve account, refreshed
+    pub repay_reserve: Account<'info, Reserve>,
+    /// Repay reserve liquidity supply token account
+    pub repay_reserve_liquidity_supply: Account<'info, TokenAccount>,
+    /// Withdraw reserve account, refreshed
+    pub withdraw_reserve: Account<'info, Reserve>,
+    /// Withdraw reserve collateral mint
+    pub withdraw_reserve_collateral_mint: Account<'info, Mint>,
+    /// Withdraw reserve collateral supply token account
+    pub withdraw_reserve_collateral_supply: Account<'info, TokenAccount>,
+    /// Withdraw reserve liquidity supply token account
+    pub withdraw_reserve_liquidity_supply: Account<'info, TokenAccount>,
+    /// Withdraw reserve liquidity fee receiver token account
+    pub withdraw_reserve_liquidity_fee_receiver: Account<'info, TokenAccount>,
+    /// Obligation account, refreshed
+    pub obligation: Account<'info, Obligation>,
+    /// Lending market account
+    pub lending_market: Account<'info, LendingMarket>,
+    /// Derived lending market authority
+    pub lending_market_authority: AccountInfo<'info>,
+    /// Authority over the source liquidity, a signer
+    pub user_transfer_authority: AccountInfo<'info>,
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+/// Repay part of an unhealthy obligation's borrow, seize collateral in return and redeem it
+pub fn liquidate_obligation_and_redeem_reserve_collateral<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, LiquidateObligationAndRedeemReserveCollateral<'info>>,
+    liquidity_amount: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let ix = instruction::liquidate_obligation_and_redeem_reserve_collateral(
+        *ctx.program.key,
+        liquidity_amount,
+        accounts.source_liquidity.key(),
+        accounts.destination_collateral.key(),
+        accounts.destination_liquidity.key(),
+        accounts.repay_reserve.key(),
+        accounts.repay_reserve_liquidity_supply.key(),
+        accounts.withdraw_reserve.key(),
+        accounts.withdraw_reserve_collateral_mint.key(),
+        accounts.withdraw_reserve_collateral_supply.key(),
+        accounts.withdraw_reserve_liquidity_supply.key(),
+        accounts.withdraw_reserve_liquidity_fee_receiver.key(),
+        accounts.obligation.key(),
+        accounts.lending_market.key(),
+        accounts.user_transfer_authority.key(),
+    );
+    invoke(&ix, &ctx)
+}
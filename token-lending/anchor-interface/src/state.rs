@@ -0,0 +1,98 @@
+//! Lending accounts as Anchor account types
+
+use anchor_lang::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    AccountDeserialize, AccountSerialize, Owner,
+};
+use solend_sdk::state;
+use std::ops::Deref;
+
+macro_rules! lending_account {
+    ($name:ident, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// Owned by the lending program at [ID](../constant.ID.html). Accounts of other deployments
+        /// can be decoded with `try_deserialize` on their data.
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct $name(state::$name);
+
+        impl $name {
+            /// Account data length
+            pub const LEN: usize = state::$name::LEN;
+        }
+
+        impl AccountDeserialize for $name {
+            fn try_deserialize_unchecked(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
+                state::$name::unpack(buf).map($name).map_err(Into::into)
+            }
+        }
+
+        // the lending program is the only writer of its accounts
+        impl AccountSerialize for $name {}
+
+        impl Owner for $name {
+            fn owner() -> Pubkey {
+                crate::ID
+            }
+        }
+
+        impl Deref for $name {
+            type Target = state::$name;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+    };
+}
+
+lending_account!(LendingMarket, "A lending market account");
+lending_account!(Reserve, "A reserve account");
+lending_account!(Obligation, "An obligation account");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::{prelude::Account, solana_program::account_info::AccountInfo};
+
+    #[test]
+    fn test_deserialize() {
+        let reserve = state::Reserve {
+            version: state::PROGRAM_VERSION,
+            ..state::Reserve::default()
+        };
+        let mut data = vec![0; Reserve::LEN];
+        state::Reserve::pack(reserve.clone(), &mut data).unwrap();
+        assert_eq!(*Reserve::try_deserialize(&mut &data[..]).unwrap(), reserve);
+
+        // uninitialized and mismatched accounts are rejected
+        assert!(Reserve::try_deserialize(&mut &vec![0; Reserve::LEN][..]).is_err());
+        assert!(Obligation::try_deserialize(&mut &data[..]).is_err());
+    }
+
+    #[test]
+    fn test_account_owner() {
+        let reserve = state::Reserve {
+            version: state::PROGRAM_VERSION,
+            ..state::Reserve::default()
+        };
+        let mut data = vec![0; Reserve::LEN];
+        state::Reserve::pack(reserve, &mut data).unwrap();
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        for (owner, ok) in [(crate::ID, true), (Pubkey::new_unique(), false)] {
+            let info = AccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                &owner,
+                false,
+                0,
+            );
+            assert_eq!(Account::<Reserve>::try_from(&info).is_ok(), ok);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use {
+    clap::{crate_description, crate_name, crate_version, value_t, App, Arg},
+    solana_clap_utils::{
+        input_parsers::pubkey_of,
+        input_validators::{is_pubkey, is_url},
+    },
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::commitment_config::CommitmentConfig,
+    solend_client::audit::{audit_reserves, get_reserve_addresses},
+    std::process::exit,
+};
+
+fn main() {
+    let default_lending_program_id: &str = &solend_sdk::solend_mainnet::id().to_string();
+
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .arg({
+            let arg = Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .help("Configuration file to use");
+            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
+                arg.default_value(config_file)
+            } else {
+                arg
+            }
+        })
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .help("JSON RPC URL for the cluster.  Default from the configuration file."),
+        )
+        .arg(
+            Arg::with_name("lending_program_id")
+                .long("program")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .required(true)
+                .default_value(default_lending_program_id)
+                .help("Lending program ID"),
+        )
+        .arg(
+            Arg::with_name("lending_market")
+                .long("market")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .help("Only audit the reserves of this lending market"),
+        )
+        .get_matches();
+
+    let cli_config = if let Some(config_file) = matches.value_of("config_file") {
+        solana_cli_config::Config::load(config_file).unwrap_or_default()
+    } else {
+        solana_cli_config::Config::default()
+    };
+    let json_rpc_url = value_t!(matches, "json_rpc_url", String).unwrap_or(cli_config.json_rpc_url);
+    let lending_program_id = pubkey_of(&matches, "lending_program_id").unwrap();
+    let lending_market = pubkey_of(&matches, "lending_market");
+    let rpc_client = RpcClient::new_with_commitment(json_rpc_url, CommitmentConfig::confirmed());
+
+    let result =
+        match get_reserve_addresses(&rpc_client, &lending_program_id, lending_market.as_ref()) {
+            Ok(reserves) => {
+                println!("Auditing {} reserves", reserves.len());
+                audit_reserves(&rpc_client, &reserves)
+            }
+            Err(err) => Err(err),
+        };
+    match result {
+        Ok(discrepancies) if discrepancies.is_empty() => println!("No discrepancies found"),
+        Ok(discrepancies) => {
+            for discrepancy in &discrepancies {
+                println!("{}", discrepancy);
+            }
+            eprintln!("Found {} discrepancies", discrepancies.len());
+            exit(1);
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            exit(2);
+        }
+    }
+}
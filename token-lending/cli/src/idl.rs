@@ -0,0 +1,249 @@
+//! `export-idl`: the lending program's instructions, accounts, types and errors in the Shank IDL
+//! format, which Codama and Solita generate clients from
+
+use {
+    num_traits::FromPrimitive,
+    serde::Serialize,
+    serde_json::{json, Value},
+    solana_program::pubkey::Pubkey,
+    solend_sdk::{
+        error::LendingError,
+        idl::{IdlField, IdlType, IdlTypeKind, ACCOUNTS, INSTRUCTIONS, TYPES},
+    },
+};
+
+/// The whole IDL document
+#[derive(Debug, Serialize)]
+pub struct Idl {
+    version: &'static str,
+    name: &'static str,
+    instructions: Vec<Instruction>,
+    accounts: Vec<TypeDef>,
+    types: Vec<TypeDef>,
+    errors: Vec<Error>,
+    metadata: Metadata,
+}
+
+#[derive(Debug, Serialize)]
+struct Instruction {
+    name: String,
+    accounts: Vec<InstructionAccount>,
+    args: Vec<Field>,
+    discriminant: Discriminant,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InstructionAccount {
+    name: String,
+    is_mut: bool,
+    is_signer: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    optional: bool,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    desc: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct Discriminant {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    value: u8,
+}
+
+#[derive(Debug, Serialize)]
+struct Field {
+    name: String,
+    #[serde(rename = "type")]
+    ty: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct TypeDef {
+    name: &'static str,
+    #[serde(rename = "type")]
+    ty: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct Error {
+    code: u32,
+    name: String,
+    msg: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Metadata {
+    origin: &'static str,
+    address: String,
+}
+
+/// `snake_case` to `camelCase`, as Shank names accounts, arguments and fields
+fn camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        match c {
+            '_' if !out.is_empty() => upper = true,
+            c if upper => {
+                out.extend(c.to_uppercase());
+                upper = false;
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn idl_type(ty: &IdlType) -> Value {
+    match ty {
+        IdlType::Bool => json!("bool"),
+        IdlType::U8 => json!("u8"),
+        IdlType::U16 => json!("u16"),
+        IdlType::U32 => json!("u32"),
+        IdlType::U64 => json!("u64"),
+        IdlType::U128 => json!("u128"),
+        IdlType::PublicKey => json!("publicKey"),
+        IdlType::Bytes(len) => json!({ "array": ["u8", len] }),
+        IdlType::Option(inner) => json!({ "option": idl_type(inner) }),
+        IdlType::Defined(name) => json!({ "defined": name }),
+    }
+}
+
+fn fields(fields: &[IdlField]) -> Vec<Field> {
+    fields
+        .iter()
+        .map(|field| Field {
+            name: camel_case(field.name),
+            ty: idl_type(&field.ty),
+        })
+        .collect()
+}
+
+fn struct_type(idl_fields: &[IdlField]) -> Value {
+    json!({ "kind": "struct", "fields": fields(idl_fields) })
+}
+
+impl Idl {
+    /// The IDL of the lending program deployed at `program_id`
+    pub fn new(program_id: &Pubkey) -> Self {
+        let instructions = INSTRUCTIONS
+            .iter()
+            .map(|instruction| Instruction {
+                name: instruction.name.to_string(),
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|account| InstructionAccount {
+                        name: camel_case(account.name),
+                        is_mut: account.writable,
+                        is_signer: account.signer,
+                        optional: account.optional,
+                        desc: account.desc,
+                    })
+                    .collect(),
+                args: fields(instruction.args),
+                discriminant: Discriminant {
+                    ty: "u8",
+                    value: instruction.discriminant,
+                },
+            })
+            .collect();
+
+        let accounts = ACCOUNTS
+            .iter()
+            .map(|account| TypeDef {
+                name: account.name,
+                ty: struct_type(account.fields),
+            })
+            .collect();
+
+        let types = TYPES
+            .iter()
+            .map(|ty| TypeDef {
+                name: ty.name,
+                ty: match ty.kind {
+                    IdlTypeKind::Struct(idl_fields) => struct_type(idl_fields),
+                    IdlTypeKind::Enum(variants) => json!({
+                        "kind": "enum",
+                        "variants": variants
+                            .iter()
+                            .map(|name| json!({ "name": name }))
+                            .collect::<Vec<_>>(),
+                    }),
+                },
+            })
+            .collect();
+
+        let errors = (0..)
+            .map_while(|code| {
+                LendingError::from_u32(code).map(|error| Error {
+                    code,
+                    name: format!("{:?}", error),
+                    msg: error.to_string(),
+                })
+            })
+            .collect();
+
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            name: "solend_program",
+            instructions,
+            accounts,
+            types,
+            errors,
+            metadata: Metadata {
+                origin: "shank",
+                address: program_id.to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_camel_case() {
+        assert_eq!(camel_case("lending_market"), "lendingMarket");
+        assert_eq!(camel_case("_padding"), "_padding");
+        assert_eq!(camel_case("version"), "version");
+    }
+
+    #[test]
+    fn test_idl() {
+        let program_id = Pubkey::new_unique();
+        let idl = serde_json::to_value(Idl::new(&program_id)).unwrap();
+
+        let init_reserve = &idl["instructions"][2];
+        assert_eq!(init_reserve["name"], "InitReserve");
+        assert_eq!(init_reserve["discriminant"]["value"], 2);
+        assert_eq!(init_reserve["accounts"][0]["name"], "sourceLiquidity");
+        assert_eq!(init_reserve["accounts"][0]["isMut"], true);
+        assert_eq!(init_reserve["args"][1]["type"]["defined"], "ReserveConfig");
+
+        let borrow = &idl["instructions"][10];
+        assert_eq!(
+            borrow["accounts"].as_array().unwrap().last().unwrap()["optional"],
+            true
+        );
+
+        assert_eq!(idl["errors"][0]["name"], "InstructionUnpackError");
+        assert_eq!(
+            idl["errors"].as_array().unwrap().len(),
+            LendingError::IsolatedTierAssetViolation as usize + 1
+        );
+        assert_eq!(idl["metadata"]["address"], program_id.to_string());
+    }
+
+    #[test]
+    fn test_checked_in_idl() {
+        let idl = serde_json::to_string_pretty(&Idl::new(&solend_program::id())).unwrap();
+        assert!(
+            include_str!("../../idl/solend_sdk.json").trim_end() == idl,
+            "token-lending/idl/solend_sdk.json is out of date, regenerate it with \
+             `solend-program export-idl --out token-lending/idl/solend_sdk.json`"
+        );
+    }
+}
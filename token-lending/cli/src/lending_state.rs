@@ -7,6 +7,7 @@ use solend_sdk::state::{Obligation, Reserve};
 use solana_client::rpc_client::RpcClient;
 use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
+use solend_client::accounts::get_multiple_reserves;
 use spl_associated_token_account::get_associated_token_address;
 use std::collections::HashSet;
 
@@ -37,12 +38,11 @@ impl SolendState {
         };
 
         // get reserve accounts
-        let reserves: Vec<(Pubkey, Reserve)> = rpc_client
-            .get_multiple_accounts(&reserve_pubkeys)
+        let reserves: Vec<(Pubkey, Reserve)> = get_multiple_reserves(rpc_client, &reserve_pubkeys)
             .unwrap()
             .into_iter()
             .zip(reserve_pubkeys.iter())
-            .map(|(account, pubkey)| (*pubkey, Reserve::unpack(&account.unwrap().data).unwrap()))
+            .map(|(reserve, pubkey)| (*pubkey, reserve.unwrap()))
             .collect();
 
         assert!(reserve_pubkeys.len() == reserves.len());
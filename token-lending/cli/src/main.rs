@@ -1,8 +1,29 @@
 use lending_state::SolendState;
-use solana_account_decoder::UiAccountEncoding;
+use output::{
+    println_display, CliAccount, CliFixtures, CliLendingMarket, CliLendingMarkets, CliObligation,
+    CliProgramVersion, CliReserve, CliSignOnly, CliTransactionMessage, CliTransactionOutput,
+    OutputFormat,
+};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::rpc_config::RpcAccountInfoConfig;
 use solana_client::rpc_config::{RpcProgramAccountsConfig, RpcSendTransactionConfig};
-use solana_client::{rpc_config::RpcAccountInfoConfig, rpc_filter::RpcFilterType};
-use solana_sdk::{commitment_config::CommitmentLevel, compute_budget::ComputeBudgetInstruction};
+use solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS;
+use solana_sdk::commitment_config::CommitmentLevel;
+use solend_client::{
+    accounts::get_multiple_reserves,
+    compute_budget::{
+        compute_budget_instructions, with_compute_budget, ComputeBudgetConfig,
+        MAX_COMPUTE_UNIT_LIMIT,
+    },
+    filters,
+    lookup_table::{
+        assign_lookup_table_addresses, create_market_lookup_table, extend_market_lookup_table,
+        get_lookup_table, get_market_reserves, market_lookup_table_addresses,
+    },
+    priority_fee::{estimate_compute_unit_price, PriorityFeeConfig},
+    rent::{get_rent, rent_exempt_total, LendingAccount, INIT_RESERVE_ACCOUNTS},
+    version::get_program_version,
+};
 use solend_program::{
     instruction::set_lending_market_owner_and_config,
     state::{validate_reserve_config, RateLimiterConfig},
@@ -16,7 +37,12 @@ use solend_sdk::{
     state::ReserveType,
 };
 
+mod idl;
 mod lending_state;
+mod output;
+mod profile;
+
+use profile::Profile;
 
 use {
     clap::{
@@ -25,20 +51,31 @@ use {
     },
     solana_clap_utils::{
         fee_payer::fee_payer_arg,
-        input_parsers::{keypair_of, pubkey_of, value_of},
-        input_validators::{is_amount, is_keypair, is_parsable, is_pubkey, is_url},
-        keypair::signer_from_path,
+        input_parsers::{pubkey_of, pubkey_of_signer, pubkeys_of, value_of},
+        input_validators::{
+            is_amount, is_parsable, is_pubkey, is_pubkey_sig, is_url, is_valid_pubkey,
+            is_valid_signer,
+        },
+        keypair::{signer_from_path_with_config, SignerFromPathConfig},
+        nonce::{NonceArgs, NONCE_ARG, NONCE_AUTHORITY_ARG},
+        offline::{blockhash_arg, sign_only_arg, BLOCKHASH_ARG, SIGNER_ARG, SIGN_ONLY_ARG},
     },
+    solana_client::blockhash_query::BlockhashQuery,
+    solana_client::client_error::ClientErrorKind,
     solana_client::rpc_client::RpcClient,
     solana_program::{
-        message::Message, native_token::lamports_to_sol, program_pack::Pack, pubkey::Pubkey,
+        instruction::Instruction, message::Message, native_token::lamports_to_sol,
+        program_pack::Pack, pubkey::Pubkey, sysvar,
     },
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_sdk::{
+        address_lookup_table_account::AddressLookupTableAccount,
         commitment_config::CommitmentConfig,
-        signature::{Keypair, Signer},
+        signature::{Keypair, Signature, Signer, SignerError},
         system_instruction,
-        transaction::Transaction,
+        transaction::{Transaction, TransactionError},
     },
+    solend_error_catalog::ErrorCatalog,
     solend_sdk::{
         self,
         instruction::{init_lending_market, init_reserve, update_reserve_config},
@@ -47,16 +84,26 @@ use {
     },
     spl_token::{
         amount_to_ui_amount,
-        instruction::{approve, revoke},
         state::{Account as Token, Mint},
         ui_amount_to_amount,
     },
-    std::{borrow::Borrow, process::exit, str::FromStr},
+    std::{
+        borrow::Borrow, fs, path::Path, process::exit, str::FromStr, sync::Arc, thread,
+        time::Duration,
+    },
     system_instruction::create_account,
 };
 
 use spl_associated_token_account::get_associated_token_address;
 use spl_associated_token_account::instruction::create_associated_token_account;
+use spl_forks_pdas::find_lending_market_metadata_address;
+
+/// Lowest priority fee paid for liquidations when none is given, in micro-lamports per compute
+/// unit. Liquidations pay more when recent transactions on the same accounts did.
+const DEFAULT_LIQUIDATION_COMPUTE_UNIT_PRICE: u64 = 30101;
+
+/// Name of the `--dump-transaction-message` argument
+const DUMP_TRANSACTION_MESSAGE_ARG: &str = "dump_transaction_message";
 
 struct Config {
     rpc_client: RpcClient,
@@ -64,6 +111,14 @@ struct Config {
     lending_program_id: Pubkey,
     verbose: bool,
     dry_run: bool,
+    compute_budget: ComputeBudgetConfig,
+    sign_only: bool,
+    dump_transaction_message: bool,
+    blockhash_query: BlockhashQuery,
+    nonce_account: Option<Pubkey>,
+    nonce_authority: Option<Box<dyn Signer>>,
+    output_format: OutputFormat,
+    profile: Profile,
 }
 
 /// Reserve config with optional fields
@@ -161,6 +216,23 @@ fn main() {
                 .validator(is_url)
                 .help("JSON RPC URL for the cluster.  Default from the configuration file."),
         )
+        .arg(
+            Arg::with_name("profile")
+                .long("profile")
+                .value_name("NAME")
+                .takes_value(true)
+                .global(true)
+                .help("Profile supplying the URL, program, market and named reserves. Either a profile from the \
+                       profiles file or a preset: mainnet"),
+        )
+        .arg(
+            Arg::with_name("profiles_file")
+                .long("profiles")
+                .value_name("PATH")
+                .takes_value(true)
+                .global(true)
+                .help("Profiles file to use [default: ~/.config/solend/profiles.yml]"),
+        )
         .arg(
             fee_payer_arg()
                 .short("p")
@@ -191,17 +263,68 @@ fn main() {
                 .global(true)
                 .help("Simulate transaction instead of executing"),
         )
+        .arg(
+            Arg::with_name("compute_unit_price")
+                .long("with-compute-unit-price")
+                .value_name("MICRO-LAMPORTS")
+                .takes_value(true)
+                .global(true)
+                .validator(is_parsable::<u64>)
+                .help("Priority fee per compute unit, in micro-lamports"),
+        )
+        .arg(
+            Arg::with_name("compute_unit_margin")
+                .long("compute-unit-margin")
+                .value_name("BPS")
+                .takes_value(true)
+                .global(true)
+                .validator(is_parsable::<u16>)
+                .default_value("1000")
+                .help("Margin added to simulated compute units, in basis points"),
+        )
+        .arg(
+            Arg::with_name("output_format")
+                .long("output")
+                .value_name("FORMAT")
+                .takes_value(true)
+                .global(true)
+                .possible_values(&["display", "json", "json-compact"])
+                .default_value("display")
+                .help("Return information in specified output format"),
+        )
+        .arg(blockhash_arg().global(true))
+        .arg(sign_only_arg().global(true))
+        .arg(
+            Arg::with_name(DUMP_TRANSACTION_MESSAGE_ARG)
+                .long("dump-transaction-message")
+                .takes_value(false)
+                .global(true)
+                .conflicts_with_all(&["dry_run", SIGN_ONLY_ARG.name])
+                .help("Print the unsigned transaction message of an admin command, base64 encoded, instead of signing \
+                       and sending it. Signers may be given as bare pubkeys"),
+        )
+        .arg(
+            Arg::with_name(SIGNER_ARG.name)
+                .long(SIGNER_ARG.long)
+                .takes_value(true)
+                .value_name("PUBKEY=SIGNATURE")
+                .validator(is_pubkey_sig)
+                .requires(BLOCKHASH_ARG.name)
+                .multiple(true)
+                .global(true)
+                .help(SIGNER_ARG.help),
+        )
+        .nonce_args(true)
         .subcommand(
             SubCommand::with_name("view-reserve")
                 .about("View reserve")
                 .arg(
                     Arg::with_name("reserve")
                         .long("reserve")
-                        .validator(is_pubkey)
-                        .value_name("PUBKEY")
+                        .value_name("PUBKEY_OR_NAME")
                         .takes_value(true)
                         .required(true)
-                        .help("reserve pubkey"),
+                        .help("reserve pubkey, or the name of a reserve of --profile"),
                 )
         )
         .subcommand(
@@ -213,14 +336,17 @@ fn main() {
                         .validator(is_pubkey)
                         .value_name("PUBKEY")
                         .takes_value(true)
-                        .required(true)
-                        .help("market pubkey"),
+                        .help("market pubkey. Default from --profile"),
                 )
         )
         .subcommand(
             SubCommand::with_name("view-all-markets")
                 .about("View all markets")
         )
+        .subcommand(
+            SubCommand::with_name("program-version")
+                .about("View the version and features of the lending program")
+        )
         .subcommand(
             SubCommand::with_name("view-obligation")
                 .about("View obligation")
@@ -291,10 +417,10 @@ fn main() {
                 .arg(
                     Arg::with_name("repay-reserve")
                         .long("repay-reserve")
-                        .value_name("RESERVE_PUBKEY")
+                        .value_name("RESERVE_PUBKEY_OR_NAME")
                         .takes_value(true)
                         .required(true)
-                        .help("repay reserve"),
+                        .help("repay reserve, or the name of a reserve of --profile"),
                 )
                 .arg(
                     Arg::with_name("source-liquidity")
@@ -307,10 +433,10 @@ fn main() {
                 .arg(
                     Arg::with_name("withdraw-reserve")
                         .long("withdraw-reserve")
-                        .value_name("RESERVE_PUBKEY")
+                        .value_name("RESERVE_PUBKEY_OR_NAME")
                         .takes_value(true)
                         .required(true)
-                        .help("withdraw reserve"),
+                        .help("withdraw reserve, or the name of a reserve of --profile"),
                 )
                 .arg(
                     Arg::with_name("liquidity-amount")
@@ -336,10 +462,10 @@ fn main() {
                 .arg(
                     Arg::with_name("withdraw-reserve")
                         .long("withdraw-reserve")
-                        .value_name("RESERVE_PUBKEY")
+                        .value_name("RESERVE_PUBKEY_OR_NAME")
                         .takes_value(true)
                         .required(true)
-                        .help("reserve that you want to withdraw ctokens from"),
+                        .help("reserve that you want to withdraw ctokens from, or its name in --profile"),
                 )
                 .arg(
                     Arg::with_name("collateral-amount")
@@ -357,10 +483,10 @@ fn main() {
                 .arg(
                     Arg::with_name("redeem-reserve")
                         .long("redeem-reserve")
-                        .value_name("RESERVE_PUBKEY")
+                        .value_name("RESERVE_PUBKEY_OR_NAME")
                         .takes_value(true)
                         .required(true)
-                        .help("reserve pubkey"),
+                        .help("reserve pubkey, or the name of a reserve of --profile"),
                 )
                 .arg(
                     Arg::with_name("collateral-amount")
@@ -371,25 +497,77 @@ fn main() {
                         .help("amount of ctokens to redeem"),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("sync-lookup-table")
+                .about("Create or extend an address lookup table with a lending market's accounts")
+                .arg(
+                    Arg::with_name("lending_market")
+                        .long("market")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("Lending market address. Default from --profile"),
+                )
+                .arg(
+                    Arg::with_name("lookup_table")
+                        .long("lookup-table")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help("Existing lookup table owned by the fee payer, may be repeated. New ones are created \
+                               for the addresses that don't fit"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("download-fixtures")
+                .about("Save a lending market's accounts as test fixtures")
+                .arg(
+                    Arg::with_name("lending_market")
+                        .long("market")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("Lending market address. Default from --profile"),
+                )
+                .arg(
+                    Arg::with_name("out_dir")
+                        .long("out-dir")
+                        .value_name("DIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Directory the account files are written to, one JSON file per account"),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("export-idl")
+                .about("Print the lending program's instructions, accounts, types and errors as a Shank IDL")
+                .arg(
+                    Arg::with_name("out")
+                        .long("out")
+                        .value_name("FILE")
+                        .takes_value(true)
+                        .help("File the IDL is written to instead of stdout"),
+                )
+        )
         .subcommand(
             SubCommand::with_name("add-reserve")
                 .about("Add a reserve to a lending market")
-                // @TODO: use is_valid_signer
                 .arg(
                     Arg::with_name("lending_market_owner")
                         .long("market-owner")
-                        .validator(is_keypair)
-                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .value_name("SIGNER")
                         .takes_value(true)
                         .required(true)
                         .help("Owner of the lending market"),
                 )
-                // @TODO: use is_valid_signer
                 .arg(
                     Arg::with_name("source_liquidity_owner")
                         .long("source-owner")
-                        .validator(is_keypair)
-                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .value_name("SIGNER")
                         .takes_value(true)
                         .required(true)
                         .help("Owner of the SPL Token account to deposit initial liquidity from"),
@@ -400,8 +578,7 @@ fn main() {
                         .validator(is_pubkey)
                         .value_name("PUBKEY")
                         .takes_value(true)
-                        .required(true)
-                        .help("Lending market address"),
+                        .help("Lending market address. Default from --profile"),
                 )
                 .arg(
                     Arg::with_name("source_liquidity")
@@ -649,8 +826,8 @@ fn main() {
                 .arg(
                     Arg::with_name("lending_market_owner")
                         .long("lending-market-owner")
-                        .validator(is_keypair)
-                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .value_name("SIGNER")
                         .takes_value(true)
                         .required(true)
                         .help("Owner of the lending market"),
@@ -661,14 +838,13 @@ fn main() {
                         .validator(is_pubkey)
                         .value_name("PUBKEY")
                         .takes_value(true)
-                        .required(true)
-                        .help("Lending market address"),
+                        .help("Lending market address. Default from --profile"),
                 )
                 .arg(
                     Arg::with_name("new_lending_market_owner")
                         .long("new-lending-market-owner")
-                        .validator(is_keypair)
-                        .value_name("KEYPAIR")
+                        .validator(is_valid_pubkey)
+                        .value_name("PUBKEY")
                         .takes_value(true)
                         .required(false)
                         .help("Owner of the lending market"),
@@ -702,7 +878,7 @@ fn main() {
                 .arg(
                     Arg::with_name("risk_authority")
                         .long("risk-authority")
-                        .validator(is_pubkey)
+                        .validator(is_valid_pubkey)
                         .value_name("PUBKEY")
                         .takes_value(true)
                         .required(false)
@@ -715,20 +891,19 @@ fn main() {
                 .arg(
                     Arg::with_name("reserve")
                         .long("reserve")
-                        .validator(is_pubkey)
-                        .value_name("PUBKEY")
+                        .value_name("PUBKEY_OR_NAME")
                         .takes_value(true)
                         .required(true)
-                        .help("Reserve address"),
+                        .help("Reserve address, or the name of a reserve of --profile"),
                 )
                 .arg(
                     Arg::with_name("lending_market_owner")
                         .long("market-owner")
-                        .validator(is_keypair)
-                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .value_name("SIGNER")
                         .takes_value(true)
                         .required(true)
-                        .help("Owner of the lending market"),
+                        .help("Owner of the lending market, or its risk authority"),
                 )
                 // @TODO: use is_valid_signer
                 .arg(
@@ -737,8 +912,7 @@ fn main() {
                         .validator(is_pubkey)
                         .value_name("PUBKEY")
                         .takes_value(true)
-                        .required(true)
-                        .help("Lending market address"),
+                        .help("Lending market address. Default from --profile"),
                 )
                 .arg(
                     Arg::with_name("optimal_utilization_rate")
@@ -984,25 +1158,57 @@ fn main() {
         } else {
             solana_cli_config::Config::default()
         };
+        let profile = match matches.value_of("profile") {
+            Some(name) => unwrap_or_exit(Profile::load(name, matches.value_of("profiles_file"))),
+            None => Profile::default(),
+        };
         let json_rpc_url = value_t!(matches, "json_rpc_url", String)
-            .unwrap_or_else(|_| cli_config.json_rpc_url.clone());
+            .ok()
+            .or_else(|| profile.json_rpc_url.clone())
+            .unwrap_or_else(|| cli_config.json_rpc_url.clone());
 
-        let fee_payer = signer_from_path(
+        let fee_payer = signer_from_path_with_config(
             &matches,
             matches
                 .value_of("fee_payer")
                 .unwrap_or(&cli_config.keypair_path),
             "fee_payer",
             &mut wallet_manager,
+            &signer_from_path_config(&matches),
         )
         .unwrap_or_else(|e| {
             eprintln!("error: {}", e);
             exit(1);
         });
 
-        let lending_program_id = pubkey_of(&matches, "lending_program_id").unwrap();
+        let lending_program_id = match profile.lending_program_id {
+            Some(program_id) if matches.occurrences_of("lending_program_id") == 0 => program_id,
+            _ => pubkey_of(&matches, "lending_program_id").unwrap(),
+        };
         let verbose = matches.is_present("verbose");
         let dry_run = matches.is_present("dry_run");
+        let sign_only = matches.is_present(SIGN_ONLY_ARG.name);
+        let dump_transaction_message = matches.is_present(DUMP_TRANSACTION_MESSAGE_ARG);
+        let blockhash_query = BlockhashQuery::new_from_matches(&matches);
+        let nonce_account = pubkey_of(&matches, NONCE_ARG.name);
+        let nonce_authority = matches.value_of(NONCE_AUTHORITY_ARG.name).map(|path| {
+            signer_from_path_with_config(
+                &matches,
+                path,
+                NONCE_AUTHORITY_ARG.name,
+                &mut wallet_manager,
+                &signer_from_path_config(&matches),
+            )
+            .unwrap_or_else(|e| {
+                eprintln!("error: {}", e);
+                exit(1);
+            })
+        });
+        let compute_budget = ComputeBudgetConfig {
+            compute_unit_margin_bps: value_t!(matches, "compute_unit_margin", u16).unwrap(),
+            compute_unit_price: value_of(&matches, "compute_unit_price"),
+        };
+        let output_format = value_t!(matches, "output_format", OutputFormat).unwrap();
 
         Config {
             rpc_client: RpcClient::new_with_commitment(json_rpc_url, CommitmentConfig::confirmed()),
@@ -1010,28 +1216,57 @@ fn main() {
             lending_program_id,
             verbose,
             dry_run,
+            compute_budget,
+            sign_only,
+            dump_transaction_message,
+            blockhash_query,
+            nonce_account,
+            nonce_authority,
+            output_format,
+            profile,
         }
     };
 
     let _ = match matches.subcommand() {
         ("view-reserve", Some(arg_matches)) => {
-            let reserve = pubkey_of(arg_matches, "reserve").unwrap();
+            let reserve = unwrap_or_exit(config.profile.reserve_of(arg_matches, "reserve"));
             let data = config.rpc_client.get_account_data(&reserve).unwrap();
-            print!("{:#?}", Reserve::unpack(&data));
+            if config.output_format.is_display() {
+                print!("{:#?}", Reserve::unpack(&data));
+            } else {
+                let state = Reserve::unpack(&data).unwrap();
+                config
+                    .output_format
+                    .print_json(&CliReserve::new(&reserve, &state));
+            }
 
             Ok(())
         }
         ("view-market", Some(arg_matches)) => {
-            let market = pubkey_of(arg_matches, "market").unwrap();
+            let market = unwrap_or_exit(config.profile.lending_market_of(arg_matches, "market"));
             let data = config.rpc_client.get_account_data(&market).unwrap();
-            print!("{:#?}", LendingMarket::unpack(&data));
+            if config.output_format.is_display() {
+                print!("{:#?}", LendingMarket::unpack(&data));
+            } else {
+                let state = LendingMarket::unpack(&data).unwrap();
+                config
+                    .output_format
+                    .print_json(&CliLendingMarket::new(&market, &state));
+            }
 
             Ok(())
         }
         ("view-obligation", Some(arg_matches)) => {
             let obligation = pubkey_of(arg_matches, "obligation").unwrap();
             let data = config.rpc_client.get_account_data(&obligation).unwrap();
-            print!("{:#?}", Obligation::unpack(&data));
+            if config.output_format.is_display() {
+                print!("{:#?}", Obligation::unpack(&data));
+            } else {
+                let state = Obligation::unpack(&data).unwrap();
+                config
+                    .output_format
+                    .print_json(&CliObligation::new(&obligation, &state));
+            }
 
             Ok(())
         }
@@ -1041,7 +1276,7 @@ fn main() {
                 .get_program_accounts_with_config(
                     &config.lending_program_id,
                     RpcProgramAccountsConfig {
-                        filters: Some(vec![RpcFilterType::DataSize(LendingMarket::LEN as u64)]),
+                        filters: Some(filters::lending_markets()),
                         account_config: RpcAccountInfoConfig {
                             encoding: Some(UiAccountEncoding::Base64Zstd),
                             ..RpcAccountInfoConfig::default()
@@ -1051,8 +1286,51 @@ fn main() {
                 )
                 .unwrap();
 
-            for (address, _) in accounts {
-                println!("{}", address);
+            if config.output_format.is_display() {
+                for (address, _) in accounts {
+                    println!("{}", address);
+                }
+            } else {
+                config.output_format.print_json(&CliLendingMarkets {
+                    lending_markets: accounts
+                        .into_iter()
+                        .map(|(address, _)| address.to_string())
+                        .collect(),
+                });
+            }
+
+            Ok(())
+        }
+        ("program-version", Some(_arg_matches)) => {
+            let version = get_program_version(
+                &config.rpc_client,
+                &config.lending_program_id,
+                &config.fee_payer.pubkey(),
+            )
+            .unwrap_or_else(|err| {
+                eprintln!("error: {}", err);
+                exit(1);
+            });
+            let output = CliProgramVersion {
+                program_id: config.lending_program_id.to_string(),
+                version: version.map(|v| format!("{}.{}.{}", v.major, v.minor, v.patch)),
+                features: version.map_or(0, |v| v.features),
+                feature_names: version
+                    .map(|v| v.feature_names().into_iter().map(str::to_string).collect())
+                    .unwrap_or_default(),
+            };
+            if config.output_format.is_display() {
+                match &output.version {
+                    Some(version) => println!(
+                        "{} {}: {}",
+                        output.program_id,
+                        version,
+                        output.feature_names.join(", ")
+                    ),
+                    None => println!("{}: predates GetVersion", output.program_id),
+                }
+            } else {
+                config.output_format.print_json(&output);
             }
 
             Ok(())
@@ -1074,9 +1352,11 @@ fn main() {
         }
         ("liquidate-obligation", Some(arg_matches)) => {
             let obligation = pubkey_of(arg_matches, "obligation").unwrap();
-            let repay_reserve = pubkey_of(arg_matches, "repay-reserve").unwrap();
+            let repay_reserve =
+                unwrap_or_exit(config.profile.reserve_of(arg_matches, "repay-reserve"));
             let source_liquidity = pubkey_of(arg_matches, "source-liquidity").unwrap();
-            let withdraw_reserve = pubkey_of(arg_matches, "withdraw-reserve").unwrap();
+            let withdraw_reserve =
+                unwrap_or_exit(config.profile.reserve_of(arg_matches, "withdraw-reserve"));
             let liquidity_amount = value_of(arg_matches, "liquidity-amount").unwrap();
 
             command_liquidate_obligation(
@@ -1090,23 +1370,52 @@ fn main() {
         }
         ("withdraw-collateral", Some(arg_matches)) => {
             let obligation = pubkey_of(arg_matches, "obligation").unwrap();
-            let withdraw_reserve = pubkey_of(arg_matches, "withdraw-reserve").unwrap();
+            let withdraw_reserve =
+                unwrap_or_exit(config.profile.reserve_of(arg_matches, "withdraw-reserve"));
             let collateral_amount = value_of(arg_matches, "collateral-amount").unwrap();
 
             command_withdraw_collateral(&config, obligation, withdraw_reserve, collateral_amount)
         }
         ("redeem-collateral", Some(arg_matches)) => {
-            let redeem_reserve = pubkey_of(arg_matches, "redeem-reserve").unwrap();
+            let redeem_reserve =
+                unwrap_or_exit(config.profile.reserve_of(arg_matches, "redeem-reserve"));
             let collateral_amount = value_of(arg_matches, "collateral-amount").unwrap();
 
             command_redeem_collateral(&config, &redeem_reserve, collateral_amount)
         }
+        ("sync-lookup-table", Some(arg_matches)) => {
+            let lending_market_pubkey = unwrap_or_exit(
+                config
+                    .profile
+                    .lending_market_of(arg_matches, "lending_market"),
+            );
+            let lookup_table_pubkeys = pubkeys_of(arg_matches, "lookup_table").unwrap_or_default();
+
+            command_sync_lookup_table(&config, &lending_market_pubkey, &lookup_table_pubkeys)
+        }
+        ("download-fixtures", Some(arg_matches)) => {
+            let lending_market_pubkey = unwrap_or_exit(
+                config
+                    .profile
+                    .lending_market_of(arg_matches, "lending_market"),
+            );
+            let out_dir = arg_matches.value_of("out_dir").unwrap();
+
+            command_download_fixtures(&config, &lending_market_pubkey, Path::new(out_dir))
+        }
+        ("export-idl", Some(arg_matches)) => {
+            command_export_idl(&config, arg_matches.value_of("out").map(Path::new))
+        }
         ("add-reserve", Some(arg_matches)) => {
-            let lending_market_owner_keypair =
-                keypair_of(arg_matches, "lending_market_owner").unwrap();
-            let source_liquidity_owner_keypair =
-                keypair_of(arg_matches, "source_liquidity_owner").unwrap();
-            let lending_market_pubkey = pubkey_of(arg_matches, "lending_market").unwrap();
+            let lending_market_owner =
+                signer_of(arg_matches, "lending_market_owner", &mut wallet_manager);
+            let source_liquidity_owner =
+                signer_of(arg_matches, "source_liquidity_owner", &mut wallet_manager);
+            let lending_market_pubkey = unwrap_or_exit(
+                config
+                    .profile
+                    .lending_market_of(arg_matches, "lending_market"),
+            );
             let source_liquidity_pubkey = pubkey_of(arg_matches, "source_liquidity").unwrap();
             let ui_amount = value_of(arg_matches, "liquidity_amount").unwrap();
             let pyth_product_pubkey = pubkey_of(arg_matches, "pyth_product").unwrap();
@@ -1188,9 +1497,9 @@ fn main() {
                     reserve_type,
                 },
                 source_liquidity_pubkey,
-                source_liquidity_owner_keypair,
+                source_liquidity_owner,
                 lending_market_pubkey,
-                lending_market_owner_keypair,
+                lending_market_owner,
                 pyth_product_pubkey,
                 pyth_price_pubkey,
                 switchboard_feed_pubkey,
@@ -1199,21 +1508,30 @@ fn main() {
             )
         }
         ("set-lending-market-owner-and-config", Some(arg_matches)) => {
-            let lending_market_owner_keypair =
-                keypair_of(arg_matches, "lending_market_owner").unwrap();
-            let lending_market_pubkey = pubkey_of(arg_matches, "lending_market").unwrap();
-            let new_lending_market_owner_keypair =
-                keypair_of(arg_matches, "new_lending_market_owner");
+            let lending_market_owner =
+                signer_of(arg_matches, "lending_market_owner", &mut wallet_manager);
+            let lending_market_pubkey = unwrap_or_exit(
+                config
+                    .profile
+                    .lending_market_of(arg_matches, "lending_market"),
+            );
+            let new_lending_market_owner_pubkey = pubkey_of_signer_or_exit(
+                arg_matches,
+                "new_lending_market_owner",
+                &mut wallet_manager,
+            );
             let rate_limiter_window_duration =
                 value_of(arg_matches, "rate_limiter_window_duration");
             let rate_limiter_max_outflow = value_of(arg_matches, "rate_limiter_max_outflow");
             let whitelisted_liquidator_pubkey = pubkey_of(arg_matches, "whitelisted_liquidator");
-            let risk_authority_pubkey = pubkey_of(arg_matches, "risk_authority").unwrap();
+            let risk_authority_pubkey =
+                pubkey_of_signer_or_exit(arg_matches, "risk_authority", &mut wallet_manager)
+                    .unwrap();
             command_set_lending_market_owner_and_config(
                 &mut config,
                 lending_market_pubkey,
-                lending_market_owner_keypair,
-                new_lending_market_owner_keypair,
+                lending_market_owner,
+                new_lending_market_owner_pubkey,
                 rate_limiter_window_duration,
                 rate_limiter_max_outflow,
                 whitelisted_liquidator_pubkey,
@@ -1221,10 +1539,14 @@ fn main() {
             )
         }
         ("update-reserve", Some(arg_matches)) => {
-            let reserve_pubkey = pubkey_of(arg_matches, "reserve").unwrap();
-            let lending_market_owner_keypair =
-                keypair_of(arg_matches, "lending_market_owner").unwrap();
-            let lending_market_pubkey = pubkey_of(arg_matches, "lending_market").unwrap();
+            let reserve_pubkey = unwrap_or_exit(config.profile.reserve_of(arg_matches, "reserve"));
+            let lending_market_owner =
+                signer_of(arg_matches, "lending_market_owner", &mut wallet_manager);
+            let lending_market_pubkey = unwrap_or_exit(
+                config
+                    .profile
+                    .lending_market_of(arg_matches, "lending_market"),
+            );
             let optimal_utilization_rate = value_of(arg_matches, "optimal_utilization_rate");
             let max_utilization_rate = value_of(arg_matches, "max_utilization_rate");
             let loan_to_value_ratio = value_of(arg_matches, "loan_to_value_ratio");
@@ -1290,7 +1612,7 @@ fn main() {
                 switchboard_feed_pubkey,
                 reserve_pubkey,
                 lending_market_pubkey,
-                lending_market_owner_keypair,
+                lending_market_owner,
             )
         }
         _ => unreachable!(),
@@ -1310,15 +1632,17 @@ fn command_create_lending_market(
     oracle_program_id: Pubkey,
     switchboard_oracle_program_id: Pubkey,
 ) -> CommandResult {
+    check_online(config)?;
+
     let lending_market_keypair = Keypair::new();
-    println!(
+    println_display!(
+        config,
         "Creating lending market {}",
         lending_market_keypair.pubkey()
     );
 
-    let lending_market_balance = config
-        .rpc_client
-        .get_minimum_balance_for_rent_exemption(LendingMarket::LEN)?;
+    let lending_market_balance =
+        LendingAccount::LendingMarket.rent_exempt_minimum(&get_rent(&config.rpc_client)?);
 
     let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
 
@@ -1356,16 +1680,20 @@ fn command_create_lending_market(
         message,
         recent_blockhash,
     );
-    send_transaction(config, transaction)?;
+    let mut output = CliTransactionOutput::default();
+    output.add_signature(send_transaction(config, transaction)?);
 
     let lending_market_pubkey = lending_market_keypair.pubkey();
     let lending_market_account = config.rpc_client.get_account(&lending_market_pubkey)?;
     let lending_market = LendingMarket::unpack_from_slice(lending_market_account.data.borrow())?;
     let authority_signer_seeds = &[lending_market_pubkey.as_ref(), &[lending_market.bump_seed]];
-    println!(
-        "Authority Address {}",
-        Pubkey::create_program_address(authority_signer_seeds, &config.lending_program_id)?,
-    );
+    let lending_market_authority =
+        Pubkey::create_program_address(authority_signer_seeds, &config.lending_program_id)?;
+    println_display!(config, "Authority Address {}", lending_market_authority);
+
+    output.add_address("lendingMarket", &lending_market_pubkey);
+    output.add_address("lendingMarketAuthority", &lending_market_authority);
+    config.output_format.print_json(&output);
     Ok(())
 }
 
@@ -1375,6 +1703,8 @@ fn command_redeem_collateral(
     redeem_reserve_pubkey: &Pubkey,
     collateral_amount: u64,
 ) -> CommandResult {
+    check_online(config)?;
+
     let redeem_reserve = {
         let data = config
             .rpc_client
@@ -1388,28 +1718,35 @@ fn command_redeem_collateral(
     let dest_ata =
         get_or_create_associated_token_address(config, &redeem_reserve.liquidity.mint_pubkey);
 
+    let instructions = budget_instructions(
+        config,
+        &[redeem_reserve_collateral(
+            config.lending_program_id,
+            collateral_amount,
+            source_ata,
+            dest_ata,
+            *redeem_reserve_pubkey,
+            redeem_reserve.collateral.mint_pubkey,
+            redeem_reserve.liquidity.supply_pubkey,
+            redeem_reserve.lending_market,
+            config.fee_payer.pubkey(),
+        )],
+        &config.compute_budget,
+    )?;
     let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
     let transaction = Transaction::new(
         &vec![config.fee_payer.as_ref()],
         Message::new_with_blockhash(
-            &[redeem_reserve_collateral(
-                config.lending_program_id,
-                collateral_amount,
-                source_ata,
-                dest_ata,
-                *redeem_reserve_pubkey,
-                redeem_reserve.collateral.mint_pubkey,
-                redeem_reserve.liquidity.supply_pubkey,
-                redeem_reserve.lending_market,
-                config.fee_payer.pubkey(),
-            )],
+            &instructions,
             Some(&config.fee_payer.pubkey()),
             &recent_blockhash,
         ),
         recent_blockhash,
     );
 
-    send_transaction(config, transaction)?;
+    let mut output = CliTransactionOutput::default();
+    output.add_signature(send_transaction(config, transaction)?);
+    config.output_format.print_json(&output);
 
     Ok(())
 }
@@ -1421,6 +1758,8 @@ fn command_withdraw_collateral(
     withdraw_reserve_pubkey: Pubkey,
     collateral_amount: u64,
 ) -> CommandResult {
+    check_online(config)?;
+
     let solend_state = SolendState::new(
         config.lending_program_id,
         obligation_pubkey,
@@ -1434,7 +1773,11 @@ fn command_withdraw_collateral(
     // make atas
     get_or_create_associated_token_address(config, &withdraw_reserve.collateral.mint_pubkey);
 
-    let instructions = solend_state.withdraw(&withdraw_reserve_pubkey, collateral_amount);
+    let instructions = budget_instructions(
+        config,
+        &solend_state.withdraw(&withdraw_reserve_pubkey, collateral_amount),
+        &config.compute_budget,
+    )?;
     let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
     let transaction = Transaction::new(
         &vec![config.fee_payer.as_ref()],
@@ -1446,7 +1789,9 @@ fn command_withdraw_collateral(
         recent_blockhash,
     );
 
-    send_transaction(config, transaction)?;
+    let mut output = CliTransactionOutput::default();
+    output.add_signature(send_transaction(config, transaction)?);
+    config.output_format.print_json(&output);
 
     Ok(())
 }
@@ -1460,6 +1805,8 @@ fn command_liquidate_obligation(
     withdraw_reserve_pubkey: Pubkey,
     liquidity_amount: u64,
 ) -> CommandResult {
+    check_online(config)?;
+
     let obligation_state = {
         let data = config.rpc_client.get_account(&obligation_pubkey)?;
         Obligation::unpack(&data.data)?
@@ -1474,13 +1821,12 @@ fn command_liquidate_obligation(
     };
 
     // get reserve accounts
-    let reserves: Vec<(Pubkey, Reserve)> = config
-        .rpc_client
-        .get_multiple_accounts(&reserve_pubkeys)?
-        .into_iter()
-        .zip(reserve_pubkeys.iter())
-        .map(|(account, pubkey)| (*pubkey, Reserve::unpack(&account.unwrap().data).unwrap()))
-        .collect();
+    let reserves: Vec<(Pubkey, Reserve)> =
+        get_multiple_reserves(&config.rpc_client, &reserve_pubkeys)?
+            .into_iter()
+            .zip(reserve_pubkeys.iter())
+            .map(|(reserve, pubkey)| (*pubkey, reserve.unwrap()))
+            .collect();
 
     assert!(reserve_pubkeys.len() == reserves.len());
 
@@ -1525,7 +1871,7 @@ fn command_liquidate_obligation(
         &withdraw_reserve_state.liquidity.mint_pubkey,
     );
 
-    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_price(30101)];
+    let mut instructions = vec![];
 
     // refresh all reserves
     instructions.extend(reserves.iter().map(|(pubkey, reserve)| {
@@ -1562,6 +1908,41 @@ fn command_liquidate_obligation(
         config.fee_payer.pubkey(),
     ));
 
+    // liquidations compete for inclusion, so always pay a priority fee, at least the default and
+    // more when recent transactions on the same accounts paid more
+    let compute_unit_price = match config.compute_budget.compute_unit_price {
+        Some(price) => price,
+        None => estimate_compute_unit_price(
+            &config.rpc_client,
+            &instructions,
+            &PriorityFeeConfig {
+                min_compute_unit_price: DEFAULT_LIQUIDATION_COMPUTE_UNIT_PRICE,
+                ..PriorityFeeConfig::default()
+            },
+        )
+        .unwrap_or_else(|err| {
+            println_display!(
+                config,
+                "Couldn't estimate the priority fee, paying {}: {}",
+                DEFAULT_LIQUIDATION_COMPUTE_UNIT_PRICE,
+                err
+            );
+            DEFAULT_LIQUIDATION_COMPUTE_UNIT_PRICE
+        }),
+    };
+    if config.verbose {
+        println_display!(
+            config,
+            "Priority fee: {} micro-lamports per compute unit",
+            compute_unit_price
+        );
+    }
+    let compute_budget = ComputeBudgetConfig {
+        compute_unit_price: Some(compute_unit_price),
+        ..config.compute_budget
+    };
+    let instructions = budget_instructions(config, &instructions, &compute_budget)?;
+
     let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
     let transaction = Transaction::new(
         &vec![config.fee_payer.as_ref()],
@@ -1573,81 +1954,234 @@ fn command_liquidate_obligation(
         recent_blockhash,
     );
 
-    send_transaction(config, transaction)?;
+    let mut output = CliTransactionOutput::default();
+    output.add_signature(send_transaction(config, transaction)?);
+    config.output_format.print_json(&output);
 
     Ok(())
 }
 
+fn command_sync_lookup_table(
+    config: &Config,
+    lending_market_pubkey: &Pubkey,
+    lookup_table_pubkeys: &[Pubkey],
+) -> CommandResult {
+    check_online(config)?;
+
+    let reserves = get_market_reserves(
+        &config.rpc_client,
+        &config.lending_program_id,
+        lending_market_pubkey,
+    )?;
+    let addresses =
+        market_lookup_table_addresses(&config.lending_program_id, lending_market_pubkey, &reserves);
+    let mut lookup_tables = vec![];
+    for lookup_table_pubkey in lookup_table_pubkeys {
+        lookup_tables.push(get_lookup_table(&config.rpc_client, lookup_table_pubkey)?);
+    }
+    // a table holds at most 256 addresses, so large markets are split over several tables
+    let assigned = assign_lookup_table_addresses(&lookup_tables, &addresses);
+
+    let mut output = CliTransactionOutput::default();
+    let authority = config.fee_payer.pubkey();
+    let mut last_slot = None;
+    while lookup_tables.len() < assigned.len() {
+        // tables are derived from the authority and a recent slot, so each needs its own slot
+        let recent_slot = loop {
+            let slot = config.rpc_client.get_slot()?;
+            if Some(slot) > last_slot {
+                break slot;
+            }
+            thread::sleep(Duration::from_millis(400));
+        };
+        last_slot = Some(recent_slot);
+        let (instruction, lookup_table_pubkey) =
+            create_market_lookup_table(&authority, &authority, recent_slot);
+        println_display!(config, "Creating lookup table {}", lookup_table_pubkey);
+
+        let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
+        let transaction = Transaction::new(
+            &vec![config.fee_payer.as_ref()],
+            Message::new_with_blockhash(&[instruction], Some(&authority), &recent_blockhash),
+            recent_blockhash,
+        );
+        output.add_signature(send_transaction(config, transaction)?);
+
+        lookup_tables.push(AddressLookupTableAccount {
+            key: lookup_table_pubkey,
+            addresses: vec![],
+        });
+    }
+
+    for (lookup_table, addresses) in lookup_tables.iter().zip(assigned.iter()) {
+        let instructions =
+            extend_market_lookup_table(lookup_table, &authority, &authority, addresses);
+        for instruction in instructions {
+            let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
+            let transaction = Transaction::new(
+                &vec![config.fee_payer.as_ref()],
+                Message::new_with_blockhash(&[instruction], Some(&authority), &recent_blockhash),
+                recent_blockhash,
+            );
+            output.add_signature(send_transaction(config, transaction)?);
+        }
+    }
+
+    let keys: Vec<String> = lookup_tables
+        .iter()
+        .map(|table| table.key.to_string())
+        .collect();
+    println_display!(
+        config,
+        "Lookup tables {} cover {} reserves in market {}",
+        keys.join(", "),
+        reserves.len(),
+        lending_market_pubkey
+    );
+    for (index, lookup_table) in lookup_tables.iter().enumerate() {
+        let role = match index {
+            0 => "lookupTable".to_string(),
+            _ => format!("lookupTable{}", index),
+        };
+        output.add_address(&role, &lookup_table.key);
+    }
+    config.output_format.print_json(&output);
+    Ok(())
+}
+
+fn command_export_idl(config: &Config, out: Option<&Path>) -> CommandResult {
+    let idl = serde_json::to_string_pretty(&idl::Idl::new(&config.lending_program_id))?;
+    match out {
+        Some(path) => {
+            fs::write(path, idl)?;
+            println_display!(config, "Wrote IDL to {}", path.display());
+        }
+        None => println!("{}", idl),
+    }
+    Ok(())
+}
+
+fn command_download_fixtures(
+    config: &Config,
+    lending_market_pubkey: &Pubkey,
+    out_dir: &Path,
+) -> CommandResult {
+    let reserves = get_market_reserves(
+        &config.rpc_client,
+        &config.lending_program_id,
+        lending_market_pubkey,
+    )?;
+    let (metadata_pubkey, _bump_seed) =
+        find_lending_market_metadata_address(lending_market_pubkey, &config.lending_program_id);
+    // the lookup table set already covers the market, reserves, oracles, mints and supplies
+    let mut addresses =
+        market_lookup_table_addresses(&config.lending_program_id, lending_market_pubkey, &reserves);
+    addresses.push(metadata_pubkey);
+
+    fs::create_dir_all(out_dir)?;
+    let mut output = CliFixtures {
+        directory: out_dir.display().to_string(),
+        accounts: vec![],
+    };
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = config.rpc_client.get_multiple_accounts(chunk)?;
+        for (pubkey, account) in chunk.iter().zip(accounts) {
+            // programs, sysvars and unused PDAs are provided by the test environment
+            let account = match account {
+                Some(account) if !account.executable && account.owner != sysvar::id() => account,
+                _ => continue,
+            };
+            let fixture = CliAccount {
+                pubkey: pubkey.to_string(),
+                account: UiAccount::encode(pubkey, &account, UiAccountEncoding::Base64, None, None),
+            };
+            let path = out_dir.join(format!("{}.json", pubkey));
+            fs::write(&path, serde_json::to_string_pretty(&fixture)?)?;
+            output.accounts.push(pubkey.to_string());
+        }
+    }
+
+    println_display!(
+        config,
+        "Wrote {} accounts for market {} to {}",
+        output.accounts.len(),
+        lending_market_pubkey,
+        output.directory
+    );
+    config.output_format.print_json(&output);
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn command_add_reserve(
     config: &mut Config,
     liquidity_amount: u64,
     reserve_config: ReserveConfig,
     source_liquidity_pubkey: Pubkey,
-    source_liquidity_owner_keypair: Keypair,
+    source_liquidity_owner: Box<dyn Signer>,
     lending_market_pubkey: Pubkey,
-    lending_market_owner_keypair: Keypair,
+    lending_market_owner: Box<dyn Signer>,
     pyth_product_pubkey: Pubkey,
     pyth_price_pubkey: Pubkey,
     switchboard_feed_pubkey: Pubkey,
     liquidity_fee_receiver_keypair: Keypair,
     source_liquidity: Token,
 ) -> CommandResult {
+    // the owner signs a transaction that uses accounts made up by this run, which an offline
+    // signer can't reproduce
+    if config.sign_only {
+        return Err(
+            "add-reserve creates the reserve's accounts before the lending market owner \
+                    signs, so it can't be signed offline; use --dump-transaction-message"
+                .into(),
+        );
+    }
+
     let reserve_keypair = Keypair::new();
     let collateral_mint_keypair = Keypair::new();
     let collateral_supply_keypair = Keypair::new();
     let liquidity_supply_keypair = Keypair::new();
     let user_collateral_keypair = Keypair::new();
-    let user_transfer_authority_keypair = Keypair::new();
 
-    println!("Adding reserve {}", reserve_keypair.pubkey());
+    println_display!(config, "Adding reserve {}", reserve_keypair.pubkey());
     if config.verbose {
-        println!(
+        println_display!(
+            config,
             "Adding collateral mint {}",
             collateral_mint_keypair.pubkey()
         );
-        println!(
+        println_display!(
+            config,
             "Adding collateral supply {}",
             collateral_supply_keypair.pubkey()
         );
-        println!(
+        println_display!(
+            config,
             "Adding liquidity supply {}",
             liquidity_supply_keypair.pubkey()
         );
-        println!(
+        println_display!(
+            config,
             "Adding liquidity fee receiver {}",
             liquidity_fee_receiver_keypair.pubkey()
         );
-        println!(
+        println_display!(
+            config,
             "Adding user collateral {}",
             user_collateral_keypair.pubkey()
         );
-        println!(
-            "Adding user transfer authority {}",
-            user_transfer_authority_keypair.pubkey()
-        );
     }
 
-    let reserve_balance = config
-        .rpc_client
-        .get_minimum_balance_for_rent_exemption(Reserve::LEN)?;
-    let collateral_mint_balance = config
-        .rpc_client
-        .get_minimum_balance_for_rent_exemption(Mint::LEN)?;
-    let token_account_balance = config
-        .rpc_client
-        .get_minimum_balance_for_rent_exemption(Token::LEN)?;
+    let rent = get_rent(&config.rpc_client)?;
+    let reserve_balance = LendingAccount::Reserve.rent_exempt_minimum(&rent);
+    let collateral_mint_balance = LendingAccount::Mint.rent_exempt_minimum(&rent);
+    let token_account_balance = LendingAccount::TokenAccount.rent_exempt_minimum(&rent);
     let collateral_supply_balance = token_account_balance;
     let user_collateral_balance = token_account_balance;
     let liquidity_supply_balance = token_account_balance;
     let liquidity_fee_receiver_balance = token_account_balance;
 
-    let total_balance = reserve_balance
-        + collateral_mint_balance
-        + collateral_supply_balance
-        + user_collateral_balance
-        + liquidity_supply_balance
-        + liquidity_fee_receiver_balance;
+    let total_balance = rent_exempt_total(&rent, INIT_RESERVE_ACCOUNTS);
     let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
 
     let message_1 = Message::new_with_blockhash(
@@ -1706,43 +2240,28 @@ fn command_add_reserve(
         &recent_blockhash,
     );
 
+    // the source liquidity owner transfers the initial liquidity itself, so that every signer of
+    // the owner's transaction is a lasting key, as multisig tooling needs for a dumped message
+    let init_instructions = [init_reserve(
+        config.lending_program_id,
+        liquidity_amount,
+        reserve_config,
+        source_liquidity_pubkey,
+        user_collateral_keypair.pubkey(),
+        reserve_keypair.pubkey(),
+        source_liquidity.mint,
+        liquidity_supply_keypair.pubkey(),
+        collateral_mint_keypair.pubkey(),
+        collateral_supply_keypair.pubkey(),
+        pyth_product_pubkey,
+        pyth_price_pubkey,
+        switchboard_feed_pubkey,
+        lending_market_pubkey,
+        lending_market_owner.pubkey(),
+        source_liquidity_owner.pubkey(),
+    )];
     let message_3 = Message::new_with_blockhash(
-        &[
-            approve(
-                &spl_token::id(),
-                &source_liquidity_pubkey,
-                &user_transfer_authority_keypair.pubkey(),
-                &source_liquidity_owner_keypair.pubkey(),
-                &[],
-                liquidity_amount,
-            )
-            .unwrap(),
-            init_reserve(
-                config.lending_program_id,
-                liquidity_amount,
-                reserve_config,
-                source_liquidity_pubkey,
-                user_collateral_keypair.pubkey(),
-                reserve_keypair.pubkey(),
-                source_liquidity.mint,
-                liquidity_supply_keypair.pubkey(),
-                collateral_mint_keypair.pubkey(),
-                collateral_supply_keypair.pubkey(),
-                pyth_product_pubkey,
-                pyth_price_pubkey,
-                switchboard_feed_pubkey,
-                lending_market_pubkey,
-                lending_market_owner_keypair.pubkey(),
-                user_transfer_authority_keypair.pubkey(),
-            ),
-            revoke(
-                &spl_token::id(),
-                &source_liquidity_pubkey,
-                &source_liquidity_owner_keypair.pubkey(),
-                &[],
-            )
-            .unwrap(),
-        ],
+        &init_instructions,
         Some(&config.fee_payer.pubkey()),
         &recent_blockhash,
     );
@@ -1755,48 +2274,69 @@ fn command_add_reserve(
             + config.rpc_client.get_fee_for_message(&message_3)?,
     )?;
 
-    let transaction_1 = Transaction::new(
-        &vec![
-            config.fee_payer.as_ref(),
-            &reserve_keypair,
-            &collateral_mint_keypair,
-            &collateral_supply_keypair,
-            &user_collateral_keypair,
-        ],
-        message_1,
-        recent_blockhash,
-    );
-    send_transaction(config, transaction_1)?;
-    let transaction_2 = Transaction::new(
-        &vec![
-            config.fee_payer.as_ref(),
-            &liquidity_supply_keypair,
-            &liquidity_fee_receiver_keypair,
-        ],
-        message_2,
-        recent_blockhash,
+    // the accounts are created and paid for by the fee payer, even when the owner's transaction
+    // is only dumped, so the fee payer has to be a real signer
+    let fee_payer_must_sign = |err: SignerError| -> Error {
+        format!(
+            "the fee payer signs the transactions that create the reserve's accounts: {}",
+            err
+        )
+        .into()
+    };
+    let mut transaction_1 = Transaction::new_unsigned(message_1);
+    transaction_1
+        .try_sign(
+            &vec![
+                config.fee_payer.as_ref(),
+                &reserve_keypair,
+                &collateral_mint_keypair,
+                &collateral_supply_keypair,
+                &user_collateral_keypair,
+            ],
+            recent_blockhash,
+        )
+        .map_err(fee_payer_must_sign)?;
+    let mut output = CliTransactionOutput::default();
+    output.add_signature(submit_transaction(config, transaction_1)?);
+    let mut transaction_2 = Transaction::new_unsigned(message_2);
+    transaction_2
+        .try_sign(
+            &vec![
+                config.fee_payer.as_ref(),
+                &liquidity_supply_keypair,
+                &liquidity_fee_receiver_keypair,
+            ],
+            recent_blockhash,
+        )
+        .map_err(fee_payer_must_sign)?;
+    output.add_signature(submit_transaction(config, transaction_2)?);
+
+    output.add_address("reserve", &reserve_keypair.pubkey());
+    output.add_address("collateralMint", &collateral_mint_keypair.pubkey());
+    output.add_address("collateralSupply", &collateral_supply_keypair.pubkey());
+    output.add_address("liquiditySupply", &liquidity_supply_keypair.pubkey());
+    output.add_address(
+        "liquidityFeeReceiver",
+        &liquidity_fee_receiver_keypair.pubkey(),
     );
-    send_transaction(config, transaction_2)?;
-    let transaction_3 = Transaction::new(
-        &vec![
-            config.fee_payer.as_ref(),
-            &source_liquidity_owner_keypair,
-            &lending_market_owner_keypair,
-            &user_transfer_authority_keypair,
+    output.add_address("userCollateral", &user_collateral_keypair.pubkey());
+    process_admin_transaction(
+        config,
+        &init_instructions,
+        &[
+            source_liquidity_owner.as_ref(),
+            lending_market_owner.as_ref(),
         ],
-        message_3,
-        recent_blockhash,
-    );
-    send_transaction(config, transaction_3)?;
-    Ok(())
+        output,
+    )
 }
 
 #[allow(clippy::too_many_arguments)]
 fn command_set_lending_market_owner_and_config(
     config: &mut Config,
     lending_market_pubkey: Pubkey,
-    lending_market_owner_keypair: Keypair,
-    new_lending_market_owner_keypair: Option<Keypair>,
+    lending_market_owner: Box<dyn Signer>,
+    new_lending_market_owner_pubkey: Option<Pubkey>,
     rate_limiter_window_duration: Option<u64>,
     rate_limiter_max_outflow: Option<u64>,
     whitelisted_liquidator_pubkey: Option<Pubkey>,
@@ -1804,19 +2344,15 @@ fn command_set_lending_market_owner_and_config(
 ) -> CommandResult {
     let lending_market_info = config.rpc_client.get_account(&lending_market_pubkey)?;
     let lending_market = LendingMarket::unpack_from_slice(lending_market_info.data.borrow())?;
-    println!("{:#?}", lending_market);
+    println_display!(config, "{:#?}", lending_market);
 
-    let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
-    let message = Message::new_with_blockhash(
+    process_admin_transaction(
+        config,
         &[set_lending_market_owner_and_config(
             config.lending_program_id,
             lending_market_pubkey,
-            lending_market_owner_keypair.pubkey(),
-            if let Some(owner) = new_lending_market_owner_keypair {
-                owner.pubkey()
-            } else {
-                lending_market.owner
-            },
+            lending_market_owner.pubkey(),
+            new_lending_market_owner_pubkey.unwrap_or(lending_market.owner),
             RateLimiterConfig {
                 window_duration: rate_limiter_window_duration
                     .unwrap_or(lending_market.rate_limiter.config.window_duration),
@@ -1826,18 +2362,9 @@ fn command_set_lending_market_owner_and_config(
             whitelisted_liquidator_pubkey,
             risk_authority_pubkey,
         )],
-        Some(&config.fee_payer.pubkey()),
-        &recent_blockhash,
-    );
-
-    let transaction = Transaction::new(
-        &vec![config.fee_payer.as_ref(), &lending_market_owner_keypair],
-        message,
-        recent_blockhash,
-    );
-
-    send_transaction(config, transaction)?;
-    Ok(())
+        &[lending_market_owner.as_ref()],
+        CliTransactionOutput::default(),
+    )
 }
 
 #[allow(clippy::too_many_arguments, clippy::unnecessary_unwrap)]
@@ -1849,18 +2376,19 @@ fn command_update_reserve(
     switchboard_feed_pubkey: Option<Pubkey>,
     reserve_pubkey: Pubkey,
     lending_market_pubkey: Pubkey,
-    lending_market_owner_keypair: Keypair,
+    lending_market_owner: Box<dyn Signer>,
 ) -> CommandResult {
     let reserve_info = config.rpc_client.get_account(&reserve_pubkey)?;
     let mut reserve = Reserve::unpack_from_slice(reserve_info.data.borrow())?;
-    println!("Reserve: {:#?}", reserve);
+    println_display!(config, "Reserve: {:#?}", reserve);
     let mut no_change = true;
     if reserve_config.optimal_utilization_rate.is_some()
         && reserve.config.optimal_utilization_rate
             != reserve_config.optimal_utilization_rate.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating optimal_utilization_rate from {} to {}",
             reserve.config.optimal_utilization_rate,
             reserve_config.optimal_utilization_rate.unwrap(),
@@ -1872,7 +2400,8 @@ fn command_update_reserve(
         && reserve.config.max_utilization_rate != reserve_config.max_utilization_rate.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating max_utilization_rate from {} to {}",
             reserve.config.max_utilization_rate,
             reserve_config.max_utilization_rate.unwrap(),
@@ -1884,7 +2413,8 @@ fn command_update_reserve(
         && reserve.config.loan_to_value_ratio != reserve_config.loan_to_value_ratio.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating loan_to_value_ratio from {} to {}",
             reserve.config.loan_to_value_ratio,
             reserve_config.loan_to_value_ratio.unwrap(),
@@ -1896,7 +2426,8 @@ fn command_update_reserve(
         && reserve.config.liquidation_bonus != reserve_config.liquidation_bonus.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating liquidation_bonus from {} to {}",
             reserve.config.liquidation_bonus,
             reserve_config.liquidation_bonus.unwrap(),
@@ -1908,7 +2439,8 @@ fn command_update_reserve(
         && reserve.config.max_liquidation_bonus != reserve_config.max_liquidation_bonus.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating max_liquidation_bonus from {} to {}",
             reserve.config.max_liquidation_bonus,
             reserve_config.max_liquidation_bonus.unwrap(),
@@ -1920,7 +2452,8 @@ fn command_update_reserve(
         && reserve.config.liquidation_threshold != reserve_config.liquidation_threshold.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating liquidation_threshold from {} to {}",
             reserve.config.liquidation_threshold,
             reserve_config.liquidation_threshold.unwrap(),
@@ -1933,7 +2466,8 @@ fn command_update_reserve(
             != reserve_config.max_liquidation_threshold.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating max_liquidation_threshold from {} to {}",
             reserve.config.max_liquidation_threshold,
             reserve_config.max_liquidation_threshold.unwrap(),
@@ -1946,7 +2480,8 @@ fn command_update_reserve(
         && reserve.config.min_borrow_rate != reserve_config.min_borrow_rate.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating min_borrow_rate from {} to {}",
             reserve.config.min_borrow_rate,
             reserve_config.min_borrow_rate.unwrap(),
@@ -1958,7 +2493,8 @@ fn command_update_reserve(
         && reserve.config.optimal_borrow_rate != reserve_config.optimal_borrow_rate.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating optimal_borrow_rate from {} to {}",
             reserve.config.optimal_borrow_rate,
             reserve_config.optimal_borrow_rate.unwrap(),
@@ -1970,7 +2506,8 @@ fn command_update_reserve(
         && reserve.config.max_borrow_rate != reserve_config.max_borrow_rate.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating max_borrow_rate from {} to {}",
             reserve.config.max_borrow_rate,
             reserve_config.max_borrow_rate.unwrap(),
@@ -1982,7 +2519,8 @@ fn command_update_reserve(
         && reserve.config.super_max_borrow_rate != reserve_config.super_max_borrow_rate.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating super_max_borrow_rate from {} to {}",
             reserve.config.super_max_borrow_rate,
             reserve_config.super_max_borrow_rate.unwrap(),
@@ -1994,7 +2532,8 @@ fn command_update_reserve(
         && reserve.config.fees.borrow_fee_wad != reserve_config.fees.borrow_fee_wad.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating borrow_fee_wad from {} to {}",
             reserve.config.fees.borrow_fee_wad,
             reserve_config.fees.borrow_fee_wad.unwrap(),
@@ -2006,7 +2545,8 @@ fn command_update_reserve(
         && reserve.config.fees.flash_loan_fee_wad != reserve_config.fees.flash_loan_fee_wad.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating flash_loan_fee_wad from {} to {}",
             reserve.config.fees.flash_loan_fee_wad,
             reserve_config.fees.flash_loan_fee_wad.unwrap(),
@@ -2019,7 +2559,8 @@ fn command_update_reserve(
             != reserve_config.fees.host_fee_percentage.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating host_fee_percentage from {} to {}",
             reserve.config.fees.host_fee_percentage,
             reserve_config.fees.host_fee_percentage.unwrap(),
@@ -2031,7 +2572,8 @@ fn command_update_reserve(
         && reserve.config.deposit_limit != reserve_config.deposit_limit.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating deposit_limit from {} to {}",
             amount_to_ui_amount(
                 reserve.config.deposit_limit,
@@ -2049,7 +2591,8 @@ fn command_update_reserve(
         && reserve.config.borrow_limit != reserve_config.borrow_limit.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating borrow_limit from {} to {}",
             amount_to_ui_amount(reserve.config.borrow_limit, reserve.liquidity.mint_decimals),
             reserve_config.borrow_limit.unwrap(),
@@ -2064,7 +2607,8 @@ fn command_update_reserve(
         && reserve.config.fee_receiver != reserve_config.fee_receiver.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating fee_receiver from {} to {}",
             reserve.config.fee_receiver,
             reserve_config.fee_receiver.unwrap(),
@@ -2077,7 +2621,8 @@ fn command_update_reserve(
             != reserve_config.protocol_liquidation_fee.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating protocol_liquidation_fee from {} to {}",
             reserve.config.protocol_liquidation_fee,
             reserve_config.protocol_liquidation_fee.unwrap(),
@@ -2089,7 +2634,8 @@ fn command_update_reserve(
         && reserve.config.protocol_take_rate != reserve_config.protocol_take_rate.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating protocol_take_rate from {} to {}",
             reserve.config.protocol_take_rate,
             reserve_config.protocol_take_rate.unwrap(),
@@ -2100,7 +2646,8 @@ fn command_update_reserve(
     let mut new_pyth_product_pubkey = solend_sdk::NULL_PUBKEY;
     if pyth_price_pubkey.is_some() {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating pyth oracle pubkey from {} to {}",
             reserve.liquidity.pyth_oracle_pubkey,
             pyth_price_pubkey.unwrap(),
@@ -2111,7 +2658,8 @@ fn command_update_reserve(
 
     if switchboard_feed_pubkey.is_some() {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating switchboard_oracle_pubkey {} to {}",
             reserve.liquidity.switchboard_oracle_pubkey,
             switchboard_feed_pubkey.unwrap(),
@@ -2124,7 +2672,8 @@ fn command_update_reserve(
             != reserve_config.rate_limiter_window_duration.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating rate_limiter_window_duration from {} to {}",
             reserve.rate_limiter.config.window_duration,
             reserve_config.rate_limiter_window_duration.unwrap(),
@@ -2138,7 +2687,8 @@ fn command_update_reserve(
             != reserve_config.rate_limiter_max_outflow.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating rate_limiter_max_outflow from {} to {}",
             reserve.rate_limiter.config.max_outflow,
             reserve_config.rate_limiter_max_outflow.unwrap(),
@@ -2150,7 +2700,8 @@ fn command_update_reserve(
         && reserve.config.added_borrow_weight_bps != reserve_config.added_borrow_weight_bps.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating added_borrow_weight_bps from {} to {}",
             reserve.config.added_borrow_weight_bps,
             reserve_config.added_borrow_weight_bps.unwrap(),
@@ -2162,7 +2713,8 @@ fn command_update_reserve(
         && reserve.config.reserve_type != reserve_config.reserve_type.unwrap()
     {
         no_change = false;
-        println!(
+        println_display!(
+            config,
             "Updating reserve_type from {:?} to {:?}",
             reserve.config.reserve_type,
             reserve_config.reserve_type.unwrap(),
@@ -2171,18 +2723,20 @@ fn command_update_reserve(
     }
 
     if validate_reserve_config(reserve.config).is_err() {
-        println!("Error: invalid reserve config");
+        println_display!(config, "Error: invalid reserve config");
         return Err("Error: invalid reserve config".into());
     }
 
     if no_change {
-        println!("No changes made for reserve {}", reserve_pubkey);
+        println_display!(config, "No changes made for reserve {}", reserve_pubkey);
+        config
+            .output_format
+            .print_json(&CliTransactionOutput::default());
         return Ok(());
     }
 
-    let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
-
-    let message = Message::new_with_blockhash(
+    process_admin_transaction(
+        config,
         &[update_reserve_config(
             config.lending_program_id,
             reserve.config,
@@ -2192,26 +2746,189 @@ fn command_update_reserve(
             },
             reserve_pubkey,
             lending_market_pubkey,
-            lending_market_owner_keypair.pubkey(),
+            lending_market_owner.pubkey(),
             new_pyth_product_pubkey,
             reserve.liquidity.pyth_oracle_pubkey,
             reserve.liquidity.switchboard_oracle_pubkey,
         )],
-        Some(&config.fee_payer.pubkey()),
-        &recent_blockhash,
-    );
+        &[lending_market_owner.as_ref()],
+        CliTransactionOutput::default(),
+    )
+}
 
-    let transaction = Transaction::new(
-        &vec![config.fee_payer.as_ref(), &lending_market_owner_keypair],
-        message,
-        recent_blockhash,
-    );
+// HELPERS
 
-    send_transaction(config, transaction)?;
-    Ok(())
+fn signer_of(
+    matches: &ArgMatches<'_>,
+    name: &str,
+    wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+) -> Box<dyn Signer> {
+    signer_from_path_with_config(
+        matches,
+        matches.value_of(name).unwrap(),
+        name,
+        wallet_manager,
+        &signer_from_path_config(matches),
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        exit(1);
+    })
 }
 
-// HELPERS
+/// Signers may be bare pubkeys when the transaction message is only printed
+fn signer_from_path_config(matches: &ArgMatches<'_>) -> SignerFromPathConfig {
+    SignerFromPathConfig {
+        allow_null_signer: matches.is_present(DUMP_TRANSACTION_MESSAGE_ARG),
+    }
+}
+
+/// Value of a result, or print its error and exit
+fn unwrap_or_exit<T>(result: Result<T, Error>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        exit(1);
+    })
+}
+
+/// Pubkey of an optional argument that accepts a pubkey or any signer, e.g. `usb://ledger`
+fn pubkey_of_signer_or_exit(
+    matches: &ArgMatches<'_>,
+    name: &str,
+    wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+) -> Option<Pubkey> {
+    pubkey_of_signer(matches, name, wallet_manager).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        exit(1);
+    })
+}
+
+/// Sign and send a transaction, or with `--sign-only` print the signatures instead. Uses the
+/// `--blockhash` and durable nonce arguments when given. `output` is printed once the
+/// transaction is sent.
+fn process_admin_transaction(
+    config: &Config,
+    instructions: &[Instruction],
+    signers: &[&dyn Signer],
+    mut output: CliTransactionOutput,
+) -> CommandResult {
+    let fee_payer = config.fee_payer.pubkey();
+    let message = match config.nonce_account {
+        Some(nonce_account) => {
+            let nonce_authority = config
+                .nonce_authority
+                .as_ref()
+                .map_or(fee_payer, |authority| authority.pubkey());
+            Message::new_with_nonce(
+                instructions.to_vec(),
+                Some(&fee_payer),
+                &nonce_account,
+                &nonce_authority,
+            )
+        }
+        None => Message::new(instructions, Some(&fee_payer)),
+    };
+
+    let mut all_signers = vec![config.fee_payer.as_ref()];
+    all_signers.extend(signers);
+    if let Some(nonce_authority) = &config.nonce_authority {
+        all_signers.push(nonce_authority.as_ref());
+    }
+    let mut unique_signers: Vec<&dyn Signer> = vec![];
+    for signer in all_signers {
+        if !unique_signers
+            .iter()
+            .any(|unique| unique.pubkey() == signer.pubkey())
+        {
+            unique_signers.push(signer);
+        }
+    }
+
+    let recent_blockhash = config
+        .blockhash_query
+        .get_blockhash(&config.rpc_client, config.rpc_client.commitment())?;
+    if config.dump_transaction_message {
+        let mut message = message;
+        message.recent_blockhash = recent_blockhash;
+        print_transaction_message(config, &message);
+        return Ok(());
+    }
+
+    let mut transaction = Transaction::new_unsigned(message);
+    if config.sign_only {
+        transaction.try_partial_sign(&unique_signers, recent_blockhash)?;
+        print_sign_only(config, &transaction);
+        Ok(())
+    } else {
+        transaction.try_sign(&unique_signers, recent_blockhash)?;
+        output.add_signature(submit_transaction(config, transaction)?);
+        config.output_format.print_json(&output);
+        Ok(())
+    }
+}
+
+/// Print signatures in the format accepted by `--signer`, and the signers that are still missing
+fn print_sign_only(config: &Config, transaction: &Transaction) {
+    let signers = transaction
+        .message
+        .account_keys
+        .iter()
+        .zip(transaction.signatures.iter());
+    let (present, absent): (Vec<_>, Vec<_>) =
+        signers.partition(|(_, signature)| **signature != Signature::default());
+    if !config.output_format.is_display() {
+        config.output_format.print_json(&CliSignOnly {
+            blockhash: transaction.message.recent_blockhash.to_string(),
+            signers: present
+                .iter()
+                .map(|(pubkey, signature)| format!("{}={}", pubkey, signature))
+                .collect(),
+            absent: absent
+                .iter()
+                .map(|(pubkey, _)| pubkey.to_string())
+                .collect(),
+        });
+        return;
+    }
+
+    println!("Blockhash: {}", transaction.message.recent_blockhash);
+    if !present.is_empty() {
+        println!("Signers (Pubkey=Signature):");
+        for (pubkey, signature) in present {
+            println!("  {}={}", pubkey, signature);
+        }
+    }
+    if !absent.is_empty() {
+        println!("Absent Signers (Pubkey):");
+        for (pubkey, _) in absent {
+            println!("  {}", pubkey);
+        }
+    }
+}
+
+/// Print an unsigned message for import into multisig or governance tooling
+fn print_transaction_message(config: &Config, message: &Message) {
+    let output = CliTransactionMessage {
+        message: base64::encode(message.serialize()),
+        blockhash: message.recent_blockhash.to_string(),
+        signers: message.account_keys[..message.header.num_required_signatures as usize]
+            .iter()
+            .map(|pubkey| pubkey.to_string())
+            .collect(),
+    };
+    if !config.output_format.is_display() {
+        config.output_format.print_json(&output);
+        return;
+    }
+
+    println!("Blockhash: {}", output.blockhash);
+    println!("Signers (Pubkey):");
+    for signer in &output.signers {
+        println!("  {}", signer);
+    }
+    println!("Transaction message (base64):");
+    println!("{}", output.message);
+}
 
 fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(), Error> {
     let balance = config.rpc_client.get_balance(&config.fee_payer.pubkey())?;
@@ -2228,15 +2945,55 @@ fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(),
     }
 }
 
+/// Only admin commands, which go through `process_admin_transaction`, can be signed offline,
+/// dumped or use a durable nonce. Other commands sign and send right away, so they refuse those arguments.
+#[allow(clippy::result_large_err)]
+fn check_online(config: &Config) -> solana_client::client_error::Result<()> {
+    let offline_arg = if config.sign_only {
+        Some(SIGN_ONLY_ARG.long)
+    } else if config.dump_transaction_message {
+        Some("dump-transaction-message")
+    } else if config.nonce_account.is_some() {
+        Some(NONCE_ARG.long)
+    } else if !matches!(config.blockhash_query, BlockhashQuery::All(_)) {
+        Some(BLOCKHASH_ARG.long)
+    } else {
+        None
+    };
+    match offline_arg {
+        Some(arg) => Err(ClientErrorKind::Custom(format!(
+            "--{} is only supported by commands signed by the lending market owner",
+            arg
+        ))
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Send and confirm a transaction signed with a recent blockhash, see `submit_transaction`
 fn send_transaction(
     config: &Config,
     transaction: Transaction,
-) -> solana_client::client_error::Result<()> {
+) -> solana_client::client_error::Result<Option<Signature>> {
+    check_online(config)?;
+    submit_transaction(config, transaction)
+}
+
+/// Send and confirm a transaction, returning its signature, or only simulate it with `--dry-run`
+#[allow(clippy::result_large_err)]
+fn submit_transaction(
+    config: &Config,
+    transaction: Transaction,
+) -> solana_client::client_error::Result<Option<Signature>> {
     if config.dry_run {
         let result = config.rpc_client.simulate_transaction(&transaction)?;
-        println!("Simulate result: {:?}", result);
+        println_display!(config, "Simulate result: {:?}", result);
+        if let Some(err) = &result.value.err {
+            print_decoded_error(config, err, &transaction_program_ids(&transaction));
+        }
+        Ok(None)
     } else {
-        let signature = config
+        let result = config
             .rpc_client
             .send_and_confirm_transaction_with_spinner_and_config(
                 &transaction,
@@ -2248,10 +3005,69 @@ fn send_transaction(
                     max_retries: None,
                     min_context_slot: None,
                 },
-            )?;
-        println!("Signature: {}", signature);
+            );
+        if let Some(err) = result
+            .as_ref()
+            .err()
+            .and_then(|e| e.get_transaction_error())
+        {
+            print_decoded_error(config, &err, &transaction_program_ids(&transaction));
+        }
+        let signature = result?;
+        println_display!(config, "Signature: {}", signature);
+        Ok(Some(signature))
     }
-    Ok(())
+}
+
+fn print_decoded_error(config: &Config, err: &TransactionError, program_ids: &[Pubkey]) {
+    if let TransactionError::InstructionError(index, instruction_error) = err {
+        if let Some(description) = program_ids.get(*index as usize).and_then(|program_id| {
+            ErrorCatalog::default()
+                .with_lending_program(config.lending_program_id)
+                .decode_instruction_error(program_id, instruction_error)
+        }) {
+            eprintln!("Instruction {} failed: {}", index, description);
+        }
+    }
+}
+
+fn transaction_program_ids(transaction: &Transaction) -> Vec<Pubkey> {
+    transaction
+        .message
+        .instructions
+        .iter()
+        .map(|instruction| *instruction.program_id(&transaction.message.account_keys))
+        .collect()
+}
+
+#[allow(clippy::result_large_err)]
+fn budget_instructions(
+    config: &Config,
+    instructions: &[Instruction],
+    compute_budget: &ComputeBudgetConfig,
+) -> solana_client::client_error::Result<Vec<Instruction>> {
+    // `send_transaction` simulates the transaction in a dry run and prints the result, units
+    // consumed included, so don't simulate it twice or stop at the first failure
+    if config.dry_run {
+        let mut budgeted = compute_budget_instructions(MAX_COMPUTE_UNIT_LIMIT, compute_budget);
+        budgeted.extend(instructions.iter().cloned());
+        return Ok(budgeted);
+    }
+    let result = with_compute_budget(
+        &config.rpc_client,
+        &config.fee_payer.pubkey(),
+        instructions,
+        compute_budget,
+    );
+    if let Some(err) = result
+        .as_ref()
+        .err()
+        .and_then(|e| e.get_transaction_error())
+    {
+        let program_ids: Vec<Pubkey> = instructions.iter().map(|ix| ix.program_id).collect();
+        print_decoded_error(config, &err, &program_ids);
+    }
+    result
 }
 
 fn quote_currency_of(matches: &ArgMatches<'_>, name: &str) -> Option<[u8; 32]> {
@@ -2274,7 +3090,7 @@ fn get_or_create_associated_token_address(config: &Config, mint: &Pubkey) -> Pub
     let ata = get_associated_token_address(&config.fee_payer.pubkey(), mint);
 
     if config.rpc_client.get_account(&ata).is_err() {
-        println!("Creating ATA for mint {:?}", mint);
+        println_display!(config, "Creating ATA for mint {:?}", mint);
 
         let recent_blockhash = config.rpc_client.get_latest_blockhash().unwrap();
         let transaction = Transaction::new(
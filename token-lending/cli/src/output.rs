@@ -0,0 +1,458 @@
+//! Machine readable command output
+
+use {
+    serde::Serialize,
+    solana_account_decoder::UiAccount,
+    solana_program::pubkey::Pubkey,
+    solana_sdk::signature::Signature,
+    solend_client::ui_amount::{amount_to_ui_amount_string, decimal_to_ui_amount_string},
+    solend_sdk::state::{
+        LastUpdate, LendingMarket, Obligation, RateLimiterConfig, Reserve, ReserveConfig,
+    },
+    std::{collections::BTreeMap, str::FromStr},
+};
+
+/// How command results are printed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable text
+    Display,
+    /// Pretty printed JSON
+    Json,
+    /// Single line JSON
+    JsonCompact,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "display" => Ok(OutputFormat::Display),
+            "json" => Ok(OutputFormat::Json),
+            "json-compact" => Ok(OutputFormat::JsonCompact),
+            _ => Err(format!("Invalid output format: {}", s)),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Print `item` as JSON. Does nothing in display mode, where commands print as they go.
+    pub fn print_json<T: Serialize>(&self, item: &T) {
+        match self {
+            OutputFormat::Display => {}
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(item).unwrap()),
+            OutputFormat::JsonCompact => println!("{}", serde_json::to_string(item).unwrap()),
+        }
+    }
+
+    /// Whether human readable text should be printed to stdout
+    pub fn is_display(&self) -> bool {
+        *self == OutputFormat::Display
+    }
+}
+
+/// Print human readable progress to stdout, or to stderr when printing JSON so stdout stays
+/// parseable
+macro_rules! println_display {
+    ($config:expr, $($arg:tt)*) => {
+        if $config.output_format.is_display() {
+            println!($($arg)*);
+        } else {
+            eprintln!($($arg)*);
+        }
+    };
+}
+pub(crate) use println_display;
+
+/// Accounts created and transactions sent by a command
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTransactionOutput {
+    /// Addresses of interest, keyed by their role
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub addresses: BTreeMap<String, String>,
+    /// Signatures of the sent transactions, in order
+    pub signatures: Vec<String>,
+}
+
+impl CliTransactionOutput {
+    /// Record an address under `role`
+    pub fn add_address(&mut self, role: &str, address: &Pubkey) {
+        self.addresses.insert(role.to_string(), address.to_string());
+    }
+
+    /// Record a sent transaction, if it was sent rather than simulated
+    pub fn add_signature(&mut self, signature: Option<Signature>) {
+        self.signatures.extend(signature.map(|s| s.to_string()));
+    }
+}
+
+/// Signatures collected by `--sign-only`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSignOnly {
+    /// Blockhash the transaction was signed with
+    pub blockhash: String,
+    /// Collected signatures as `pubkey=signature`
+    pub signers: Vec<String>,
+    /// Signers whose signature is still missing
+    pub absent: Vec<String>,
+}
+
+/// Unsigned transaction message printed by `--dump-transaction-message`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTransactionMessage {
+    /// Serialized message, base64 encoded
+    pub message: String,
+    /// Blockhash the message was built with
+    pub blockhash: String,
+    /// Accounts that must sign the message
+    pub signers: Vec<String>,
+}
+
+/// Accounts written by `download-fixtures`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliFixtures {
+    /// Directory the accounts were written to
+    pub directory: String,
+    /// Addresses of the written accounts
+    pub accounts: Vec<String>,
+}
+
+/// An account in the format of `solana account --output json`, which `solana-test-validator
+/// --account` and the program test helpers load
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAccount {
+    pub pubkey: String,
+    pub account: UiAccount,
+}
+
+/// Lending market addresses
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliLendingMarkets {
+    /// Lending market addresses
+    pub lending_markets: Vec<String>,
+}
+
+/// Version of a deployed lending program
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliProgramVersion {
+    /// Lending program ID
+    pub program_id: String,
+    /// Crate version, `None` if the program predates the `GetVersion` instruction
+    pub version: Option<String>,
+    /// Feature bitmap
+    pub features: u64,
+    /// Names of the known features in the bitmap
+    pub feature_names: Vec<String>,
+}
+
+/// Decoded lending market
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliLendingMarket {
+    pub address: String,
+    pub version: u8,
+    pub bump_seed: u8,
+    pub owner: String,
+    pub quote_currency: String,
+    pub token_program_id: String,
+    pub oracle_program_id: String,
+    pub switchboard_oracle_program_id: String,
+    pub rate_limiter: CliRateLimiterConfig,
+    pub whitelisted_liquidator: Option<String>,
+    pub risk_authority: String,
+}
+
+impl CliLendingMarket {
+    /// Decoded lending market at `address`
+    pub fn new(address: &Pubkey, lending_market: &LendingMarket) -> Self {
+        Self {
+            address: address.to_string(),
+            version: lending_market.version,
+            bump_seed: lending_market.bump_seed,
+            owner: lending_market.owner.to_string(),
+            quote_currency: quote_currency_to_string(&lending_market.quote_currency),
+            token_program_id: lending_market.token_program_id.to_string(),
+            oracle_program_id: lending_market.oracle_program_id.to_string(),
+            switchboard_oracle_program_id: lending_market.switchboard_oracle_program_id.to_string(),
+            rate_limiter: lending_market.rate_limiter.config.into(),
+            whitelisted_liquidator: lending_market.whitelisted_liquidator.map(|p| p.to_string()),
+            risk_authority: lending_market.risk_authority.to_string(),
+        }
+    }
+}
+
+/// Decoded rate limiter config
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliRateLimiterConfig {
+    pub window_duration: u64,
+    pub max_outflow: u64,
+}
+
+impl From<RateLimiterConfig> for CliRateLimiterConfig {
+    fn from(config: RateLimiterConfig) -> Self {
+        Self {
+            window_duration: config.window_duration,
+            max_outflow: config.max_outflow,
+        }
+    }
+}
+
+/// Decoded last update
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliLastUpdate {
+    pub slot: u64,
+    pub stale: bool,
+}
+
+impl From<&LastUpdate> for CliLastUpdate {
+    fn from(last_update: &LastUpdate) -> Self {
+        Self {
+            slot: last_update.slot,
+            stale: last_update.stale,
+        }
+    }
+}
+
+/// Decoded reserve. Decimal values are printed in their decimal string form.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliReserve {
+    pub address: String,
+    pub version: u8,
+    pub last_update: CliLastUpdate,
+    pub lending_market: String,
+    pub liquidity: CliReserveLiquidity,
+    pub collateral: CliReserveCollateral,
+    pub config: CliReserveConfig,
+    pub rate_limiter: CliRateLimiterConfig,
+}
+
+/// Decoded reserve liquidity
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliReserveLiquidity {
+    pub mint_pubkey: String,
+    pub mint_decimals: u8,
+    pub supply_pubkey: String,
+    pub pyth_oracle_pubkey: String,
+    pub switchboard_oracle_pubkey: String,
+    pub available_amount: u64,
+    pub available_ui_amount: String,
+    pub borrowed_amount_wads: String,
+    pub borrowed_ui_amount: String,
+    pub cumulative_borrow_rate_wads: String,
+    pub accumulated_protocol_fees_wads: String,
+    pub market_price: String,
+    pub smoothed_market_price: String,
+}
+
+/// Decoded reserve collateral
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliReserveCollateral {
+    pub mint_pubkey: String,
+    pub mint_total_supply: u64,
+    pub supply_pubkey: String,
+}
+
+/// Decoded reserve config
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliReserveConfig {
+    pub optimal_utilization_rate: u8,
+    pub max_utilization_rate: u8,
+    pub loan_to_value_ratio: u8,
+    pub liquidation_bonus: u8,
+    pub max_liquidation_bonus: u8,
+    pub liquidation_threshold: u8,
+    pub max_liquidation_threshold: u8,
+    pub min_borrow_rate: u8,
+    pub optimal_borrow_rate: u8,
+    pub max_borrow_rate: u8,
+    pub super_max_borrow_rate: u64,
+    pub borrow_fee_wad: u64,
+    pub flash_loan_fee_wad: u64,
+    pub host_fee_percentage: u8,
+    pub deposit_limit: u64,
+    pub borrow_limit: u64,
+    pub fee_receiver: String,
+    pub protocol_liquidation_fee: u8,
+    pub protocol_take_rate: u8,
+    pub added_borrow_weight_bps: u64,
+    pub reserve_type: String,
+}
+
+impl From<&ReserveConfig> for CliReserveConfig {
+    fn from(config: &ReserveConfig) -> Self {
+        Self {
+            optimal_utilization_rate: config.optimal_utilization_rate,
+            max_utilization_rate: config.max_utilization_rate,
+            loan_to_value_ratio: config.loan_to_value_ratio,
+            liquidation_bonus: config.liquidation_bonus,
+            max_liquidation_bonus: config.max_liquidation_bonus,
+            liquidation_threshold: config.liquidation_threshold,
+            max_liquidation_threshold: config.max_liquidation_threshold,
+            min_borrow_rate: config.min_borrow_rate,
+            optimal_borrow_rate: config.optimal_borrow_rate,
+            max_borrow_rate: config.max_borrow_rate,
+            super_max_borrow_rate: config.super_max_borrow_rate,
+            borrow_fee_wad: config.fees.borrow_fee_wad,
+            flash_loan_fee_wad: config.fees.flash_loan_fee_wad,
+            host_fee_percentage: config.fees.host_fee_percentage,
+            deposit_limit: config.deposit_limit,
+            borrow_limit: config.borrow_limit,
+            fee_receiver: config.fee_receiver.to_string(),
+            protocol_liquidation_fee: config.protocol_liquidation_fee,
+            protocol_take_rate: config.protocol_take_rate,
+            added_borrow_weight_bps: config.added_borrow_weight_bps,
+            reserve_type: format!("{:?}", config.reserve_type),
+        }
+    }
+}
+
+impl CliReserve {
+    /// Decoded reserve at `address`
+    pub fn new(address: &Pubkey, reserve: &Reserve) -> Self {
+        Self {
+            address: address.to_string(),
+            version: reserve.version,
+            last_update: (&reserve.last_update).into(),
+            lending_market: reserve.lending_market.to_string(),
+            liquidity: CliReserveLiquidity {
+                mint_pubkey: reserve.liquidity.mint_pubkey.to_string(),
+                mint_decimals: reserve.liquidity.mint_decimals,
+                supply_pubkey: reserve.liquidity.supply_pubkey.to_string(),
+                pyth_oracle_pubkey: reserve.liquidity.pyth_oracle_pubkey.to_string(),
+                switchboard_oracle_pubkey: reserve.liquidity.switchboard_oracle_pubkey.to_string(),
+                available_amount: reserve.liquidity.available_amount,
+                available_ui_amount: amount_to_ui_amount_string(
+                    reserve.liquidity.available_amount,
+                    reserve.liquidity.mint_decimals,
+                ),
+                borrowed_amount_wads: reserve.liquidity.borrowed_amount_wads.to_string(),
+                borrowed_ui_amount: decimal_to_ui_amount_string(
+                    reserve.liquidity.borrowed_amount_wads,
+                    reserve.liquidity.mint_decimals,
+                )
+                .unwrap_or_default(),
+                cumulative_borrow_rate_wads: reserve
+                    .liquidity
+                    .cumulative_borrow_rate_wads
+                    .to_string(),
+                accumulated_protocol_fees_wads: reserve
+                    .liquidity
+                    .accumulated_protocol_fees_wads
+                    .to_string(),
+                market_price: reserve.liquidity.market_price.to_string(),
+                smoothed_market_price: reserve.liquidity.smoothed_market_price.to_string(),
+            },
+            collateral: CliReserveCollateral {
+                mint_pubkey: reserve.collateral.mint_pubkey.to_string(),
+                mint_total_supply: reserve.collateral.mint_total_supply,
+                supply_pubkey: reserve.collateral.supply_pubkey.to_string(),
+            },
+            config: (&reserve.config).into(),
+            rate_limiter: reserve.rate_limiter.config.into(),
+        }
+    }
+}
+
+/// Decoded obligation. Decimal values are printed in their decimal string form.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliObligation {
+    pub address: String,
+    pub version: u8,
+    pub last_update: CliLastUpdate,
+    pub lending_market: String,
+    pub owner: String,
+    pub deposits: Vec<CliObligationCollateral>,
+    pub borrows: Vec<CliObligationLiquidity>,
+    pub deposited_value: String,
+    pub borrowed_value: String,
+    pub borrowed_value_upper_bound: String,
+    pub allowed_borrow_value: String,
+    pub unhealthy_borrow_value: String,
+    pub super_unhealthy_borrow_value: String,
+    pub borrowing_isolated_asset: bool,
+}
+
+/// Decoded obligation deposit
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliObligationCollateral {
+    pub deposit_reserve: String,
+    pub deposited_amount: u64,
+    pub market_value: String,
+}
+
+/// Decoded obligation borrow
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliObligationLiquidity {
+    pub borrow_reserve: String,
+    pub cumulative_borrow_rate_wads: String,
+    pub borrowed_amount_wads: String,
+    pub market_value: String,
+}
+
+impl CliObligation {
+    /// Decoded obligation at `address`
+    pub fn new(address: &Pubkey, obligation: &Obligation) -> Self {
+        Self {
+            address: address.to_string(),
+            version: obligation.version,
+            last_update: (&obligation.last_update).into(),
+            lending_market: obligation.lending_market.to_string(),
+            owner: obligation.owner.to_string(),
+            deposits: obligation
+                .deposits
+                .iter()
+                .map(|deposit| CliObligationCollateral {
+                    deposit_reserve: deposit.deposit_reserve.to_string(),
+                    deposited_amount: deposit.deposited_amount,
+                    market_value: deposit.market_value.to_string(),
+                })
+                .collect(),
+            borrows: obligation
+                .borrows
+                .iter()
+                .map(|borrow| CliObligationLiquidity {
+                    borrow_reserve: borrow.borrow_reserve.to_string(),
+                    cumulative_borrow_rate_wads: borrow.cumulative_borrow_rate_wads.to_string(),
+                    borrowed_amount_wads: borrow.borrowed_amount_wads.to_string(),
+                    market_value: borrow.market_value.to_string(),
+                })
+                .collect(),
+            deposited_value: obligation.deposited_value.to_string(),
+            borrowed_value: obligation.borrowed_value.to_string(),
+            borrowed_value_upper_bound: obligation.borrowed_value_upper_bound.to_string(),
+            allowed_borrow_value: obligation.allowed_borrow_value.to_string(),
+            unhealthy_borrow_value: obligation.unhealthy_borrow_value.to_string(),
+            super_unhealthy_borrow_value: obligation.super_unhealthy_borrow_value.to_string(),
+            borrowing_isolated_asset: obligation.borrowing_isolated_asset,
+        }
+    }
+}
+
+/// Quote currency as text when it is a null padded string such as "USD", otherwise as a pubkey
+fn quote_currency_to_string(quote_currency: &[u8; 32]) -> String {
+    let len = quote_currency
+        .iter()
+        .position(|b| *b == 0)
+        .unwrap_or(quote_currency.len());
+    match std::str::from_utf8(&quote_currency[..len]) {
+        Ok(text) if quote_currency[len..].iter().all(|b| *b == 0) => text.to_string(),
+        _ => Pubkey::new_from_array(*quote_currency).to_string(),
+    }
+}
@@ -0,0 +1,213 @@
+//! Named profiles selected with `--profile`, so the cluster, program, market and reserves of a
+//! deployment don't have to be passed to every command.
+//!
+//! Profiles are read from a YAML file, `~/.config/solend/profiles.yml` unless `--profiles` says
+//! otherwise:
+//!
+//! ```yaml
+//! profiles:
+//!   main:
+//!     url: https://api.mainnet-beta.solana.com
+//!     program_id: So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo
+//!     market: 4UpD2fh7xH3VP9QQaXtsS1YY3bxzWhtfpks7FatyKvdY
+//!     reserves:
+//!       SOL: 8PbodeaosQP19SjYFx855UMqWxH2HynZLdBXmsrbac36
+//! ```
+//!
+//! Every field is optional. A profile in the file takes precedence over a preset of the same name.
+
+use {
+    clap::ArgMatches,
+    serde::Deserialize,
+    solana_program::pubkey::Pubkey,
+    std::{collections::BTreeMap, env, error::Error, fs, path::PathBuf, str::FromStr},
+};
+
+/// Presets for well known markets, available without a profiles file
+const PRESETS: &[(&str, &str)] = &[(
+    "mainnet",
+    "url: https://api.mainnet-beta.solana.com
+program_id: So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo
+market: 4UpD2fh7xH3VP9QQaXtsS1YY3bxzWhtfpks7FatyKvdY
+reserves:
+  SOL: 8PbodeaosQP19SjYFx855UMqWxH2HynZLdBXmsrbac36
+  USDC: BgxfHJDzm44T7XG68MYKx7YisTjZu73tVovyZSjJMpmw
+",
+)];
+
+#[derive(Debug, Default, Deserialize)]
+struct ProfilesFile {
+    #[serde(default)]
+    profiles: BTreeMap<String, RawProfile>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawProfile {
+    url: Option<String>,
+    program_id: Option<String>,
+    market: Option<String>,
+    #[serde(default)]
+    reserves: BTreeMap<String, String>,
+}
+
+/// Addresses of one deployment. The default profile is empty, so every address must be passed
+/// as an argument.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Profile {
+    /// JSON RPC URL of the cluster
+    pub json_rpc_url: Option<String>,
+    /// Lending program id
+    pub lending_program_id: Option<Pubkey>,
+    /// Lending market, used when a command's `--market` is left out
+    pub lending_market: Option<Pubkey>,
+    /// Reserves by name, which reserve arguments accept in place of an address
+    pub reserves: BTreeMap<String, Pubkey>,
+}
+
+fn parse_pubkey(profile: &str, field: &str, value: &str) -> Result<Pubkey, Box<dyn Error>> {
+    Pubkey::from_str(value).map_err(|_| {
+        format!(
+            "profile {}: {} is not a valid pubkey: {}",
+            profile, field, value
+        )
+        .into()
+    })
+}
+
+impl Profile {
+    fn from_raw(name: &str, raw: RawProfile) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            json_rpc_url: raw.url,
+            lending_program_id: raw
+                .program_id
+                .map(|id| parse_pubkey(name, "program_id", &id))
+                .transpose()?,
+            lending_market: raw
+                .market
+                .map(|market| parse_pubkey(name, "market", &market))
+                .transpose()?,
+            reserves: raw
+                .reserves
+                .into_iter()
+                .map(|(reserve, address)| {
+                    let pubkey = parse_pubkey(name, &format!("reserve {}", reserve), &address)?;
+                    Ok((reserve, pubkey))
+                })
+                .collect::<Result<_, Box<dyn Error>>>()?,
+        })
+    }
+
+    /// Loads profile `name` from the profiles file at `path`, or the default profiles file,
+    /// falling back to the presets
+    pub fn load(name: &str, path: Option<&str>) -> Result<Self, Box<dyn Error>> {
+        let path = path.map(PathBuf::from).or_else(default_profiles_file);
+        let mut file = match path {
+            Some(path) if path.exists() => {
+                serde_yaml::from_str::<ProfilesFile>(&fs::read_to_string(&path)?)
+                    .map_err(|e| format!("invalid profiles file {}: {}", path.display(), e))?
+            }
+            _ => ProfilesFile::default(),
+        };
+
+        if let Some(raw) = file.profiles.remove(name) {
+            return Self::from_raw(name, raw);
+        }
+        match PRESETS.iter().find(|(preset, _)| *preset == name) {
+            Some((_, preset)) => Self::from_raw(name, serde_yaml::from_str(preset)?),
+            None => Err(format!("no profile named {}", name).into()),
+        }
+    }
+
+    /// The lending market given as argument `arg`, or else this profile's market
+    pub fn lending_market_of(
+        &self,
+        matches: &ArgMatches<'_>,
+        arg: &str,
+    ) -> Result<Pubkey, Box<dyn Error>> {
+        match matches.value_of(arg) {
+            Some(market) => {
+                Pubkey::from_str(market).map_err(|_| format!("invalid market: {}", market).into())
+            }
+            None => self
+                .lending_market
+                .ok_or_else(|| "--market is required when the profile has no market".into()),
+        }
+    }
+
+    /// The reserve given as argument `arg`, either an address or the name of one of this
+    /// profile's reserves
+    pub fn reserve_of(
+        &self,
+        matches: &ArgMatches<'_>,
+        arg: &str,
+    ) -> Result<Pubkey, Box<dyn Error>> {
+        let reserve = matches.value_of(arg).ok_or("reserve is required")?;
+        if let Some(pubkey) = self.reserves.get(reserve) {
+            return Ok(*pubkey);
+        }
+        Pubkey::from_str(reserve).map_err(|_| {
+            format!(
+                "{} is neither a reserve address nor a reserve of the profile",
+                reserve
+            )
+            .into()
+        })
+    }
+}
+
+/// `~/.config/solend/profiles.yml`
+fn default_profiles_file() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| {
+        PathBuf::from(home)
+            .join(".config")
+            .join("solend")
+            .join("profiles.yml")
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_presets_parse() {
+        for (name, _) in PRESETS {
+            let profile = Profile::load(name, Some("/nonexistent/profiles.yml")).unwrap();
+            assert!(profile.lending_market.is_some());
+        }
+    }
+
+    #[test]
+    fn test_load_from_file() {
+        let path = env::temp_dir().join(format!("solend-profiles-{}.yml", std::process::id()));
+        let market = Pubkey::new_unique();
+        let reserve = Pubkey::new_unique();
+        writeln!(
+            fs::File::create(&path).unwrap(),
+            "profiles:\n  devnet:\n    url: http://localhost:8899\n    market: {}\n    reserves:\n      USDC: {}\n  mainnet:\n    market: {}",
+            market, reserve, reserve
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+
+        assert_eq!(
+            Profile::load("devnet", Some(path)).unwrap(),
+            Profile {
+                json_rpc_url: Some("http://localhost:8899".to_string()),
+                lending_program_id: None,
+                lending_market: Some(market),
+                reserves: BTreeMap::from([("USDC".to_string(), reserve)]),
+            }
+        );
+        // the file overrides the preset
+        assert_eq!(
+            Profile::load("mainnet", Some(path)).unwrap().lending_market,
+            Some(reserve)
+        );
+        assert!(Profile::load("testnet", Some(path)).is_err());
+
+        fs::remove_file(path).unwrap();
+    }
+}
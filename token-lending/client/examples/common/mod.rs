@@ -0,0 +1,357 @@
+//! The lending happy path shared by the examples: create a mint, create a lending market and a
+//! reserve for it, deposit, borrow, repay and withdraw.
+
+use solana_client::{client_error::Result as ClientResult, rpc_client::RpcClient};
+use solana_sdk::{
+    instruction::Instruction,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction::{create_account, create_account_with_seed},
+};
+use solend_client::send::{send_and_confirm, SendConfig};
+use solend_sdk::{
+    builder::BorrowBuilder,
+    instruction::{
+        deposit_reserve_liquidity_and_obligation_collateral, init_lending_market, init_obligation,
+        init_reserve, repay_obligation_liquidity,
+        withdraw_obligation_collateral_and_redeem_reserve_collateral,
+    },
+    state::{LendingMarket, Obligation, Reserve, ReserveConfig, ReserveFees, ReserveType},
+};
+use spl_forks_pdas::{obligation_address, obligation_seed};
+use spl_token::state::{Account as Token, Mint};
+
+const QUOTE_CURRENCY: [u8; 32] = *b"USD\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
+const DECIMALS: u8 = 6;
+const MINT_AMOUNT: u64 = 1_000 * 1_000_000;
+const INITIAL_LIQUIDITY: u64 = 10 * 1_000_000;
+const DEPOSIT_AMOUNT: u64 = 100 * 1_000_000;
+const BORROW_AMOUNT: u64 = 10 * 1_000_000;
+
+/// Accounts the happy path runs with
+pub struct Demo {
+    pub rpc_client: RpcClient,
+    pub payer: Box<dyn Signer>,
+    pub program_id: Pubkey,
+    /// Owner of the Pyth accounts, set on the lending market
+    pub oracle_program_id: Pubkey,
+    /// Switchboard program set on the lending market
+    pub switchboard_oracle_program_id: Pubkey,
+    pub pyth_product: Pubkey,
+    pub pyth_price: Pubkey,
+    pub switchboard_feed: Pubkey,
+}
+
+impl Demo {
+    fn send(
+        &self,
+        step: &str,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> ClientResult<()> {
+        let mut all_signers: Vec<&dyn Signer> = vec![self.payer.as_ref()];
+        all_signers.extend(signers.iter().map(|signer| *signer as &dyn Signer));
+        let signature = send_and_confirm(
+            &self.rpc_client,
+            instructions,
+            &self.payer.pubkey(),
+            &all_signers,
+            &SendConfig::default(),
+        )?;
+        println!("{}: {}", step, signature);
+        Ok(())
+    }
+
+    fn rent(&self, len: usize) -> ClientResult<u64> {
+        self.rpc_client.get_minimum_balance_for_rent_exemption(len)
+    }
+
+    fn create_token_account(
+        &self,
+        account: &Keypair,
+        mint: &Pubkey,
+    ) -> ClientResult<Vec<Instruction>> {
+        Ok(vec![
+            create_account(
+                &self.payer.pubkey(),
+                &account.pubkey(),
+                self.rent(Token::LEN)?,
+                Token::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &account.pubkey(),
+                mint,
+                &self.payer.pubkey(),
+            )
+            .unwrap(),
+        ])
+    }
+
+    /// Creates a mint, a lending market and a reserve for the mint, then deposits, borrows,
+    /// repays and withdraws
+    pub fn run(&self) -> ClientResult<()> {
+        let payer = self.payer.pubkey();
+
+        // a mint for the reserve's liquidity, and a token account holding some of it
+        let mint = Keypair::new();
+        let wallet = Keypair::new();
+        let mut instructions = vec![
+            create_account(
+                &payer,
+                &mint.pubkey(),
+                self.rent(Mint::LEN)?,
+                Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &payer,
+                None,
+                DECIMALS,
+            )
+            .unwrap(),
+        ];
+        instructions.extend(self.create_token_account(&wallet, &mint.pubkey())?);
+        instructions.push(
+            spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &wallet.pubkey(),
+                &payer,
+                &[],
+                MINT_AMOUNT,
+            )
+            .unwrap(),
+        );
+        self.send("Create mint", &instructions, &[&mint, &wallet])?;
+
+        // the lending market, owned by the payer
+        let lending_market = Keypair::new();
+        self.send(
+            "Create lending market",
+            &[
+                create_account(
+                    &payer,
+                    &lending_market.pubkey(),
+                    self.rent(LendingMarket::LEN)?,
+                    LendingMarket::LEN as u64,
+                    &self.program_id,
+                ),
+                init_lending_market(
+                    self.program_id,
+                    payer,
+                    QUOTE_CURRENCY,
+                    lending_market.pubkey(),
+                    self.oracle_program_id,
+                    self.switchboard_oracle_program_id,
+                ),
+            ],
+            &[&lending_market],
+        )?;
+
+        // the reserve and its token accounts, seeded with some liquidity
+        let reserve = Keypair::new();
+        let collateral_mint = Keypair::new();
+        let collateral_supply = Keypair::new();
+        let liquidity_supply = Keypair::new();
+        let user_collateral = Keypair::new();
+        let fee_receiver = Keypair::new();
+        let mut instructions = vec![];
+        for (account, len, owner) in [
+            (&reserve, Reserve::LEN, &self.program_id),
+            (&collateral_mint, Mint::LEN, &spl_token::id()),
+            (&collateral_supply, Token::LEN, &spl_token::id()),
+            (&liquidity_supply, Token::LEN, &spl_token::id()),
+            (&user_collateral, Token::LEN, &spl_token::id()),
+        ] {
+            instructions.push(create_account(
+                &payer,
+                &account.pubkey(),
+                self.rent(len)?,
+                len as u64,
+                owner,
+            ));
+        }
+        self.send(
+            "Create reserve accounts",
+            &instructions,
+            &[
+                &reserve,
+                &collateral_mint,
+                &collateral_supply,
+                &liquidity_supply,
+                &user_collateral,
+            ],
+        )?;
+
+        let mut instructions = self.create_token_account(&fee_receiver, &mint.pubkey())?;
+        instructions.push(init_reserve(
+            self.program_id,
+            INITIAL_LIQUIDITY,
+            ReserveConfig {
+                optimal_utilization_rate: 80,
+                max_utilization_rate: 90,
+                loan_to_value_ratio: 50,
+                liquidation_bonus: 5,
+                max_liquidation_bonus: 5,
+                liquidation_threshold: 55,
+                max_liquidation_threshold: 65,
+                min_borrow_rate: 0,
+                optimal_borrow_rate: 4,
+                max_borrow_rate: 30,
+                super_max_borrow_rate: 50,
+                fees: ReserveFees {
+                    borrow_fee_wad: 0,
+                    flash_loan_fee_wad: 0,
+                    host_fee_percentage: 0,
+                },
+                deposit_limit: u64::MAX,
+                borrow_limit: u64::MAX,
+                fee_receiver: fee_receiver.pubkey(),
+                protocol_liquidation_fee: 0,
+                protocol_take_rate: 0,
+                added_borrow_weight_bps: 0,
+                reserve_type: ReserveType::Regular,
+            },
+            wallet.pubkey(),
+            user_collateral.pubkey(),
+            reserve.pubkey(),
+            mint.pubkey(),
+            liquidity_supply.pubkey(),
+            collateral_mint.pubkey(),
+            collateral_supply.pubkey(),
+            self.pyth_product,
+            self.pyth_price,
+            self.switchboard_feed,
+            lending_market.pubkey(),
+            payer,
+            payer,
+        ));
+        self.send("Init reserve", &instructions, &[&fee_receiver])?;
+
+        // an obligation at the payer's usual address in this market
+        let obligation = obligation_address(&payer, &lending_market.pubkey(), &self.program_id)
+            .expect("obligation seed is valid");
+        self.send(
+            "Create obligation",
+            &[
+                create_account_with_seed(
+                    &payer,
+                    &obligation,
+                    &payer,
+                    &obligation_seed(&lending_market.pubkey()),
+                    self.rent(Obligation::LEN)?,
+                    Obligation::LEN as u64,
+                    &self.program_id,
+                ),
+                init_obligation(self.program_id, obligation, lending_market.pubkey(), payer),
+            ],
+            &[],
+        )?;
+
+        let refresh_reserve = solend_sdk::instruction::refresh_reserve(
+            self.program_id,
+            reserve.pubkey(),
+            self.pyth_price,
+            self.switchboard_feed,
+        );
+        let refresh_obligation = solend_sdk::instruction::refresh_obligation(
+            self.program_id,
+            obligation,
+            vec![reserve.pubkey()],
+        );
+
+        self.send(
+            "Deposit",
+            &[
+                refresh_reserve.clone(),
+                deposit_reserve_liquidity_and_obligation_collateral(
+                    self.program_id,
+                    DEPOSIT_AMOUNT,
+                    wallet.pubkey(),
+                    user_collateral.pubkey(),
+                    reserve.pubkey(),
+                    liquidity_supply.pubkey(),
+                    collateral_mint.pubkey(),
+                    lending_market.pubkey(),
+                    collateral_supply.pubkey(),
+                    obligation,
+                    payer,
+                    self.pyth_price,
+                    self.switchboard_feed,
+                    payer,
+                ),
+            ],
+            &[],
+        )?;
+
+        self.send(
+            "Borrow",
+            &[
+                refresh_reserve.clone(),
+                refresh_obligation.clone(),
+                BorrowBuilder::new(self.program_id, BORROW_AMOUNT)
+                    .source_liquidity(liquidity_supply.pubkey())
+                    .destination_liquidity(wallet.pubkey())
+                    .borrow_reserve(reserve.pubkey())
+                    .borrow_reserve_liquidity_fee_receiver(fee_receiver.pubkey())
+                    .obligation(obligation)
+                    .lending_market(lending_market.pubkey())
+                    .obligation_owner(payer)
+                    .build(),
+            ],
+            &[],
+        )?;
+
+        self.send(
+            "Repay",
+            &[
+                refresh_reserve.clone(),
+                repay_obligation_liquidity(
+                    self.program_id,
+                    u64::MAX,
+                    wallet.pubkey(),
+                    liquidity_supply.pubkey(),
+                    reserve.pubkey(),
+                    obligation,
+                    lending_market.pubkey(),
+                    payer,
+                ),
+            ],
+            &[],
+        )?;
+
+        // a fully repaid borrow is removed, so the obligation is refreshed with its deposit only
+        self.send(
+            "Withdraw",
+            &[
+                refresh_reserve,
+                refresh_obligation,
+                withdraw_obligation_collateral_and_redeem_reserve_collateral(
+                    self.program_id,
+                    u64::MAX,
+                    collateral_supply.pubkey(),
+                    user_collateral.pubkey(),
+                    reserve.pubkey(),
+                    obligation,
+                    lending_market.pubkey(),
+                    wallet.pubkey(),
+                    collateral_mint.pubkey(),
+                    liquidity_supply.pubkey(),
+                    payer,
+                    payer,
+                ),
+            ],
+            &[],
+        )?;
+
+        println!("Lending market: {}", lending_market.pubkey());
+        println!("Reserve: {}", reserve.pubkey());
+        println!("Obligation: {}", obligation);
+        Ok(())
+    }
+}
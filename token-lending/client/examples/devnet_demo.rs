@@ -0,0 +1,127 @@
+//! Runs the lending happy path against a cluster, devnet by default: create a mint, create a
+//! lending market and a reserve for it, deposit, borrow, repay and withdraw.
+//!
+//! ```sh
+//! cargo run -p solend-client --example devnet_demo -- --url https://api.devnet.solana.com
+//! ```
+//!
+//! The payer, `--keypair` or the Solana CLI keypair, needs about 0.1 SOL. The reserve prices the
+//! new mint with the given oracles, SOL/USD on devnet by default, which must be fresh for the
+//! refreshes to succeed.
+
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use clap::{crate_description, crate_name, crate_version, value_t, App, Arg};
+use common::Demo;
+use solana_clap_utils::{
+    input_parsers::pubkey_of,
+    input_validators::{is_pubkey, is_url, is_valid_signer},
+    keypair::DefaultSigner,
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::process::exit;
+
+const PYTH_PROGRAM_ID_DEV: &str = "gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s";
+const SWITCHBOARD_PROGRAM_ID_DEV: &str = "7azgmy1pFXHikv36q1zZASvFq5vFa39TT9NweVugKKTU";
+const PYTH_PRODUCT_SOL_USD_DEV: &str = "3Mnn2fX6rQyUsyELYms1sBJyChWofzSNRoqYzvgMVz5E";
+const PYTH_PRICE_SOL_USD_DEV: &str = "J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix";
+const SWITCHBOARD_SOL_USD_DEV: &str = "GvDMxPzN1sCj7L26YDK2HnMRXEQmQ2aemov8YBtPS7vR";
+
+fn main() {
+    let default_program_id: &str = &solend_sdk::solend_devnet::id().to_string();
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .help("JSON RPC URL for the cluster.  Default from the configuration file."),
+        )
+        .arg(
+            Arg::with_name("keypair")
+                .long("keypair")
+                .value_name("KEYPAIR")
+                .validator(is_valid_signer)
+                .takes_value(true)
+                .help(
+                    "Payer and owner of everything created.  Default from the configuration file.",
+                ),
+        )
+        .arg(
+            Arg::with_name("lending_program_id")
+                .long("program")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .default_value(default_program_id)
+                .help("Lending program ID"),
+        )
+        .arg(
+            Arg::with_name("pyth_product")
+                .long("pyth-product")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .default_value(PYTH_PRODUCT_SOL_USD_DEV)
+                .help("Pyth product account the reserve is priced with"),
+        )
+        .arg(
+            Arg::with_name("pyth_price")
+                .long("pyth-price")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .default_value(PYTH_PRICE_SOL_USD_DEV)
+                .help("Pyth price account the reserve is priced with"),
+        )
+        .arg(
+            Arg::with_name("switchboard_feed")
+                .long("switchboard-feed")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .default_value(SWITCHBOARD_SOL_USD_DEV)
+                .help("Switchboard feed the reserve is priced with"),
+        )
+        .get_matches();
+
+    let cli_config = solana_cli_config::CONFIG_FILE
+        .as_ref()
+        .and_then(|config_file| solana_cli_config::Config::load(config_file).ok())
+        .unwrap_or_default();
+    let json_rpc_url = value_t!(matches, "json_rpc_url", String).unwrap_or(cli_config.json_rpc_url);
+    let default_signer = DefaultSigner::new(
+        "keypair",
+        matches
+            .value_of("keypair")
+            .map(str::to_string)
+            .unwrap_or(cli_config.keypair_path),
+    );
+    let payer = default_signer
+        .signer_from_path(&matches, &mut None)
+        .unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            exit(1);
+        });
+
+    let demo = Demo {
+        rpc_client: RpcClient::new_with_commitment(json_rpc_url, CommitmentConfig::confirmed()),
+        payer,
+        program_id: pubkey_of(&matches, "lending_program_id").unwrap(),
+        oracle_program_id: PYTH_PROGRAM_ID_DEV.parse().unwrap(),
+        switchboard_oracle_program_id: SWITCHBOARD_PROGRAM_ID_DEV.parse().unwrap(),
+        pyth_product: pubkey_of(&matches, "pyth_product").unwrap(),
+        pyth_price: pubkey_of(&matches, "pyth_price").unwrap(),
+        switchboard_feed: pubkey_of(&matches, "switchboard_feed").unwrap(),
+    };
+    if let Err(err) = demo.run() {
+        eprintln!("error: {}", err);
+        exit(1);
+    }
+}
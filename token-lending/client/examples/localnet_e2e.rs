@@ -0,0 +1,274 @@
+//! Boots a `solana-test-validator` with the lending program from the workspace build, seeds it
+//! with the SOL/USD Pyth accounts from the program's test fixtures and runs the lending happy
+//! path against it, so the whole stack can be exercised without setting up a validator by hand.
+//!
+//! ```sh
+//! cargo build-bpf --manifest-path token-lending/program/Cargo.toml
+//! cargo run -p solend-client --example localnet_e2e
+//! ```
+//!
+//! The validator starts at the slot the fixture price was published in, so the price is fresh for
+//! the length of the run. Accounts downloaded with `solend-cli download-fixtures` can be added
+//! with `--fixtures`. The ledger is deleted when the run ends.
+
+#![allow(clippy::result_large_err)]
+
+mod common;
+
+use clap::{crate_description, crate_name, crate_version, value_t, App, Arg};
+use common::Demo;
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_clap_utils::input_validators::is_port;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    commitment_config::CommitmentConfig,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+};
+use std::{
+    error::Error,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    process::{exit, Child, Command, Stdio},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+const PYTH_PROGRAM_ID_DEV: &str = "gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s";
+const PYTH_PRODUCT_SOL_USD_DEV: &str = "3Mnn2fX6rQyUsyELYms1sBJyChWofzSNRoqYzvgMVz5E";
+const PYTH_PRICE_SOL_USD_DEV: &str = "J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix";
+
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const AIRDROP: u64 = 10 * LAMPORTS_PER_SOL;
+
+/// A running test validator, stopped and its ledger deleted on drop
+struct Validator {
+    process: Child,
+    dir: PathBuf,
+    rpc_url: String,
+}
+
+impl Validator {
+    fn start(
+        validator: &str,
+        program_so: &Path,
+        accounts: &[(Pubkey, Account)],
+        warp_slot: u64,
+        rpc_port: u16,
+    ) -> Result<Self, Box<dyn Error>> {
+        let dir = std::env::temp_dir().join(format!("solend-localnet-{}", std::process::id()));
+        fs::create_dir_all(dir.join("accounts"))?;
+
+        let mut command = Command::new(validator);
+        command
+            .arg("--ledger")
+            .arg(dir.join("ledger"))
+            .arg("--reset")
+            .arg("--quiet")
+            .args(["--rpc-port", &rpc_port.to_string()])
+            .args(["--warp-slot", &warp_slot.to_string()])
+            .arg("--bpf-program")
+            .arg(solend_sdk::solend_devnet::id().to_string())
+            .arg(program_so);
+        for (pubkey, account) in accounts {
+            // the `solana account --output json` format the validator loads accounts from
+            let path = dir.join("accounts").join(format!("{}.json", pubkey));
+            let fixture = serde_json::json!({
+                "pubkey": pubkey.to_string(),
+                "account": UiAccount::encode(pubkey, account, UiAccountEncoding::Base64, None, None),
+            });
+            fs::write(&path, serde_json::to_string(&fixture)?)?;
+            command.arg("--account").arg(pubkey.to_string()).arg(path);
+        }
+
+        let process = command
+            .stdout(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("couldn't start {}: {}", validator, e))?;
+        Ok(Self {
+            process,
+            dir,
+            rpc_url: format!("http://127.0.0.1:{}", rpc_port),
+        })
+    }
+
+    /// Waits until the validator answers health checks
+    fn wait_until_healthy(&mut self, rpc_client: &RpcClient) -> Result<(), Box<dyn Error>> {
+        let start = Instant::now();
+        while rpc_client.get_health().is_err() {
+            if let Some(status) = self.process.try_wait()? {
+                return Err(format!("validator exited with {}", status).into());
+            }
+            if start.elapsed() > STARTUP_TIMEOUT {
+                return Err("validator didn't become healthy in time".into());
+            }
+            sleep(POLL_INTERVAL);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Validator {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// A program test fixture, raw account data as saved by `solana account --output-file`
+fn read_raw_fixture(name: &str, owner: &Pubkey) -> Result<(Pubkey, Account), Box<dyn Error>> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../program/tests/fixtures")
+        .join(format!("{}.bin", name));
+    let data = fs::read(&path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+    Ok((
+        name.parse()?,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: *owner,
+            executable: false,
+            rent_epoch: 0,
+        },
+    ))
+}
+
+/// Every `*.json` account in `dir`, as written by `solend-cli download-fixtures`
+fn read_fixture_dir(dir: &str) -> Result<Vec<(Pubkey, Account)>, Box<dyn Error>> {
+    let mut accounts = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension() != Some(OsStr::new("json")) {
+            continue;
+        }
+        let fixture: serde_json::Value = serde_json::from_slice(&fs::read(&path)?)?;
+        let account: UiAccount = serde_json::from_value(fixture["account"].clone())?;
+        let pubkey = fixture["pubkey"].as_str().unwrap_or_default().parse()?;
+        let account = account
+            .decode()
+            .ok_or_else(|| format!("couldn't decode {}", path.display()))?;
+        accounts.push((pubkey, account));
+    }
+    Ok(accounts)
+}
+
+fn request_airdrop(rpc_client: &RpcClient, to: &Pubkey) -> Result<(), Box<dyn Error>> {
+    let signature = rpc_client.request_airdrop(to, AIRDROP)?;
+    let start = Instant::now();
+    while !rpc_client.confirm_transaction(&signature)? {
+        if start.elapsed() > STARTUP_TIMEOUT {
+            return Err("airdrop wasn't confirmed in time".into());
+        }
+        sleep(POLL_INTERVAL);
+    }
+    Ok(())
+}
+
+fn run(
+    validator: &str,
+    program_so: &Path,
+    fixtures: Option<&str>,
+    rpc_port: u16,
+) -> Result<(), Box<dyn Error>> {
+    if !program_so.exists() {
+        return Err(format!(
+            "{} doesn't exist, build the program with `cargo build-bpf` first",
+            program_so.display()
+        )
+        .into());
+    }
+
+    let oracle_program_id: Pubkey = PYTH_PROGRAM_ID_DEV.parse()?;
+    let product = read_raw_fixture(PYTH_PRODUCT_SOL_USD_DEV, &oracle_program_id)?;
+    let price = read_raw_fixture(PYTH_PRICE_SOL_USD_DEV, &oracle_program_id)?;
+    let warp_slot = pyth_sdk_solana::state::load_price_account(&price.1.data)
+        .map_err(|e| format!("invalid Pyth price fixture: {:?}", e))?
+        .agg
+        .pub_slot;
+
+    let mut accounts = vec![product, price];
+    if let Some(dir) = fixtures {
+        accounts.extend(read_fixture_dir(dir)?);
+    }
+
+    let mut validator = Validator::start(validator, program_so, &accounts, warp_slot, rpc_port)?;
+    let rpc_client =
+        RpcClient::new_with_commitment(validator.rpc_url.clone(), CommitmentConfig::confirmed());
+    validator.wait_until_healthy(&rpc_client)?;
+    println!("Validator: {}", validator.rpc_url);
+
+    let payer = Keypair::new();
+    request_airdrop(&rpc_client, &payer.pubkey())?;
+
+    Demo {
+        rpc_client,
+        payer: Box::new(payer),
+        program_id: solend_sdk::solend_devnet::id(),
+        oracle_program_id,
+        switchboard_oracle_program_id: solend_sdk::switchboard_v2_devnet::id(),
+        pyth_product: PYTH_PRODUCT_SOL_USD_DEV.parse()?,
+        pyth_price: PYTH_PRICE_SOL_USD_DEV.parse()?,
+        switchboard_feed: solend_sdk::NULL_PUBKEY,
+    }
+    .run()?;
+    Ok(())
+}
+
+fn main() {
+    let default_program_so = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../target/deploy/solend_program.so")
+        .display()
+        .to_string();
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .arg(
+            Arg::with_name("program_so")
+                .long("program-so")
+                .value_name("PATH")
+                .takes_value(true)
+                .default_value(&default_program_so)
+                .help("Lending program built with `cargo build-bpf`"),
+        )
+        .arg(
+            Arg::with_name("fixtures")
+                .long("fixtures")
+                .value_name("DIR")
+                .takes_value(true)
+                .help("Directory of accounts written by `solend-cli download-fixtures` to seed"),
+        )
+        .arg(
+            Arg::with_name("validator")
+                .long("validator")
+                .value_name("PATH")
+                .takes_value(true)
+                .default_value("solana-test-validator")
+                .help("Test validator executable"),
+        )
+        .arg(
+            Arg::with_name("rpc_port")
+                .long("rpc-port")
+                .value_name("PORT")
+                .takes_value(true)
+                .validator(is_port)
+                .default_value("8899")
+                .help("JSON RPC port of the validator"),
+        )
+        .get_matches();
+
+    if let Err(err) = run(
+        matches.value_of("validator").unwrap(),
+        Path::new(matches.value_of("program_so").unwrap()),
+        matches.value_of("fixtures"),
+        value_t!(matches, "rpc_port", u16).unwrap(),
+    ) {
+        eprintln!("error: {}", err);
+        exit(1);
+    }
+}
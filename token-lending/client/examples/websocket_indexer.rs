@@ -0,0 +1,289 @@
+//! Keeps a live in-memory view of the lending markets and reserves of a lending program, and
+//! prints the events its transactions emit: a starting point for an indexer.
+//!
+//! ```sh
+//! cargo run -p solend-client --example websocket_indexer -- --url https://api.devnet.solana.com
+//! ```
+//!
+//! The view is seeded with `getProgramAccounts` and then follows the RPC node's websocket
+//! `programSubscribe` and `logsSubscribe` notifications. Both streams are turned into [Update]s,
+//! so another source of account and transaction updates can feed the same loop.
+
+#![allow(clippy::result_large_err)]
+
+use clap::{crate_description, crate_name, crate_version, value_t, App, Arg};
+use solana_account_decoder::UiAccountEncoding;
+use solana_clap_utils::{input_parsers::pubkey_of, input_validators::is_pubkey};
+use solana_client::{
+    pubsub_client::PubsubClient,
+    rpc_client::RpcClient,
+    rpc_config::{
+        RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionLogsConfig,
+        RpcTransactionLogsFilter,
+    },
+    rpc_filter::RpcFilterType,
+};
+use solana_sdk::{
+    account::Account,
+    clock::Slot,
+    commitment_config::CommitmentConfig,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+};
+use solend_client::filters;
+use solend_event_parser::{parse_logs, LendingEvent};
+use solend_sdk::state::{LendingMarket, Reserve};
+use std::{
+    collections::HashMap,
+    process::exit,
+    sync::mpsc::{channel, Sender},
+    thread,
+};
+
+/// An account or transaction notification from the validator
+enum Update {
+    Account {
+        slot: Slot,
+        pubkey: Pubkey,
+        account: Account,
+    },
+    Transaction {
+        slot: Slot,
+        signature: String,
+        logs: Vec<String>,
+    },
+}
+
+/// Latest known state of the lending markets and reserves of a program
+#[derive(Default)]
+struct MarketView {
+    lending_markets: HashMap<Pubkey, (Slot, LendingMarket)>,
+    reserves: HashMap<Pubkey, (Slot, Reserve)>,
+}
+
+impl MarketView {
+    /// Applies an account update, ignoring updates older than the state already held. Accounts
+    /// that no longer decode, e.g. because they were closed, are dropped from the view.
+    fn apply_account(&mut self, slot: Slot, pubkey: Pubkey, account: &Account) {
+        match account.data.len() {
+            LendingMarket::LEN => apply(&mut self.lending_markets, slot, pubkey, account),
+            Reserve::LEN => apply(&mut self.reserves, slot, pubkey, account),
+            _ => {
+                self.lending_markets.remove(&pubkey);
+                self.reserves.remove(&pubkey);
+            }
+        }
+    }
+
+    fn print_reserve(&self, pubkey: &Pubkey) {
+        if let Some((slot, reserve)) = self.reserves.get(pubkey) {
+            println!(
+                "[{}] reserve {} of market {}: {} available, {} borrowed",
+                slot,
+                pubkey,
+                reserve.lending_market,
+                reserve.liquidity.available_amount,
+                reserve.liquidity.borrowed_amount_wads,
+            );
+        }
+    }
+}
+
+fn apply<T: Pack + IsInitialized>(
+    states: &mut HashMap<Pubkey, (Slot, T)>,
+    slot: Slot,
+    pubkey: Pubkey,
+    account: &Account,
+) {
+    if matches!(states.get(&pubkey), Some((held, _)) if *held > slot) {
+        return;
+    }
+    match T::unpack(&account.data) {
+        Ok(state) => {
+            states.insert(pubkey, (slot, state));
+        }
+        Err(_) => {
+            states.remove(&pubkey);
+        }
+    }
+}
+
+/// Seeds `view` with the current lending markets and reserves of `program_id`
+fn load_snapshot(rpc_client: &RpcClient, program_id: &Pubkey, view: &mut MarketView) {
+    let slot = rpc_client.get_slot().unwrap_or_default();
+    for filters in [filters::lending_markets(), filters::reserves()] {
+        let accounts = rpc_client
+            .get_program_accounts_with_config(program_id, program_accounts_config(filters))
+            .unwrap_or_else(|err| {
+                eprintln!("error: {}", err);
+                exit(1);
+            });
+        for (pubkey, account) in accounts {
+            view.apply_account(slot, pubkey, &account);
+        }
+    }
+}
+
+fn program_accounts_config(filters: Vec<RpcFilterType>) -> RpcProgramAccountsConfig {
+    RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    }
+}
+
+/// Streams account and transaction notifications of `program_id` into `sender`, one thread per
+/// subscription
+fn subscribe(websocket_url: &str, program_id: &Pubkey, sender: Sender<Update>) {
+    let (_accounts_subscription, accounts) = PubsubClient::program_subscribe(
+        websocket_url,
+        program_id,
+        Some(program_accounts_config(vec![])),
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        exit(1);
+    });
+    let (_logs_subscription, logs) = PubsubClient::logs_subscribe(
+        websocket_url,
+        RpcTransactionLogsFilter::Mentions(vec![program_id.to_string()]),
+        RpcTransactionLogsConfig {
+            commitment: Some(CommitmentConfig::confirmed()),
+        },
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        exit(1);
+    });
+
+    let account_sender = sender.clone();
+    let accounts_thread = thread::spawn(move || {
+        for update in accounts.iter() {
+            let account = match update.value.account.decode::<Account>() {
+                Some(account) => account,
+                None => continue,
+            };
+            let pubkey = match update.value.pubkey.parse() {
+                Ok(pubkey) => pubkey,
+                Err(_) => continue,
+            };
+            let update = Update::Account {
+                slot: update.context.slot,
+                pubkey,
+                account,
+            };
+            if account_sender.send(update).is_err() {
+                break;
+            }
+        }
+    });
+    let logs_thread = thread::spawn(move || {
+        for update in logs.iter() {
+            // failed transactions log events they then roll back
+            if update.value.err.is_some() {
+                continue;
+            }
+            let update = Update::Transaction {
+                slot: update.context.slot,
+                signature: update.value.signature,
+                logs: update.value.logs,
+            };
+            if sender.send(update).is_err() {
+                break;
+            }
+        }
+    });
+
+    // the subscriptions stay open for as long as their threads forward notifications
+    accounts_thread.join().ok();
+    logs_thread.join().ok();
+}
+
+fn main() {
+    let default_program_id: &str = &solend_sdk::solend_devnet::id().to_string();
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .help("JSON RPC URL for the cluster.  Default from the configuration file."),
+        )
+        .arg(
+            Arg::with_name("websocket_url")
+                .long("ws")
+                .value_name("URL")
+                .takes_value(true)
+                .help("Websocket URL for the cluster.  Default derived from the JSON RPC URL."),
+        )
+        .arg(
+            Arg::with_name("lending_program_id")
+                .long("program")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .default_value(default_program_id)
+                .help("Lending program ID"),
+        )
+        .get_matches();
+
+    let cli_config = solana_cli_config::CONFIG_FILE
+        .as_ref()
+        .and_then(|config_file| solana_cli_config::Config::load(config_file).ok())
+        .unwrap_or_default();
+    let json_rpc_url = value_t!(matches, "json_rpc_url", String).unwrap_or(cli_config.json_rpc_url);
+    let websocket_url = value_t!(matches, "websocket_url", String)
+        .unwrap_or_else(|_| solana_cli_config::Config::compute_websocket_url(&json_rpc_url));
+    let program_id = pubkey_of(&matches, "lending_program_id").unwrap();
+
+    let rpc_client = RpcClient::new_with_commitment(json_rpc_url, CommitmentConfig::confirmed());
+    let mut view = MarketView::default();
+    load_snapshot(&rpc_client, &program_id, &mut view);
+    println!(
+        "loaded {} lending markets and {} reserves",
+        view.lending_markets.len(),
+        view.reserves.len()
+    );
+
+    let (sender, receiver) = channel();
+    thread::spawn(move || subscribe(&websocket_url, &program_id, sender));
+
+    for update in receiver {
+        match update {
+            Update::Account {
+                slot,
+                pubkey,
+                account,
+            } => {
+                view.apply_account(slot, pubkey, &account);
+                view.print_reserve(&pubkey);
+            }
+            Update::Transaction {
+                slot,
+                signature,
+                logs,
+            } => {
+                for event in parse_logs(&program_id, &logs) {
+                    match event {
+                        LendingEvent::Liquidated(liquidation) => println!(
+                            "[{}] {}: obligation {} liquidated by {}, {} repaid to {}",
+                            slot,
+                            signature,
+                            liquidation.obligation,
+                            liquidation.liquidator,
+                            liquidation.repay_amount,
+                            liquidation.repay_reserve,
+                        ),
+                        event => println!("[{}] {}: {:?}", slot, signature, event),
+                    }
+                }
+            }
+        }
+    }
+}
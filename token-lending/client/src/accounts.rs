@@ -0,0 +1,108 @@
+//! Batched fetching of lending accounts: addresses are split into `getMultipleAccounts` requests
+//! of the largest size the RPC accepts and the results decoded into their typed state
+
+use solana_client::{
+    client_error::{ClientErrorKind, Result as ClientResult},
+    rpc_client::RpcClient,
+    rpc_request::MAX_MULTIPLE_ACCOUNTS,
+};
+use solana_sdk::{
+    account::Account,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+};
+use solend_sdk::state::{LendingMarket, Obligation, Reserve};
+
+/// Accounts at `addresses`, in order, `None` for those that don't exist
+pub fn get_multiple_accounts(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> ClientResult<Vec<Option<Account>>> {
+    let mut accounts = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        accounts.extend(rpc_client.get_multiple_accounts(chunk)?);
+    }
+    Ok(accounts)
+}
+
+/// Decode `accounts` fetched from `addresses`. Missing accounts decode to `None`, while an
+/// existing account that can't be decoded as `T` fails the whole batch.
+pub fn decode_accounts<T: Pack + IsInitialized>(
+    addresses: &[Pubkey],
+    accounts: Vec<Option<Account>>,
+) -> ClientResult<Vec<Option<T>>> {
+    addresses
+        .iter()
+        .zip(accounts)
+        .map(|(pubkey, account)| {
+            account
+                .map(|account| T::unpack(&account.data))
+                .transpose()
+                .map_err(|err| ClientErrorKind::Custom(format!("{}: {}", pubkey, err)).into())
+        })
+        .collect()
+}
+
+/// Accounts at `addresses` decoded as `T`, in order, `None` for those that don't exist
+pub fn get_multiple_packed<T: Pack + IsInitialized>(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> ClientResult<Vec<Option<T>>> {
+    decode_accounts(addresses, get_multiple_accounts(rpc_client, addresses)?)
+}
+
+/// Lending markets at `addresses`, in order
+pub fn get_lending_markets(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> ClientResult<Vec<Option<LendingMarket>>> {
+    get_multiple_packed(rpc_client, addresses)
+}
+
+/// Reserves at `addresses`, in order
+pub fn get_multiple_reserves(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> ClientResult<Vec<Option<Reserve>>> {
+    get_multiple_packed(rpc_client, addresses)
+}
+
+/// Obligations at `addresses`, in order
+pub fn get_obligations(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> ClientResult<Vec<Option<Obligation>>> {
+    get_multiple_packed(rpc_client, addresses)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solend_sdk::state::InitObligationParams;
+
+    #[test]
+    fn test_decode_accounts() {
+        let addresses = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let obligation = Obligation::new(InitObligationParams {
+            current_slot: 1,
+            lending_market: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            deposits: vec![],
+            borrows: vec![],
+        });
+        let mut data = vec![0; Obligation::LEN];
+        Obligation::pack(obligation.clone(), &mut data).unwrap();
+        let account = Account {
+            data,
+            ..Account::default()
+        };
+
+        assert_eq!(
+            decode_accounts::<Obligation>(&addresses, vec![Some(account.clone()), None]).unwrap(),
+            vec![Some(obligation), None]
+        );
+
+        let err = decode_accounts::<Reserve>(&addresses, vec![None, Some(account)]).unwrap_err();
+        assert!(err.to_string().contains(&addresses[1].to_string()));
+    }
+}
@@ -0,0 +1,347 @@
+//! Reconciliation of reserve state against the token accounts backing it
+
+use crate::filters;
+use solana_account_decoder::UiDataSliceConfig;
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_request::MAX_MULTIPLE_ACCOUNTS,
+};
+use solana_sdk::{
+    account::Account,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+};
+use solend_sdk::state::Reserve;
+use spl_token::state::{Account as TokenAccount, Mint};
+use std::fmt;
+
+/// A reserve whose recorded state disagrees with the accounts backing it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// The liquidity supply token account doesn't hold the recorded available liquidity
+    LiquiditySupply {
+        /// Reserve address
+        reserve: Pubkey,
+        /// Liquidity supply token account
+        supply: Pubkey,
+        /// Available liquidity recorded in the reserve
+        recorded: u64,
+        /// Balance of the liquidity supply token account
+        actual: u64,
+    },
+    /// The collateral mint supply differs from the supply recorded in the reserve
+    CollateralMintSupply {
+        /// Reserve address
+        reserve: Pubkey,
+        /// Collateral mint
+        mint: Pubkey,
+        /// Collateral mint supply recorded in the reserve
+        recorded: u64,
+        /// Supply of the collateral mint
+        actual: u64,
+    },
+    /// An account the reserve points to is missing or can't be decoded
+    InvalidAccount {
+        /// Reserve address
+        reserve: Pubkey,
+        /// Missing or undecodable account
+        account: Pubkey,
+        /// What's wrong with the account
+        reason: String,
+    },
+    /// The reserve account itself can't be decoded
+    InvalidReserve {
+        /// Reserve address
+        reserve: Pubkey,
+        /// Why the reserve couldn't be decoded
+        reason: String,
+    },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Discrepancy::LiquiditySupply {
+                reserve,
+                supply,
+                recorded,
+                actual,
+            } => write!(
+                f,
+                "reserve {}: available liquidity {} but liquidity supply {} holds {}",
+                reserve, recorded, supply, actual
+            ),
+            Discrepancy::CollateralMintSupply {
+                reserve,
+                mint,
+                recorded,
+                actual,
+            } => write!(
+                f,
+                "reserve {}: collateral supply {} but collateral mint {} has supply {}",
+                reserve, recorded, mint, actual
+            ),
+            Discrepancy::InvalidAccount {
+                reserve,
+                account,
+                reason,
+            } => write!(f, "reserve {}: account {} {}", reserve, account, reason),
+            Discrepancy::InvalidReserve { reserve, reason } => {
+                write!(f, "reserve {}: {}", reserve, reason)
+            }
+        }
+    }
+}
+
+/// Check a reserve against its liquidity supply token account and collateral mint, which should
+/// be fetched in the same slot as the reserve
+pub fn check_reserve(
+    reserve_pubkey: &Pubkey,
+    reserve: &Reserve,
+    liquidity_supply: Option<&Account>,
+    collateral_mint: Option<&Account>,
+) -> Vec<Discrepancy> {
+    let mut discrepancies = vec![];
+
+    let supply_pubkey = reserve.liquidity.supply_pubkey;
+    match decode_account::<TokenAccount>(liquidity_supply) {
+        Ok(supply) if supply.amount != reserve.liquidity.available_amount => {
+            discrepancies.push(Discrepancy::LiquiditySupply {
+                reserve: *reserve_pubkey,
+                supply: supply_pubkey,
+                recorded: reserve.liquidity.available_amount,
+                actual: supply.amount,
+            })
+        }
+        Ok(_) => {}
+        Err(reason) => discrepancies.push(Discrepancy::InvalidAccount {
+            reserve: *reserve_pubkey,
+            account: supply_pubkey,
+            reason,
+        }),
+    }
+
+    let mint_pubkey = reserve.collateral.mint_pubkey;
+    match decode_account::<Mint>(collateral_mint) {
+        Ok(mint) if mint.supply != reserve.collateral.mint_total_supply => {
+            discrepancies.push(Discrepancy::CollateralMintSupply {
+                reserve: *reserve_pubkey,
+                mint: mint_pubkey,
+                recorded: reserve.collateral.mint_total_supply,
+                actual: mint.supply,
+            })
+        }
+        Ok(_) => {}
+        Err(reason) => discrepancies.push(Discrepancy::InvalidAccount {
+            reserve: *reserve_pubkey,
+            account: mint_pubkey,
+            reason,
+        }),
+    }
+
+    discrepancies
+}
+
+fn decode_account<T: Pack + IsInitialized>(account: Option<&Account>) -> Result<T, String> {
+    let account = account.ok_or_else(|| "is missing".to_string())?;
+    if account.owner != spl_token::id() {
+        return Err(format!(
+            "is owned by {}, not the token program",
+            account.owner
+        ));
+    }
+    T::unpack(&account.data).map_err(|err| format!("can't be decoded: {}", err))
+}
+
+/// Addresses of every reserve of `lending_program_id`, or only those in `lending_market`
+pub fn get_reserve_addresses(
+    rpc_client: &RpcClient,
+    lending_program_id: &Pubkey,
+    lending_market: Option<&Pubkey>,
+) -> ClientResult<Vec<Pubkey>> {
+    let filters = match lending_market {
+        Some(lending_market) => filters::reserves_in_market(lending_market),
+        None => filters::reserves(),
+    };
+    let accounts = rpc_client.get_program_accounts_with_config(
+        lending_program_id,
+        RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                data_slice: Some(UiDataSliceConfig {
+                    offset: 0,
+                    length: 0,
+                }),
+                ..RpcAccountInfoConfig::default()
+            },
+            with_context: Some(false),
+        },
+    )?;
+    Ok(accounts.into_iter().map(|(pubkey, _)| pubkey).collect())
+}
+
+/// Fetch each reserve together with its liquidity supply and collateral mint, and check them.
+/// Each reserve is fetched twice: once to find its accounts, then again with them in a single
+/// request so all three are read in the same slot.
+pub fn audit_reserves(
+    rpc_client: &RpcClient,
+    reserve_pubkeys: &[Pubkey],
+) -> ClientResult<Vec<Discrepancy>> {
+    let mut discrepancies = vec![];
+    for chunk in reserve_pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS / 3) {
+        let mut reserves = vec![];
+        for (pubkey, account) in chunk.iter().zip(rpc_client.get_multiple_accounts(chunk)?) {
+            match account.map(|account| Reserve::unpack(&account.data)) {
+                Some(Ok(reserve)) => reserves.push((*pubkey, reserve)),
+                Some(Err(err)) => discrepancies.push(Discrepancy::InvalidReserve {
+                    reserve: *pubkey,
+                    reason: format!("can't be decoded: {}", err),
+                }),
+                None => discrepancies.push(Discrepancy::InvalidReserve {
+                    reserve: *pubkey,
+                    reason: "is missing".to_string(),
+                }),
+            }
+        }
+
+        let addresses: Vec<Pubkey> = reserves
+            .iter()
+            .flat_map(|(pubkey, reserve)| {
+                [
+                    *pubkey,
+                    reserve.liquidity.supply_pubkey,
+                    reserve.collateral.mint_pubkey,
+                ]
+            })
+            .collect();
+        let accounts = rpc_client.get_multiple_accounts(&addresses)?;
+        for ((pubkey, _), accounts) in reserves.iter().zip(accounts.chunks(3)) {
+            match accounts[0]
+                .as_ref()
+                .map(|account| Reserve::unpack(&account.data))
+            {
+                Some(Ok(reserve)) => discrepancies.extend(check_reserve(
+                    pubkey,
+                    &reserve,
+                    accounts[1].as_ref(),
+                    accounts[2].as_ref(),
+                )),
+                // the reserve changed shape between the two requests, which a rerun will show
+                _ => discrepancies.push(Discrepancy::InvalidReserve {
+                    reserve: *pubkey,
+                    reason: "couldn't be fetched again".to_string(),
+                }),
+            }
+        }
+    }
+    Ok(discrepancies)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_sdk::program_option::COption;
+    use solend_sdk::state::{ReserveCollateral, ReserveLiquidity};
+
+    fn token_account(amount: u64) -> Account {
+        let mut account = Account::new(1, TokenAccount::LEN, &spl_token::id());
+        TokenAccount::pack(
+            TokenAccount {
+                mint: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                amount,
+                state: spl_token::state::AccountState::Initialized,
+                ..TokenAccount::default()
+            },
+            &mut account.data,
+        )
+        .unwrap();
+        account
+    }
+
+    fn mint(supply: u64) -> Account {
+        let mut account = Account::new(1, Mint::LEN, &spl_token::id());
+        Mint::pack(
+            Mint {
+                mint_authority: COption::Some(Pubkey::new_unique()),
+                supply,
+                decimals: 6,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+            &mut account.data,
+        )
+        .unwrap();
+        account
+    }
+
+    #[test]
+    fn test_check_reserve() {
+        let reserve_pubkey = Pubkey::new_unique();
+        let reserve = Reserve {
+            liquidity: ReserveLiquidity {
+                supply_pubkey: Pubkey::new_unique(),
+                available_amount: 100,
+                ..ReserveLiquidity::default()
+            },
+            collateral: ReserveCollateral {
+                mint_pubkey: Pubkey::new_unique(),
+                mint_total_supply: 50,
+                ..ReserveCollateral::default()
+            },
+            ..Reserve::default()
+        };
+
+        assert_eq!(
+            check_reserve(
+                &reserve_pubkey,
+                &reserve,
+                Some(&token_account(100)),
+                Some(&mint(50))
+            ),
+            vec![]
+        );
+
+        assert_eq!(
+            check_reserve(
+                &reserve_pubkey,
+                &reserve,
+                Some(&token_account(90)),
+                Some(&mint(51))
+            ),
+            vec![
+                Discrepancy::LiquiditySupply {
+                    reserve: reserve_pubkey,
+                    supply: reserve.liquidity.supply_pubkey,
+                    recorded: 100,
+                    actual: 90,
+                },
+                Discrepancy::CollateralMintSupply {
+                    reserve: reserve_pubkey,
+                    mint: reserve.collateral.mint_pubkey,
+                    recorded: 50,
+                    actual: 51,
+                },
+            ]
+        );
+
+        let mut not_a_token_account = token_account(100);
+        not_a_token_account.owner = Pubkey::new_unique();
+        let discrepancies =
+            check_reserve(&reserve_pubkey, &reserve, Some(&not_a_token_account), None);
+        assert_eq!(discrepancies.len(), 2);
+        assert!(matches!(
+            &discrepancies[0],
+            Discrepancy::InvalidAccount { account, .. } if *account == reserve.liquidity.supply_pubkey
+        ));
+        assert_eq!(
+            discrepancies[1].to_string(),
+            format!(
+                "reserve {}: account {} is missing",
+                reserve_pubkey, reserve.collateral.mint_pubkey
+            )
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Compute budget sizing from transaction simulation
+
+use solana_client::{
+    client_error::{ClientErrorKind, Result as ClientResult},
+    rpc_client::RpcClient,
+    rpc_config::RpcSimulateTransactionConfig,
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    transaction::{Transaction, TransactionError},
+};
+
+/// Maximum compute units a transaction can request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Default margin added on top of simulated compute units, in basis points
+pub const DEFAULT_COMPUTE_UNIT_MARGIN_BPS: u16 = 1_000;
+
+/// How compute budget instructions are attached to a transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComputeBudgetConfig {
+    /// Margin added to the simulated compute units, in basis points
+    pub compute_unit_margin_bps: u16,
+    /// Priority fee in micro-lamports per compute unit, if any
+    pub compute_unit_price: Option<u64>,
+}
+
+impl Default for ComputeBudgetConfig {
+    fn default() -> Self {
+        Self {
+            compute_unit_margin_bps: DEFAULT_COMPUTE_UNIT_MARGIN_BPS,
+            compute_unit_price: None,
+        }
+    }
+}
+
+/// Compute unit limit to request for a transaction that consumed `units_consumed` in simulation
+pub fn compute_unit_limit(units_consumed: u64, margin_bps: u16) -> u32 {
+    let margin = units_consumed.saturating_mul(margin_bps as u64) / 10_000;
+    units_consumed
+        .saturating_add(margin)
+        .min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+}
+
+/// Compute budget instructions for the given limit and config
+pub fn compute_budget_instructions(
+    compute_unit_limit: u32,
+    config: &ComputeBudgetConfig,
+) -> Vec<Instruction> {
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+        compute_unit_limit,
+    )];
+    if let Some(price) = config.compute_unit_price {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+    }
+    instructions
+}
+
+/// Simulate `instructions` and return them prefixed with a compute unit limit sized from the
+/// simulation plus margin, and the configured priority fee. `instructions` must not already
+/// contain compute budget instructions.
+///
+/// A failed simulation is returned as a transaction error whose instruction index refers to
+/// `instructions`.
+pub fn with_compute_budget(
+    rpc_client: &RpcClient,
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    config: &ComputeBudgetConfig,
+) -> ClientResult<Vec<Instruction>> {
+    // simulate with the maximum limit so the default budget doesn't fail the simulation
+    let mut simulated = compute_budget_instructions(MAX_COMPUTE_UNIT_LIMIT, config);
+    let prefix_len = simulated.len() as u8;
+    simulated.extend(instructions.iter().cloned());
+    let transaction = Transaction::new_unsigned(Message::new(&simulated, Some(payer)));
+    let result = rpc_client.simulate_transaction_with_config(
+        &transaction,
+        RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(rpc_client.commitment()),
+            ..RpcSimulateTransactionConfig::default()
+        },
+    )?;
+    match result.value.err {
+        Some(TransactionError::InstructionError(index, err)) if index >= prefix_len => {
+            return Err(
+                ClientErrorKind::TransactionError(TransactionError::InstructionError(
+                    index - prefix_len,
+                    err,
+                ))
+                .into(),
+            );
+        }
+        Some(err) => return Err(ClientErrorKind::TransactionError(err).into()),
+        None => {}
+    }
+
+    let limit = result
+        .value
+        .units_consumed
+        .map(|units| compute_unit_limit(units, config.compute_unit_margin_bps))
+        .unwrap_or(MAX_COMPUTE_UNIT_LIMIT);
+    let mut budgeted = compute_budget_instructions(limit, config);
+    budgeted.extend(instructions.iter().cloned());
+    Ok(budgeted)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_compute_unit_limit() {
+        assert_eq!(compute_unit_limit(0, 1_000), 0);
+        assert_eq!(compute_unit_limit(200_000, 1_000), 220_000);
+        assert_eq!(compute_unit_limit(200_000, 0), 200_000);
+        assert_eq!(compute_unit_limit(1_300_000, 1_000), MAX_COMPUTE_UNIT_LIMIT);
+        assert_eq!(
+            compute_unit_limit(u64::MAX, u16::MAX),
+            MAX_COMPUTE_UNIT_LIMIT
+        );
+    }
+
+    #[test]
+    fn test_compute_budget_instructions() {
+        let config = ComputeBudgetConfig::default();
+        assert_eq!(
+            compute_budget_instructions(100_000, &config),
+            vec![ComputeBudgetInstruction::set_compute_unit_limit(100_000)]
+        );
+
+        let config = ComputeBudgetConfig {
+            compute_unit_price: Some(5),
+            ..config
+        };
+        assert_eq!(
+            compute_budget_instructions(100_000, &config),
+            vec![
+                ComputeBudgetInstruction::set_compute_unit_limit(100_000),
+                ComputeBudgetInstruction::set_compute_unit_price(5),
+            ]
+        );
+    }
+}
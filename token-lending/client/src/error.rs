@@ -0,0 +1,234 @@
+//! Errors of failed lending transactions, carrying the failing instruction, the program error it
+//! resolves to and the accounts it was given
+
+use crate::simulate::{decode_instruction_error, failed_program, SimulationError};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    transaction::TransactionError,
+};
+use std::fmt;
+use thiserror::Error;
+
+/// Why sending a lending transaction failed
+#[derive(Debug, Error)]
+pub enum LendingClientError {
+    /// One of the transaction's instructions failed
+    #[error("{0}")]
+    Instruction(Box<InstructionFailure>),
+    /// The transaction failed before or outside any instruction
+    #[error("transaction failed: {0}")]
+    Transaction(TransactionError),
+    /// The RPC request failed without running the transaction
+    #[error(transparent)]
+    Rpc(ClientError),
+}
+
+/// A failed instruction of a lending transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstructionFailure {
+    /// Index of the failing top-level instruction
+    pub index: u8,
+    /// Program the top-level instruction invoked
+    pub program_id: Pubkey,
+    /// Error the instruction failed with, resolved to the error of the program that raised it
+    pub error: SimulationError,
+    /// Accounts passed to the top-level instruction
+    pub accounts: Vec<AccountMeta>,
+    /// Log messages, when the failure came from a simulation
+    pub logs: Vec<String>,
+}
+
+impl fmt::Display for InstructionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (program {}, accounts", self.error, self.program_id)?;
+        for (i, account) in self.accounts.iter().enumerate() {
+            let separator = if i == 0 { ": " } else { ", " };
+            write!(f, "{}{}", separator, account.pubkey)?;
+            if account.is_writable {
+                write!(f, " [w]")?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+impl LendingClientError {
+    /// Add context to `err`, returned while sending a transaction made of `instructions`.
+    ///
+    /// Custom error codes are resolved against the program the logs show failing. Without logs,
+    /// such as for a transaction sent with preflight skipped, the top-level instruction's program
+    /// is assumed to have raised the error.
+    pub fn new(
+        err: ClientError,
+        lending_program_id: &Pubkey,
+        instructions: &[Instruction],
+    ) -> Self {
+        let (index, error) = match err.get_transaction_error() {
+            Some(TransactionError::InstructionError(index, error)) => (index, error),
+            Some(err) => return LendingClientError::Transaction(err),
+            None => return LendingClientError::Rpc(err),
+        };
+        let instruction = match instructions.get(index as usize) {
+            Some(instruction) => instruction,
+            None => return LendingClientError::Rpc(err),
+        };
+
+        let logs = preflight_logs(&err);
+        let failed_program_id = failed_program(&logs).unwrap_or(instruction.program_id);
+        LendingClientError::Instruction(Box::new(InstructionFailure {
+            index,
+            program_id: instruction.program_id,
+            error: decode_instruction_error(
+                lending_program_id,
+                index,
+                Some(failed_program_id),
+                error,
+            ),
+            accounts: instruction.accounts.clone(),
+            logs,
+        }))
+    }
+
+    /// The decoded failure, if an instruction failed
+    pub fn instruction_failure(&self) -> Option<&InstructionFailure> {
+        match self {
+            LendingClientError::Instruction(failure) => Some(failure),
+            _ => None,
+        }
+    }
+}
+
+fn preflight_logs(err: &ClientError) -> Vec<String> {
+    match err.kind() {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            ..
+        }) => result.logs.clone().unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_client::rpc_response::RpcSimulateTransactionResult;
+    use solana_sdk::instruction::InstructionError;
+    use solend_error_catalog::decode_lending_error;
+    use solend_sdk::error::LendingError;
+    use spl_token::error::TokenError;
+
+    fn instructions(program_id: Pubkey, accounts: Vec<AccountMeta>) -> Vec<Instruction> {
+        vec![
+            Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]),
+            Instruction::new_with_bytes(program_id, &[], accounts),
+        ]
+    }
+
+    #[test]
+    fn test_confirmed_failure() {
+        let program_id = Pubkey::new_unique();
+        let obligation = Pubkey::new_unique();
+        let clock = solana_sdk::sysvar::clock::id();
+        let instructions = instructions(
+            program_id,
+            vec![
+                AccountMeta::new(obligation, false),
+                AccountMeta::new_readonly(clock, false),
+            ],
+        );
+        let code = LendingError::ObligationStale as u32;
+        let err = ClientErrorKind::TransactionError(TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(code),
+        ))
+        .into();
+
+        let err = LendingClientError::new(err, &program_id, &instructions);
+        let failure = err.instruction_failure().unwrap();
+        assert_eq!(failure.index, 1);
+        assert_eq!(failure.program_id, program_id);
+        assert_eq!(
+            failure.error,
+            SimulationError::Lending {
+                instruction: 1,
+                error: decode_lending_error(code).unwrap(),
+            }
+        );
+        assert_eq!(failure.accounts, instructions[1].accounts);
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "instruction 1: {} (program {}, accounts: {} [w], {})",
+                decode_lending_error(code).unwrap(),
+                program_id,
+                obligation,
+                clock
+            )
+        );
+    }
+
+    #[test]
+    fn test_preflight_failure() {
+        let program_id = Pubkey::new_unique();
+        let instructions = instructions(program_id, vec![]);
+        let code = TokenError::InsufficientFunds as u32;
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            format!("Program {} invoke [2]", spl_token::id()),
+            format!(
+                "Program {} failed: custom program error: {:#x}",
+                spl_token::id(),
+                code
+            ),
+        ];
+        let err: ClientError = ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: -32002,
+            message: "Transaction simulation failed".to_string(),
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(
+                RpcSimulateTransactionResult {
+                    err: Some(TransactionError::InstructionError(
+                        1,
+                        InstructionError::Custom(code),
+                    )),
+                    logs: Some(logs.clone()),
+                    accounts: None,
+                    units_consumed: None,
+                    return_data: None,
+                },
+            ),
+        })
+        .into();
+
+        let err = LendingClientError::new(err, &program_id, &instructions);
+        let failure = err.instruction_failure().unwrap();
+        assert_eq!(
+            failure.error,
+            SimulationError::Token {
+                instruction: 1,
+                error: TokenError::InsufficientFunds,
+            }
+        );
+        assert_eq!(failure.logs, logs);
+    }
+
+    #[test]
+    fn test_other_failures() {
+        let program_id = Pubkey::new_unique();
+        let err = ClientErrorKind::TransactionError(TransactionError::AccountNotFound).into();
+        assert!(matches!(
+            LendingClientError::new(err, &program_id, &[]),
+            LendingClientError::Transaction(TransactionError::AccountNotFound)
+        ));
+
+        let err = ClientErrorKind::Custom("connection refused".to_string()).into();
+        assert!(matches!(
+            LendingClientError::new(err, &program_id, &[]),
+            LendingClientError::Rpc(_)
+        ));
+    }
+}
@@ -0,0 +1,111 @@
+//! `getProgramAccounts` filters selecting lending accounts by type and by the fields indexers
+//! look them up by, built from the offsets exported by `solend_sdk::state`
+
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{program_pack::Pack, pubkey::Pubkey};
+use solend_sdk::state::{
+    LendingMarket, Obligation, Reserve, LENDING_MARKET_OWNER_OFFSET,
+    OBLIGATION_LENDING_MARKET_OFFSET, OBLIGATION_OWNER_OFFSET, RESERVE_COLLATERAL_MINT_OFFSET,
+    RESERVE_LENDING_MARKET_OFFSET, RESERVE_LIQUIDITY_MINT_OFFSET,
+};
+
+/// Matches accounts holding `pubkey` at `offset`
+pub fn pubkey_at(offset: usize, pubkey: &Pubkey) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(offset, pubkey.to_bytes().to_vec()))
+}
+
+/// Every lending market
+pub fn lending_markets() -> Vec<RpcFilterType> {
+    vec![RpcFilterType::DataSize(LendingMarket::LEN as u64)]
+}
+
+/// Lending markets owned by `owner`
+pub fn lending_markets_by_owner(owner: &Pubkey) -> Vec<RpcFilterType> {
+    let mut filters = lending_markets();
+    filters.push(pubkey_at(LENDING_MARKET_OWNER_OFFSET, owner));
+    filters
+}
+
+/// Every reserve
+pub fn reserves() -> Vec<RpcFilterType> {
+    vec![RpcFilterType::DataSize(Reserve::LEN as u64)]
+}
+
+/// Reserves in `lending_market`
+pub fn reserves_in_market(lending_market: &Pubkey) -> Vec<RpcFilterType> {
+    let mut filters = reserves();
+    filters.push(pubkey_at(RESERVE_LENDING_MARKET_OFFSET, lending_market));
+    filters
+}
+
+/// Reserves lending `liquidity_mint`, across all markets
+pub fn reserves_by_liquidity_mint(liquidity_mint: &Pubkey) -> Vec<RpcFilterType> {
+    let mut filters = reserves();
+    filters.push(pubkey_at(RESERVE_LIQUIDITY_MINT_OFFSET, liquidity_mint));
+    filters
+}
+
+/// The reserve minting `collateral_mint`
+pub fn reserves_by_collateral_mint(collateral_mint: &Pubkey) -> Vec<RpcFilterType> {
+    let mut filters = reserves();
+    filters.push(pubkey_at(RESERVE_COLLATERAL_MINT_OFFSET, collateral_mint));
+    filters
+}
+
+/// Every obligation
+pub fn obligations() -> Vec<RpcFilterType> {
+    vec![RpcFilterType::DataSize(Obligation::LEN as u64)]
+}
+
+/// Obligations in `lending_market`
+pub fn obligations_in_market(lending_market: &Pubkey) -> Vec<RpcFilterType> {
+    let mut filters = obligations();
+    filters.push(pubkey_at(OBLIGATION_LENDING_MARKET_OFFSET, lending_market));
+    filters
+}
+
+/// Obligations owned by `owner`, across all markets
+pub fn obligations_by_owner(owner: &Pubkey) -> Vec<RpcFilterType> {
+    let mut filters = obligations();
+    filters.push(pubkey_at(OBLIGATION_OWNER_OFFSET, owner));
+    filters
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_sdk::account::{Account, AccountSharedData};
+    use solend_sdk::state::InitObligationParams;
+
+    fn matches(filters: &[RpcFilterType], account: &AccountSharedData) -> bool {
+        filters.iter().all(|filter| filter.allows(account))
+    }
+
+    #[test]
+    fn test_obligation_filters() {
+        let lending_market = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut data = vec![0; Obligation::LEN];
+        Obligation::pack(
+            Obligation::new(InitObligationParams {
+                current_slot: 0,
+                lending_market,
+                owner,
+                deposits: vec![],
+                borrows: vec![],
+            }),
+            &mut data,
+        )
+        .unwrap();
+        let account = AccountSharedData::from(Account {
+            data,
+            ..Account::default()
+        });
+
+        assert!(matches(&obligations(), &account));
+        assert!(matches(&obligations_in_market(&lending_market), &account));
+        assert!(matches(&obligations_by_owner(&owner), &account));
+        assert!(!matches(&obligations_by_owner(&lending_market), &account));
+        assert!(!matches(&reserves(), &account));
+    }
+}
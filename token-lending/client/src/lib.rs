@@ -0,0 +1,20 @@
+#![deny(missing_docs)]
+// solana_client's ClientError is large, and it's what every helper here returns
+#![allow(clippy::result_large_err)]
+
+//! RPC client helpers for building and sending Solend lending transactions.
+
+pub mod accounts;
+pub mod audit;
+pub mod compute_budget;
+pub mod error;
+pub mod filters;
+pub mod lookup_table;
+pub mod monitor;
+pub mod priority_fee;
+pub mod rent;
+pub mod send;
+pub mod simulate;
+pub mod subscription;
+pub mod ui_amount;
+pub mod version;
@@ -0,0 +1,314 @@
+//! Versioned transactions and address lookup tables for lending markets
+
+use crate::filters;
+use solana_address_lookup_table_program::{
+    instruction::{create_lookup_table, extend_lookup_table},
+    state::{AddressLookupTable, LOOKUP_TABLE_MAX_ADDRESSES},
+};
+use solana_client::{
+    client_error::{ClientErrorKind, Result as ClientResult},
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+};
+use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, VersionedMessage},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signers::Signers,
+    sysvar,
+    transaction::VersionedTransaction,
+};
+use solend_sdk::{
+    state::{LendingMarketMetadata, Reserve},
+    NULL_PUBKEY,
+};
+use spl_forks_pdas::{find_lending_market_authority_address, find_lending_market_metadata_address};
+use std::mem::size_of;
+
+pub use solend_sdk::state::RESERVE_LENDING_MARKET_OFFSET;
+
+/// Maximum number of addresses added by a single extend instruction, keeping the transaction
+/// under the packet size limit
+pub const MAX_ADDRESSES_PER_EXTEND: usize = 20;
+
+/// Fetch all reserves belonging to `lending_market`
+pub fn get_market_reserves(
+    rpc_client: &RpcClient,
+    lending_program_id: &Pubkey,
+    lending_market: &Pubkey,
+) -> ClientResult<Vec<(Pubkey, Reserve)>> {
+    let accounts = rpc_client.get_program_accounts_with_config(
+        lending_program_id,
+        RpcProgramAccountsConfig {
+            filters: Some(filters::reserves_in_market(lending_market)),
+            account_config: RpcAccountInfoConfig::default(),
+            with_context: Some(false),
+        },
+    )?;
+
+    accounts
+        .into_iter()
+        .map(|(pubkey, account)| {
+            Reserve::unpack(&account.data)
+                .map(|reserve| (pubkey, reserve))
+                .map_err(|err| ClientErrorKind::Custom(format!("{}: {}", pubkey, err)).into())
+        })
+        .collect()
+}
+
+/// Addresses worth keeping in a lookup table for a lending market: the market, its authority,
+/// each reserve with its oracles, mints and supply accounts, and the token program and sysvars
+/// passed to lending instructions. The lending program itself is left out since invoked
+/// programs can't be loaded from a lookup table, and so are unset oracles.
+pub fn market_lookup_table_addresses(
+    lending_program_id: &Pubkey,
+    lending_market: &Pubkey,
+    reserves: &[(Pubkey, Reserve)],
+) -> Vec<Pubkey> {
+    let (lending_market_authority, _bump_seed) =
+        find_lending_market_authority_address(lending_market, lending_program_id);
+
+    let mut addresses = vec![
+        *lending_market,
+        lending_market_authority,
+        spl_token::id(),
+        sysvar::instructions::id(),
+    ];
+    for (pubkey, reserve) in reserves {
+        addresses.extend([
+            *pubkey,
+            reserve.liquidity.mint_pubkey,
+            reserve.liquidity.supply_pubkey,
+            reserve.liquidity.pyth_oracle_pubkey,
+            reserve.liquidity.switchboard_oracle_pubkey,
+            reserve.config.fee_receiver,
+            reserve.collateral.mint_pubkey,
+            reserve.collateral.supply_pubkey,
+        ]);
+    }
+
+    let mut unique = Vec::with_capacity(addresses.len());
+    for address in addresses {
+        if address != NULL_PUBKEY && address != Pubkey::default() && !unique.contains(&address) {
+            unique.push(address);
+        }
+    }
+    unique
+}
+
+/// Split the addresses in `wanted` that none of `lookup_tables` contain yet into the addresses to
+/// add to each of `lookup_tables`, in order, followed by the addresses of each new table needed.
+/// Existing tables are filled up to `LOOKUP_TABLE_MAX_ADDRESSES` before new ones are started.
+pub fn assign_lookup_table_addresses(
+    lookup_tables: &[AddressLookupTableAccount],
+    wanted: &[Pubkey],
+) -> Vec<Vec<Pubkey>> {
+    let mut missing = wanted.iter().filter(|address| {
+        !lookup_tables
+            .iter()
+            .any(|table| table.addresses.contains(address))
+    });
+
+    let mut assigned: Vec<Vec<Pubkey>> = lookup_tables
+        .iter()
+        .map(|table| {
+            let room = LOOKUP_TABLE_MAX_ADDRESSES.saturating_sub(table.addresses.len());
+            missing.by_ref().take(room).cloned().collect()
+        })
+        .collect();
+    let remaining: Vec<Pubkey> = missing.cloned().collect();
+    assigned.extend(
+        remaining
+            .chunks(LOOKUP_TABLE_MAX_ADDRESSES)
+            .map(|chunk| chunk.to_vec()),
+    );
+    assigned
+}
+
+/// Instruction creating a new lookup table owned by `authority`, and the table address
+pub fn create_market_lookup_table(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    recent_slot: u64,
+) -> (Instruction, Pubkey) {
+    create_lookup_table(*authority, *payer, recent_slot)
+}
+
+/// Instructions adding every address in `wanted` that `lookup_table` doesn't contain yet, in
+/// batches small enough to each fit in a transaction
+pub fn extend_market_lookup_table(
+    lookup_table: &AddressLookupTableAccount,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    wanted: &[Pubkey],
+) -> Vec<Instruction> {
+    let missing: Vec<Pubkey> = wanted
+        .iter()
+        .filter(|address| !lookup_table.addresses.contains(address))
+        .cloned()
+        .collect();
+
+    missing
+        .chunks(MAX_ADDRESSES_PER_EXTEND)
+        .map(|chunk| {
+            extend_lookup_table(lookup_table.key, *authority, Some(*payer), chunk.to_vec())
+        })
+        .collect()
+}
+
+/// Fetch and decode a lookup table account
+pub fn get_lookup_table(
+    rpc_client: &RpcClient,
+    lookup_table: &Pubkey,
+) -> ClientResult<AddressLookupTableAccount> {
+    let account = rpc_client.get_account(lookup_table)?;
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|err| ClientErrorKind::Custom(format!("{}: {}", lookup_table, err)))?;
+    Ok(AddressLookupTableAccount {
+        key: *lookup_table,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+/// Lookup tables recorded in a lending market's metadata account, if it has one
+pub fn get_market_lookup_tables(
+    rpc_client: &RpcClient,
+    lending_program_id: &Pubkey,
+    lending_market: &Pubkey,
+) -> ClientResult<Vec<Pubkey>> {
+    let (metadata_pubkey, _bump_seed) =
+        find_lending_market_metadata_address(lending_market, lending_program_id);
+    let data = match rpc_client
+        .get_account_with_commitment(&metadata_pubkey, rpc_client.commitment())?
+        .value
+    {
+        Some(account) => account.data,
+        None => return Ok(vec![]),
+    };
+    if data.len() != size_of::<LendingMarketMetadata>() {
+        return Err(ClientErrorKind::Custom(format!(
+            "{}: invalid lending market metadata size",
+            metadata_pubkey
+        ))
+        .into());
+    }
+    let metadata: LendingMarketMetadata = bytemuck::pod_read_unaligned(&data);
+    Ok(metadata
+        .lookup_tables
+        .iter()
+        .filter(|table| **table != Pubkey::default())
+        .cloned()
+        .collect())
+}
+
+/// Compile `instructions` into a signed v0 transaction using `lookup_tables`
+pub fn build_v0_transaction<T: Signers>(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+    signers: &T,
+) -> ClientResult<VersionedTransaction> {
+    let message = v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)
+        .map_err(|err| ClientErrorKind::Custom(err.to_string()))?;
+    Ok(VersionedTransaction::try_new(
+        VersionedMessage::V0(message),
+        signers,
+    )?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_extend_market_lookup_table() {
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![Pubkey::new_unique()],
+        };
+        let authority = Pubkey::new_unique();
+        let mut wanted = table.addresses.clone();
+        wanted.extend((0..MAX_ADDRESSES_PER_EXTEND + 1).map(|_| Pubkey::new_unique()));
+
+        let instructions = extend_market_lookup_table(&table, &authority, &authority, &wanted);
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(
+            instructions[0],
+            extend_lookup_table(
+                table.key,
+                authority,
+                Some(authority),
+                wanted[1..MAX_ADDRESSES_PER_EXTEND + 1].to_vec()
+            )
+        );
+
+        let table = AddressLookupTableAccount {
+            addresses: wanted.clone(),
+            ..table
+        };
+        assert!(extend_market_lookup_table(&table, &authority, &authority, &wanted).is_empty());
+    }
+
+    #[test]
+    fn test_market_lookup_table_addresses() {
+        let lending_program_id = Pubkey::new_unique();
+        let lending_market = Pubkey::new_unique();
+        let reserve = Reserve::default();
+
+        let addresses = market_lookup_table_addresses(
+            &lending_program_id,
+            &lending_market,
+            &[
+                (Pubkey::new_unique(), reserve.clone()),
+                (Pubkey::new_unique(), reserve),
+            ],
+        );
+        // shared mints, oracles and supplies are only listed once, and unset ones not at all
+        assert_eq!(addresses.len(), 4 + 2);
+        assert_eq!(addresses[0], lending_market);
+
+        let mut reserve = Reserve::default();
+        reserve.liquidity.mint_pubkey = Pubkey::new_unique();
+        reserve.liquidity.pyth_oracle_pubkey = NULL_PUBKEY;
+        reserve.liquidity.switchboard_oracle_pubkey = NULL_PUBKEY;
+        let addresses = market_lookup_table_addresses(
+            &lending_program_id,
+            &lending_market,
+            &[(Pubkey::new_unique(), reserve.clone())],
+        );
+        assert_eq!(addresses.len(), 4 + 2);
+        assert!(addresses.contains(&reserve.liquidity.mint_pubkey));
+        assert!(!addresses.contains(&NULL_PUBKEY));
+    }
+
+    #[test]
+    fn test_assign_lookup_table_addresses() {
+        let wanted: Vec<Pubkey> = (0..2 * LOOKUP_TABLE_MAX_ADDRESSES + 10)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: wanted[..LOOKUP_TABLE_MAX_ADDRESSES - 5].to_vec(),
+        };
+
+        let assigned = assign_lookup_table_addresses(&[table], &wanted);
+        assert_eq!(
+            assigned,
+            vec![
+                wanted[LOOKUP_TABLE_MAX_ADDRESSES - 5..LOOKUP_TABLE_MAX_ADDRESSES].to_vec(),
+                wanted[LOOKUP_TABLE_MAX_ADDRESSES..2 * LOOKUP_TABLE_MAX_ADDRESSES].to_vec(),
+                wanted[2 * LOOKUP_TABLE_MAX_ADDRESSES..].to_vec(),
+            ]
+        );
+
+        assert_eq!(
+            assign_lookup_table_addresses(&[], &wanted[..3]),
+            vec![wanted[..3].to_vec()]
+        );
+        assert!(assign_lookup_table_addresses(&[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,318 @@
+//! Invariant checks for continuously monitoring a lending program: reserve backing, oracle
+//! freshness and rate limiter saturation
+
+use crate::{
+    accounts::get_multiple_accounts,
+    audit::{audit_reserves, get_reserve_addresses, Discrepancy},
+};
+use serde_json::{json, Value};
+use solana_client::{client_error::Result as ClientResult, rpc_client::RpcClient};
+use solana_sdk::{
+    account::{from_account, Account},
+    account_info::IntoAccountInfo,
+    clock::{Clock, Slot},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar,
+};
+use solend_sdk::state::{LendingMarket, RateLimiter, Reserve};
+use std::{collections::BTreeSet, fmt};
+
+/// Thresholds an alert is raised at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Thresholds {
+    /// Share of a rate limiter's max outflow, in percent, that may be used up in the current
+    /// window
+    pub rate_limiter_percent: u8,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            rate_limiter_percent: 80,
+        }
+    }
+}
+
+/// A breached invariant
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Alert {
+    /// A reserve disagrees with the token accounts backing it
+    Backing(Discrepancy),
+    /// Neither oracle of a reserve has a usable price, so it can't be refreshed
+    OracleUnavailable {
+        /// Reserve address
+        reserve: Pubkey,
+        /// Primary oracle of the reserve
+        pyth_oracle: Pubkey,
+        /// Switchboard feed of the reserve
+        switchboard_oracle: Pubkey,
+        /// Why the price was rejected
+        reason: String,
+    },
+    /// The outflow in a rate limiter's current window is at or above the threshold
+    RateLimiterSaturated {
+        /// Lending market or reserve the rate limiter belongs to
+        account: Pubkey,
+        /// Outflow in the current window, in percent of the max outflow
+        used_percent: u64,
+        /// Max outflow per window
+        max_outflow: u64,
+    },
+}
+
+impl Alert {
+    /// Short name of the invariant the alert is for
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Alert::Backing(_) => "backing",
+            Alert::OracleUnavailable { .. } => "oracle_unavailable",
+            Alert::RateLimiterSaturated { .. } => "rate_limiter_saturated",
+        }
+    }
+
+    /// The alert as a JSON object, with `kind` and `message` fields plus the alert's own
+    pub fn to_json(&self) -> Value {
+        let mut value = match self {
+            Alert::Backing(_) => json!({}),
+            Alert::OracleUnavailable {
+                reserve,
+                pyth_oracle,
+                switchboard_oracle,
+                reason,
+            } => json!({
+                "reserve": reserve.to_string(),
+                "pythOracle": pyth_oracle.to_string(),
+                "switchboardOracle": switchboard_oracle.to_string(),
+                "reason": reason,
+            }),
+            Alert::RateLimiterSaturated {
+                account,
+                used_percent,
+                max_outflow,
+            } => json!({
+                "account": account.to_string(),
+                "usedPercent": used_percent,
+                "maxOutflow": max_outflow,
+            }),
+        };
+        value["kind"] = json!(self.kind());
+        value["message"] = json!(self.to_string());
+        value
+    }
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Alert::Backing(discrepancy) => write!(f, "{}", discrepancy),
+            Alert::OracleUnavailable {
+                reserve, reason, ..
+            } => write!(f, "reserve {}: no usable oracle price: {}", reserve, reason),
+            Alert::RateLimiterSaturated {
+                account,
+                used_percent,
+                max_outflow,
+            } => write!(
+                f,
+                "rate limiter of {}: {}% of the max outflow {} used",
+                account, used_percent, max_outflow
+            ),
+        }
+    }
+}
+
+/// Check that a reserve's oracles give a price the program would accept at `clock`
+pub fn check_oracle(
+    reserve_pubkey: &Pubkey,
+    reserve: &Reserve,
+    pyth_oracle: Option<Account>,
+    switchboard_oracle: Option<Account>,
+    clock: &Clock,
+) -> Option<Alert> {
+    let pyth_oracle_pubkey = reserve.liquidity.pyth_oracle_pubkey;
+    let switchboard_oracle_pubkey = reserve.liquidity.switchboard_oracle_pubkey;
+    let mut pyth_oracle = pyth_oracle.unwrap_or_default();
+    // the switchboard decoders expect a feed account, so a missing one isn't passed at all
+    let mut switchboard_oracle = switchboard_oracle.filter(|account| !account.data.is_empty());
+
+    let pyth_info = (&pyth_oracle_pubkey, &mut pyth_oracle).into_account_info();
+    let switchboard_info = switchboard_oracle
+        .as_mut()
+        .map(|account| (&switchboard_oracle_pubkey, account).into_account_info());
+    match solend_oracles::get_price(switchboard_info.as_ref(), &pyth_info, clock) {
+        Ok(_) => None,
+        Err(err) => Some(Alert::OracleUnavailable {
+            reserve: *reserve_pubkey,
+            pyth_oracle: pyth_oracle_pubkey,
+            switchboard_oracle: switchboard_oracle_pubkey,
+            reason: err.to_string(),
+        }),
+    }
+}
+
+/// Check how much of a rate limiter's max outflow is used up at `slot`. Disabled rate limiters
+/// never alert.
+pub fn check_rate_limiter(
+    account: &Pubkey,
+    mut rate_limiter: RateLimiter,
+    slot: Slot,
+    thresholds: &Thresholds,
+) -> Option<Alert> {
+    let max_outflow = rate_limiter.config.max_outflow;
+    if rate_limiter.config.window_duration == 0 {
+        return None;
+    }
+    let remaining = rate_limiter
+        .remaining_outflow(slot)
+        .ok()?
+        .try_floor_u64()
+        .ok()?;
+    let used_percent = match max_outflow {
+        0 => 100,
+        max_outflow => {
+            (u128::from(max_outflow - remaining.min(max_outflow)) * 100 / u128::from(max_outflow))
+                as u64
+        }
+    };
+    if used_percent < u64::from(thresholds.rate_limiter_percent) {
+        return None;
+    }
+    Some(Alert::RateLimiterSaturated {
+        account: *account,
+        used_percent,
+        max_outflow,
+    })
+}
+
+/// Run every check against the reserves of `lending_program_id`, or only those in
+/// `lending_market`, and their lending markets
+pub fn check_invariants(
+    rpc_client: &RpcClient,
+    lending_program_id: &Pubkey,
+    lending_market: Option<&Pubkey>,
+    thresholds: &Thresholds,
+) -> ClientResult<Vec<Alert>> {
+    let reserve_pubkeys = get_reserve_addresses(rpc_client, lending_program_id, lending_market)?;
+    let mut alerts: Vec<Alert> = audit_reserves(rpc_client, &reserve_pubkeys)?
+        .into_iter()
+        .map(Alert::Backing)
+        .collect();
+
+    let reserves: Vec<(Pubkey, Reserve)> = reserve_pubkeys
+        .iter()
+        .zip(get_multiple_accounts(rpc_client, &reserve_pubkeys)?)
+        .filter_map(|(pubkey, account)| Some((*pubkey, Reserve::unpack(&account?.data).ok()?)))
+        .collect();
+    let lending_market_pubkeys: Vec<Pubkey> = reserves
+        .iter()
+        .map(|(_, reserve)| reserve.lending_market)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    // the clock is fetched with the accounts so prices are judged in the slot they were read at
+    let mut addresses = vec![sysvar::clock::id()];
+    addresses.extend(&lending_market_pubkeys);
+    for (_, reserve) in &reserves {
+        addresses.push(reserve.liquidity.pyth_oracle_pubkey);
+        addresses.push(reserve.liquidity.switchboard_oracle_pubkey);
+    }
+    let mut accounts = get_multiple_accounts(rpc_client, &addresses)?.into_iter();
+    let clock: Clock = accounts
+        .next()
+        .flatten()
+        .and_then(|account| from_account(&account))
+        .unwrap_or_default();
+
+    for (pubkey, account) in lending_market_pubkeys.iter().zip(accounts.by_ref()) {
+        if let Some(lending_market) = account.and_then(|a| LendingMarket::unpack(&a.data).ok()) {
+            alerts.extend(check_rate_limiter(
+                pubkey,
+                lending_market.rate_limiter,
+                clock.slot,
+                thresholds,
+            ));
+        }
+    }
+    for (pubkey, reserve) in &reserves {
+        let pyth_oracle = accounts.next().flatten();
+        let switchboard_oracle = accounts.next().flatten();
+        alerts.extend(check_oracle(
+            pubkey,
+            reserve,
+            pyth_oracle,
+            switchboard_oracle,
+            &clock,
+        ));
+        alerts.extend(check_rate_limiter(
+            pubkey,
+            reserve.rate_limiter,
+            clock.slot,
+            thresholds,
+        ));
+    }
+
+    Ok(alerts)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solend_sdk::{math::Decimal, state::RateLimiterConfig};
+
+    #[test]
+    fn test_check_rate_limiter() {
+        let account = Pubkey::new_unique();
+        let thresholds = Thresholds::default();
+        let config = RateLimiterConfig {
+            window_duration: 10,
+            max_outflow: 100,
+        };
+
+        let mut rate_limiter = RateLimiter::new(config, 10);
+        assert_eq!(
+            check_rate_limiter(&account, rate_limiter, 10, &thresholds),
+            None
+        );
+
+        rate_limiter.update(10, Decimal::from(85u64)).unwrap();
+        let alert = check_rate_limiter(&account, rate_limiter, 10, &thresholds).unwrap();
+        assert_eq!(
+            alert,
+            Alert::RateLimiterSaturated {
+                account,
+                used_percent: 85,
+                max_outflow: 100,
+            }
+        );
+        assert_eq!(alert.to_json()["kind"], "rate_limiter_saturated");
+        assert_eq!(alert.to_json()["usedPercent"], 85);
+
+        // the outflow ages out of the window
+        assert_eq!(
+            check_rate_limiter(&account, rate_limiter, 30, &thresholds),
+            None
+        );
+
+        let disabled = RateLimiter::new(RateLimiterConfig::default(), 10);
+        assert_eq!(
+            check_rate_limiter(&account, disabled, 10, &thresholds),
+            None
+        );
+    }
+
+    #[test]
+    fn test_check_oracle() {
+        let reserve_pubkey = Pubkey::new_unique();
+        let mut reserve = Reserve::default();
+        reserve.liquidity.pyth_oracle_pubkey = Pubkey::new_unique();
+        reserve.liquidity.switchboard_oracle_pubkey = Pubkey::new_unique();
+
+        let alert = check_oracle(&reserve_pubkey, &reserve, None, None, &Clock::default());
+        assert!(matches!(
+            alert,
+            Some(Alert::OracleUnavailable { reserve, .. }) if reserve == reserve_pubkey
+        ));
+    }
+}
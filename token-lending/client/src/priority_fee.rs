@@ -0,0 +1,161 @@
+//! Priority fee estimation from recent prioritization fees
+
+use serde::Deserialize;
+use serde_json::json;
+use solana_client::{
+    client_error::Result as ClientResult, rpc_client::RpcClient, rpc_request::RpcRequest,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+/// Most accounts `getRecentPrioritizationFees` accepts
+pub const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+
+/// Default percentile of recent fees to pay
+pub const DEFAULT_PRIORITY_FEE_PERCENTILE: u8 = 75;
+
+/// Default cap on the suggested price, in micro-lamports per compute unit
+pub const DEFAULT_MAX_COMPUTE_UNIT_PRICE: u64 = 10_000_000;
+
+/// How a compute unit price is picked from recent prioritization fees
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriorityFeeConfig {
+    /// Percentile of recent per-slot fees to pay, from 0 to 100
+    pub percentile: u8,
+    /// Lowest price suggested, in micro-lamports per compute unit
+    pub min_compute_unit_price: u64,
+    /// Highest price suggested, in micro-lamports per compute unit
+    pub max_compute_unit_price: u64,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            percentile: DEFAULT_PRIORITY_FEE_PERCENTILE,
+            min_compute_unit_price: 0,
+            max_compute_unit_price: DEFAULT_MAX_COMPUTE_UNIT_PRICE,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcPrioritizationFee {
+    prioritization_fee: u64,
+}
+
+/// Accounts `instructions` write-lock, without duplicates. Transactions only compete for
+/// inclusion with transactions locking the same accounts, so these are the accounts whose recent
+/// fees matter.
+pub fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = vec![];
+    for meta in instructions.iter().flat_map(|ix| &ix.accounts) {
+        if meta.is_writable && !accounts.contains(&meta.pubkey) {
+            accounts.push(meta.pubkey);
+        }
+    }
+    accounts
+}
+
+/// Per-slot minimum prioritization fees of recent transactions locking all of `accounts`, in
+/// micro-lamports per compute unit. Only the first `MAX_PRIORITIZATION_FEE_ACCOUNTS` are used.
+pub fn get_recent_prioritization_fees(
+    rpc_client: &RpcClient,
+    accounts: &[Pubkey],
+) -> ClientResult<Vec<u64>> {
+    let accounts: Vec<String> = accounts
+        .iter()
+        .take(MAX_PRIORITIZATION_FEE_ACCOUNTS)
+        .map(|pubkey| pubkey.to_string())
+        .collect();
+    let fees: Vec<RpcPrioritizationFee> = rpc_client.send(
+        RpcRequest::Custom {
+            method: "getRecentPrioritizationFees",
+        },
+        json!([accounts]),
+    )?;
+    Ok(fees.into_iter().map(|fee| fee.prioritization_fee).collect())
+}
+
+/// Price to pay given recent per-slot `fees`: the configured percentile, clamped to the configured
+/// range. No fees suggests the minimum.
+pub fn suggest_compute_unit_price(fees: &[u64], config: &PriorityFeeConfig) -> u64 {
+    let mut fees = fees.to_vec();
+    fees.sort_unstable();
+    let percentile = config.percentile.min(100) as usize;
+    let fee = match fees.len() {
+        0 => 0,
+        len => {
+            // nearest rank: the smallest fee at least `percentile` percent of slots paid
+            let rank = len - len * (100 - percentile) / 100;
+            fees[rank.clamp(1, len) - 1]
+        }
+    };
+    fee.max(config.min_compute_unit_price)
+        .min(config.max_compute_unit_price)
+}
+
+/// Sample recent prioritization fees for the accounts `instructions` write-lock and suggest a
+/// compute unit price for them
+pub fn estimate_compute_unit_price(
+    rpc_client: &RpcClient,
+    instructions: &[Instruction],
+    config: &PriorityFeeConfig,
+) -> ClientResult<u64> {
+    let fees = get_recent_prioritization_fees(rpc_client, &writable_accounts(instructions))?;
+    Ok(suggest_compute_unit_price(&fees, config))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    #[test]
+    fn test_suggest_compute_unit_price() {
+        let config = PriorityFeeConfig::default();
+        let fees: Vec<u64> = (1..=100).rev().collect();
+        assert_eq!(suggest_compute_unit_price(&fees, &config), 75);
+        assert_eq!(suggest_compute_unit_price(&[], &config), 0);
+        assert_eq!(suggest_compute_unit_price(&[7], &config), 7);
+
+        let config = PriorityFeeConfig {
+            percentile: 100,
+            min_compute_unit_price: 10,
+            max_compute_unit_price: 50,
+        };
+        assert_eq!(suggest_compute_unit_price(&fees, &config), 50);
+        assert_eq!(suggest_compute_unit_price(&[], &config), 10);
+
+        let config = PriorityFeeConfig {
+            percentile: 0,
+            ..PriorityFeeConfig::default()
+        };
+        assert_eq!(suggest_compute_unit_price(&fees, &config), 1);
+    }
+
+    #[test]
+    fn test_writable_accounts() {
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let program_id = Pubkey::new_unique();
+        let instructions = [
+            Instruction::new_with_bytes(
+                program_id,
+                &[],
+                vec![
+                    AccountMeta::new(a, false),
+                    AccountMeta::new_readonly(b, false),
+                ],
+            ),
+            Instruction::new_with_bytes(
+                program_id,
+                &[],
+                vec![AccountMeta::new(c, true), AccountMeta::new(a, false)],
+            ),
+        ];
+        assert_eq!(writable_accounts(&instructions), vec![a, c]);
+    }
+}
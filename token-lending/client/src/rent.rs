@@ -0,0 +1,108 @@
+//! Sizes and rent-exempt minimums of the accounts created when setting up lending state, so
+//! wallets paying for them can be funded exactly
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_client::RpcClient,
+};
+use solana_sdk::{account::from_account, program_pack::Pack, rent::Rent, sysvar};
+use solend_sdk::state::{LendingMarket, LendingMarketMetadata, Obligation, Reserve};
+use spl_token::state::{Account as Token, Mint};
+
+/// An account created by a lending instruction or by the transactions that precede it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LendingAccount {
+    /// Lending market, created before `InitLendingMarket`
+    LendingMarket,
+    /// Lending market metadata, created by `UpdateMarketMetadata`
+    LendingMarketMetadata,
+    /// Reserve, created before `InitReserve`
+    Reserve,
+    /// Obligation, created before `InitObligation`
+    Obligation,
+    /// SPL token mint, such as a reserve's collateral mint
+    Mint,
+    /// SPL token account, such as a reserve's supplies and fee receiver
+    TokenAccount,
+}
+
+impl LendingAccount {
+    /// Data size in bytes
+    pub fn size(self) -> usize {
+        match self {
+            LendingAccount::LendingMarket => LendingMarket::LEN,
+            LendingAccount::LendingMarketMetadata => std::mem::size_of::<LendingMarketMetadata>(),
+            LendingAccount::Reserve => Reserve::LEN,
+            LendingAccount::Obligation => Obligation::LEN,
+            LendingAccount::Mint => Mint::LEN,
+            LendingAccount::TokenAccount => Token::LEN,
+        }
+    }
+
+    /// Lamports needed for the account to be rent exempt
+    pub fn rent_exempt_minimum(self, rent: &Rent) -> u64 {
+        rent.minimum_balance(self.size())
+    }
+}
+
+/// Accounts created to initialize a lending market
+pub const INIT_LENDING_MARKET_ACCOUNTS: &[LendingAccount] = &[LendingAccount::LendingMarket];
+
+/// Accounts created to add a reserve: the reserve, its collateral mint, collateral supply,
+/// liquidity supply and liquidity fee receiver, and the collateral account receiving the
+/// initial deposit
+pub const INIT_RESERVE_ACCOUNTS: &[LendingAccount] = &[
+    LendingAccount::Reserve,
+    LendingAccount::Mint,
+    LendingAccount::TokenAccount,
+    LendingAccount::TokenAccount,
+    LendingAccount::TokenAccount,
+    LendingAccount::TokenAccount,
+];
+
+/// Accounts created to open an obligation
+pub const INIT_OBLIGATION_ACCOUNTS: &[LendingAccount] = &[LendingAccount::Obligation];
+
+/// Lamports needed to make all of `accounts` rent exempt
+pub fn rent_exempt_total(rent: &Rent, accounts: &[LendingAccount]) -> u64 {
+    accounts
+        .iter()
+        .map(|account| account.rent_exempt_minimum(rent))
+        .sum()
+}
+
+/// The cluster's rent parameters, read from the rent sysvar
+pub fn get_rent(rpc_client: &RpcClient) -> ClientResult<Rent> {
+    let account = rpc_client.get_account(&sysvar::rent::id())?;
+    from_account(&account).ok_or_else(|| {
+        ClientError::from(ClientErrorKind::Custom(
+            "invalid rent sysvar account".to_string(),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rent_exempt_minimums() {
+        let rent = Rent::default();
+        assert_eq!(
+            LendingAccount::TokenAccount.rent_exempt_minimum(&rent),
+            2_039_280
+        );
+        assert_eq!(LendingAccount::Mint.rent_exempt_minimum(&rent), 1_461_600);
+        assert_eq!(
+            LendingAccount::Reserve.rent_exempt_minimum(&rent),
+            rent.minimum_balance(Reserve::LEN)
+        );
+
+        assert_eq!(
+            rent_exempt_total(&rent, INIT_RESERVE_ACCOUNTS),
+            rent.minimum_balance(Reserve::LEN)
+                + rent.minimum_balance(Mint::LEN)
+                + 4 * rent.minimum_balance(Token::LEN)
+        );
+    }
+}
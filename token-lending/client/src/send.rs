@@ -0,0 +1,184 @@
+//! Transaction sending with blockhash refresh, backoff and confirmation
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_client::RpcClient,
+    rpc_config::RpcSendTransactionConfig,
+};
+use solana_sdk::{
+    commitment_config::{CommitmentConfig, CommitmentLevel},
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::Signature,
+    signers::Signers,
+    transaction::{Transaction, TransactionError},
+};
+use std::{cmp::min, thread::sleep, time::Duration};
+
+/// Default number of times a transaction is sent before giving up
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// How often signature statuses are polled while waiting for confirmation
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How transactions are sent and confirmed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SendConfig {
+    /// Commitment the transaction must reach before it counts as sent
+    pub commitment: CommitmentConfig,
+    /// Skip the preflight simulation
+    pub skip_preflight: bool,
+    /// Commitment the preflight simulation runs at
+    pub preflight_commitment: CommitmentLevel,
+    /// Number of times the transaction is signed with a fresh blockhash and sent, at least 1
+    pub max_attempts: u32,
+    /// Delay before the second attempt, doubled after every further attempt
+    pub initial_backoff: Duration,
+    /// Longest delay between attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for SendConfig {
+    fn default() -> Self {
+        Self {
+            commitment: CommitmentConfig::confirmed(),
+            skip_preflight: false,
+            preflight_commitment: CommitmentLevel::Processed,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+/// Delay before sending attempt `attempt`, counting from 0
+pub fn backoff(config: &SendConfig, attempt: u32) -> Duration {
+    if attempt == 0 {
+        return Duration::ZERO;
+    }
+    let factor = 1u32.checked_shl(attempt - 1).unwrap_or(u32::MAX);
+    min(
+        config.initial_backoff.saturating_mul(factor),
+        config.max_backoff,
+    )
+}
+
+/// True if `err` means the transaction's blockhash is unknown or expired, so it can be signed
+/// again with a fresh blockhash and resent
+pub fn is_blockhash_not_found(err: &ClientError) -> bool {
+    err.get_transaction_error() == Some(TransactionError::BlockhashNotFound)
+}
+
+/// Sign `instructions` with a fresh blockhash, send them and wait for `config.commitment`.
+///
+/// When the blockhash is rejected or expires before the transaction lands, the transaction is
+/// signed again with a new blockhash and resent after an exponential backoff, up to
+/// `config.max_attempts` times. Any other error is returned immediately.
+pub fn send_and_confirm<T: Signers>(
+    rpc_client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &T,
+    config: &SendConfig,
+) -> ClientResult<Signature> {
+    let message = Message::new(instructions, Some(payer));
+    let mut last_err = None;
+    for attempt in 0..config.max_attempts.max(1) {
+        sleep(backoff(config, attempt));
+
+        let blockhash = rpc_client.get_latest_blockhash()?;
+        let mut transaction = Transaction::new_unsigned(message.clone());
+        transaction.try_sign(signers, blockhash)?;
+
+        match send_and_wait(rpc_client, &transaction, config) {
+            Err(err) if is_blockhash_not_found(&err) => last_err = Some(err),
+            result => return result,
+        }
+    }
+    Err(last_err.unwrap_or_else(|| ClientErrorKind::Custom("no attempts made".to_string()).into()))
+}
+
+/// Send a signed transaction once and wait until it reaches `config.commitment` or its blockhash
+/// expires, in which case `BlockhashNotFound` is returned
+fn send_and_wait(
+    rpc_client: &RpcClient,
+    transaction: &Transaction,
+    config: &SendConfig,
+) -> ClientResult<Signature> {
+    let signature = rpc_client.send_transaction_with_config(
+        transaction,
+        RpcSendTransactionConfig {
+            skip_preflight: config.skip_preflight,
+            preflight_commitment: Some(config.preflight_commitment),
+            ..RpcSendTransactionConfig::default()
+        },
+    )?;
+
+    loop {
+        let blockhash_valid = rpc_client
+            .is_blockhash_valid(&transaction.message.recent_blockhash, config.commitment)?;
+        // check the status after the blockhash so a transaction landing in between isn't missed
+        if let Some(result) =
+            rpc_client.get_signature_status_with_commitment(&signature, config.commitment)?
+        {
+            return result
+                .map(|()| signature)
+                .map_err(|err| ClientErrorKind::TransactionError(err).into());
+        }
+        if !blockhash_valid {
+            return Err(
+                ClientErrorKind::TransactionError(TransactionError::BlockhashNotFound).into(),
+            );
+        }
+        sleep(CONFIRMATION_POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_client::{
+        rpc_request::{RpcError, RpcResponseErrorData},
+        rpc_response::RpcSimulateTransactionResult,
+    };
+
+    #[test]
+    fn test_backoff() {
+        let config = SendConfig::default();
+        assert_eq!(backoff(&config, 0), Duration::ZERO);
+        assert_eq!(backoff(&config, 1), Duration::from_millis(500));
+        assert_eq!(backoff(&config, 2), Duration::from_secs(1));
+        assert_eq!(backoff(&config, 4), Duration::from_secs(4));
+        assert_eq!(backoff(&config, 5), Duration::from_secs(8));
+        assert_eq!(backoff(&config, 40), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn test_is_blockhash_not_found() {
+        let err: ClientError =
+            ClientErrorKind::TransactionError(TransactionError::BlockhashNotFound).into();
+        assert!(is_blockhash_not_found(&err));
+
+        // preflight failures carry the simulation error
+        let err: ClientError = ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: -32002,
+            message: "Transaction simulation failed".to_string(),
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(
+                RpcSimulateTransactionResult {
+                    err: Some(TransactionError::BlockhashNotFound),
+                    logs: None,
+                    accounts: None,
+                    units_consumed: None,
+                    return_data: None,
+                },
+            ),
+        })
+        .into();
+        assert!(is_blockhash_not_found(&err));
+
+        let err: ClientError =
+            ClientErrorKind::TransactionError(TransactionError::AccountNotFound).into();
+        assert!(!is_blockhash_not_found(&err));
+    }
+}
@@ -0,0 +1,280 @@
+//! Transaction simulation decoded into lending errors and events. Lending error codes are resolved
+//! through `solend-error-catalog`, so simulations, the CLI and the client describe them the same way.
+
+use num_traits::FromPrimitive;
+use solana_client::{
+    client_error::Result as ClientResult, rpc_client::RpcClient,
+    rpc_config::RpcSimulateTransactionConfig, rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    message::Message,
+    pubkey::Pubkey,
+    transaction::{Transaction, TransactionError},
+};
+use solend_error_catalog::{ErrorCatalog, ErrorDescription};
+use solend_event_parser::{parse_logs, LendingEvent};
+use spl_token::error::TokenError;
+use std::{fmt, str::FromStr};
+
+/// Why a simulated transaction failed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SimulationError {
+    /// The lending program returned one of its errors
+    Lending {
+        /// Index of the failing top-level instruction
+        instruction: u8,
+        /// Error returned by the lending program, as described by the error catalog
+        error: ErrorDescription,
+    },
+    /// The token program returned one of its errors, usually from a lending program CPI
+    Token {
+        /// Index of the failing top-level instruction
+        instruction: u8,
+        /// Error returned by the token program
+        error: TokenError,
+    },
+    /// An instruction failed with an error no known program returned
+    Instruction {
+        /// Index of the failing top-level instruction
+        instruction: u8,
+        /// Program that failed, when the logs show it
+        program_id: Option<Pubkey>,
+        /// Error the instruction failed with
+        error: InstructionError,
+    },
+    /// The transaction failed before or outside any instruction
+    Transaction(TransactionError),
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationError::Lending { instruction, error } => {
+                write!(f, "instruction {}: {}", instruction, error)
+            }
+            SimulationError::Token { instruction, error } => {
+                write!(f, "instruction {}: token program: {}", instruction, error)
+            }
+            SimulationError::Instruction {
+                instruction,
+                program_id: Some(program_id),
+                error,
+            } => write!(f, "instruction {}: {}: {}", instruction, program_id, error),
+            SimulationError::Instruction {
+                instruction,
+                program_id: None,
+                error,
+            } => write!(f, "instruction {}: {}", instruction, error),
+            SimulationError::Transaction(error) => error.fmt(f),
+        }
+    }
+}
+
+/// Outcome of a simulated transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulationReport {
+    /// Why the transaction would fail, `None` if it would succeed
+    pub error: Option<SimulationError>,
+    /// Events the lending program logged before the transaction finished or failed
+    pub events: Vec<LendingEvent>,
+    /// Raw log messages
+    pub logs: Vec<String>,
+    /// Compute units the transaction consumed
+    pub units_consumed: Option<u64>,
+}
+
+impl SimulationReport {
+    /// True if the transaction would succeed
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Simulate `instructions` against the cluster's latest state and decode the result.
+///
+/// Signatures aren't verified and the cluster substitutes a recent blockhash, so nothing needs
+/// to be signed and the report can be shown before asking the user to sign.
+pub fn simulate(
+    rpc_client: &RpcClient,
+    lending_program_id: &Pubkey,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+) -> ClientResult<SimulationReport> {
+    let transaction = Transaction::new_unsigned(Message::new(instructions, Some(payer)));
+    let result = rpc_client
+        .simulate_transaction_with_config(
+            &transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(rpc_client.commitment()),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )?
+        .value;
+    Ok(decode_simulation(lending_program_id, &result))
+}
+
+/// Decode the result of simulating a transaction for `lending_program_id`
+pub fn decode_simulation(
+    lending_program_id: &Pubkey,
+    result: &RpcSimulateTransactionResult,
+) -> SimulationReport {
+    let logs = result.logs.clone().unwrap_or_default();
+    SimulationReport {
+        error: result
+            .err
+            .clone()
+            .map(|err| decode_error(lending_program_id, err, &logs)),
+        events: parse_logs(lending_program_id, &logs),
+        logs,
+        units_consumed: result.units_consumed,
+    }
+}
+
+/// Map a transaction error to the error of the program that raised it. Custom error codes only
+/// mean something to the program that returned them, which the logs show as the first
+/// `Program <id> failed:` line: the innermost failing invocation.
+pub fn decode_error<S: AsRef<str>>(
+    lending_program_id: &Pubkey,
+    err: TransactionError,
+    logs: &[S],
+) -> SimulationError {
+    match err {
+        TransactionError::InstructionError(instruction, error) => {
+            decode_instruction_error(lending_program_id, instruction, failed_program(logs), error)
+        }
+        err => SimulationError::Transaction(err),
+    }
+}
+
+/// Map the error top-level instruction `instruction` failed with to the error of `program_id`,
+/// the program that raised it
+pub fn decode_instruction_error(
+    lending_program_id: &Pubkey,
+    instruction: u8,
+    program_id: Option<Pubkey>,
+    error: InstructionError,
+) -> SimulationError {
+    if let Some(program_id) = program_id {
+        let catalog = ErrorCatalog::default().with_lending_program(*lending_program_id);
+        if let Some(error) = catalog.decode_instruction_error(&program_id, &error) {
+            return SimulationError::Lending { instruction, error };
+        }
+        if let InstructionError::Custom(code) = error {
+            if program_id == spl_token::id() {
+                if let Some(error) = TokenError::from_u32(code) {
+                    return SimulationError::Token { instruction, error };
+                }
+            }
+        }
+    }
+    SimulationError::Instruction {
+        instruction,
+        program_id,
+        error,
+    }
+}
+
+/// The innermost program that failed, from the logs of a failed transaction
+pub fn failed_program<S: AsRef<str>>(logs: &[S]) -> Option<Pubkey> {
+    logs.iter().find_map(|log| {
+        let mut words = log.as_ref().strip_prefix("Program ")?.split(' ');
+        match (words.next(), words.next()) {
+            (Some(id), Some("failed:")) => Pubkey::from_str(id).ok(),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solend_error_catalog::decode_lending_error;
+    use solend_sdk::error::LendingError;
+
+    fn result(err: Option<TransactionError>, logs: Vec<String>) -> RpcSimulateTransactionResult {
+        RpcSimulateTransactionResult {
+            err,
+            logs: Some(logs),
+            accounts: None,
+            units_consumed: Some(12_345),
+            return_data: None,
+        }
+    }
+
+    #[test]
+    fn test_decode_lending_error() {
+        let program_id = Pubkey::new_unique();
+        let code = LendingError::BorrowTooLarge as u32;
+        let report = decode_simulation(
+            &program_id,
+            &result(
+                Some(TransactionError::InstructionError(
+                    1,
+                    InstructionError::Custom(code),
+                )),
+                vec![
+                    format!("Program {} invoke [1]", program_id),
+                    format!(
+                        "Program {} failed: custom program error: {:#x}",
+                        program_id, code
+                    ),
+                ],
+            ),
+        );
+        assert!(!report.is_ok());
+        assert_eq!(
+            report.error,
+            Some(SimulationError::Lending {
+                instruction: 1,
+                error: decode_lending_error(code).unwrap(),
+            })
+        );
+        assert_eq!(report.units_consumed, Some(12_345));
+        assert_eq!(report.logs.len(), 2);
+    }
+
+    #[test]
+    fn test_decode_cpi_error() {
+        let program_id = Pubkey::new_unique();
+        let code = TokenError::InsufficientFunds as u32;
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            format!("Program {} invoke [2]", spl_token::id()),
+            format!(
+                "Program {} failed: custom program error: {:#x}",
+                spl_token::id(),
+                code
+            ),
+            format!(
+                "Program {} failed: custom program error: {:#x}",
+                program_id, code
+            ),
+        ];
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(code));
+        // the same code means something else to the lending program
+        assert_eq!(
+            decode_error(&program_id, err.clone(), &logs),
+            SimulationError::Token {
+                instruction: 0,
+                error: TokenError::InsufficientFunds,
+            }
+        );
+
+        let other = Pubkey::new_unique();
+        assert_eq!(
+            decode_error(&other, err, &logs[..0]),
+            SimulationError::Instruction {
+                instruction: 0,
+                program_id: None,
+                error: InstructionError::Custom(code),
+            }
+        );
+        assert_eq!(
+            decode_error(&program_id, TransactionError::AccountNotFound, &logs),
+            SimulationError::Transaction(TransactionError::AccountNotFound)
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Websocket subscriptions that decode lending account updates
+
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::{
+    pubsub_client::{PubsubAccountClientSubscription, PubsubClient, PubsubClientError},
+    rpc_config::RpcAccountInfoConfig,
+    rpc_response::Response as RpcResponse,
+};
+use solana_sdk::{
+    account::Account,
+    clock::Slot,
+    commitment_config::CommitmentConfig,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+};
+use solend_sdk::state::{Obligation, Reserve};
+use std::{
+    sync::mpsc::{channel, Receiver},
+    thread,
+};
+
+/// A decoded account update
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateUpdate<T> {
+    /// Slot the update was observed at
+    pub slot: Slot,
+    /// Account that changed
+    pub pubkey: Pubkey,
+    /// Decoded account state, or why it couldn't be decoded, e.g. because the account was closed
+    pub state: Result<T, ProgramError>,
+}
+
+/// An open account subscription delivering decoded updates
+pub struct StateSubscription<T> {
+    subscription: PubsubAccountClientSubscription,
+    receiver: Receiver<StateUpdate<T>>,
+}
+
+impl<T> StateSubscription<T> {
+    /// Channel the decoded updates are delivered on
+    pub fn receiver(&self) -> &Receiver<StateUpdate<T>> {
+        &self.receiver
+    }
+
+    /// Unsubscribe and close the websocket. The receiver disconnects once pending updates are
+    /// drained.
+    pub fn shutdown(mut self) -> thread::Result<()> {
+        self.subscription.shutdown()
+    }
+}
+
+/// Subscribe to updates of the reserve at `reserve_pubkey`
+pub fn subscribe_reserve(
+    websocket_url: &str,
+    lending_program_id: &Pubkey,
+    reserve_pubkey: &Pubkey,
+    commitment: Option<CommitmentConfig>,
+) -> Result<StateSubscription<Reserve>, PubsubClientError> {
+    subscribe_state(
+        websocket_url,
+        lending_program_id,
+        reserve_pubkey,
+        commitment,
+    )
+}
+
+/// Subscribe to updates of the obligation at `obligation_pubkey`
+pub fn subscribe_obligation(
+    websocket_url: &str,
+    lending_program_id: &Pubkey,
+    obligation_pubkey: &Pubkey,
+    commitment: Option<CommitmentConfig>,
+) -> Result<StateSubscription<Obligation>, PubsubClientError> {
+    subscribe_state(
+        websocket_url,
+        lending_program_id,
+        obligation_pubkey,
+        commitment,
+    )
+}
+
+/// Subscribe to updates of any packed account owned by `program_id`
+pub fn subscribe_state<T: Pack + IsInitialized + Send + 'static>(
+    websocket_url: &str,
+    program_id: &Pubkey,
+    pubkey: &Pubkey,
+    commitment: Option<CommitmentConfig>,
+) -> Result<StateSubscription<T>, PubsubClientError> {
+    let (subscription, updates) = PubsubClient::account_subscribe(
+        websocket_url,
+        pubkey,
+        Some(RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment,
+            ..RpcAccountInfoConfig::default()
+        }),
+    )?;
+
+    let (sender, receiver) = channel();
+    let program_id = *program_id;
+    let pubkey = *pubkey;
+    thread::spawn(move || {
+        // ends when the subscription shuts down or the receiver is dropped
+        for update in updates.iter() {
+            if sender
+                .send(decode_update(&program_id, &pubkey, update))
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    Ok(StateSubscription {
+        subscription,
+        receiver,
+    })
+}
+
+fn decode_update<T: Pack + IsInitialized>(
+    program_id: &Pubkey,
+    pubkey: &Pubkey,
+    update: RpcResponse<UiAccount>,
+) -> StateUpdate<T> {
+    let state = match update.value.decode::<Account>() {
+        Some(account) if account.owner != *program_id => Err(ProgramError::IncorrectProgramId),
+        Some(account) => T::unpack(&account.data),
+        None => Err(ProgramError::InvalidAccountData),
+    };
+    StateUpdate {
+        slot: update.context.slot,
+        pubkey: *pubkey,
+        state,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_client::rpc_response::RpcResponseContext;
+
+    fn update(pubkey: &Pubkey, account: &Account) -> RpcResponse<UiAccount> {
+        RpcResponse {
+            context: RpcResponseContext {
+                slot: 7,
+                api_version: None,
+            },
+            value: UiAccount::encode(pubkey, account, UiAccountEncoding::Base64, None, None),
+        }
+    }
+
+    #[test]
+    fn test_decode_update() {
+        let program_id = Pubkey::new_unique();
+        let pubkey = Pubkey::new_unique();
+        let reserve = Reserve {
+            version: 1,
+            ..Reserve::default()
+        };
+        let mut account = Account::new(1, Reserve::LEN, &program_id);
+        Reserve::pack(reserve.clone(), &mut account.data).unwrap();
+
+        let decoded = decode_update::<Reserve>(&program_id, &pubkey, update(&pubkey, &account));
+        assert_eq!(
+            decoded,
+            StateUpdate {
+                slot: 7,
+                pubkey,
+                state: Ok(reserve),
+            }
+        );
+
+        // an account owned by another program is never decoded
+        account.owner = Pubkey::new_unique();
+        let decoded = decode_update::<Reserve>(&program_id, &pubkey, update(&pubkey, &account));
+        assert_eq!(decoded.state, Err(ProgramError::IncorrectProgramId));
+
+        // a closed account has no data left to decode
+        let closed = Account::new(0, 0, &program_id);
+        let decoded = decode_update::<Reserve>(&program_id, &pubkey, update(&pubkey, &closed));
+        assert!(decoded.state.is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! Conversion between raw token amounts and the amounts shown to users, following the mint's
+//! decimals and, for Token-2022 mints, the interest-bearing extension
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_client::RpcClient,
+};
+use solana_sdk::{
+    clock::UnixTimestamp, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
+};
+use solend_sdk::math::{Decimal, SCALE};
+use spl_token_2022::extension::{
+    interest_bearing_mint::InterestBearingConfig, BaseStateWithExtensions, StateWithExtensions,
+};
+
+/// How a mint's raw amounts are shown to users
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UiAmountConfig {
+    /// Mint decimals
+    pub decimals: u8,
+    /// Interest accrued on the displayed amount, for Token-2022 interest-bearing mints
+    pub interest_bearing: Option<InterestBearingConfig>,
+}
+
+impl UiAmountConfig {
+    /// Display config of a mint owned by `owner`, which must be the token or Token-2022 program
+    pub fn from_mint_account(owner: &Pubkey, data: &[u8]) -> Result<Self, ProgramError> {
+        if *owner == spl_token::id() {
+            let mint = spl_token::state::Mint::unpack(data)?;
+            Ok(Self {
+                decimals: mint.decimals,
+                interest_bearing: None,
+            })
+        } else if *owner == spl_token_2022::id() {
+            let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data)?;
+            Ok(Self {
+                decimals: mint.base.decimals,
+                interest_bearing: mint.get_extension::<InterestBearingConfig>().ok().copied(),
+            })
+        } else {
+            Err(ProgramError::IncorrectProgramId)
+        }
+    }
+
+    /// `amount` as shown to users at `unix_timestamp`, with trailing zeros trimmed
+    pub fn amount_to_ui_amount_string(&self, amount: u64, unix_timestamp: UnixTimestamp) -> String {
+        self.interest_bearing
+            .and_then(|config| config.amount_to_ui_amount(amount, self.decimals, unix_timestamp))
+            .unwrap_or_else(|| amount_to_ui_amount_string(amount, self.decimals))
+    }
+
+    /// Raw amount of `ui_amount` at `unix_timestamp`
+    pub fn try_ui_amount_into_amount(
+        &self,
+        ui_amount: &str,
+        unix_timestamp: UnixTimestamp,
+    ) -> Result<u64, ProgramError> {
+        match self.interest_bearing {
+            Some(config) => {
+                config.try_ui_amount_into_amount(ui_amount, self.decimals, unix_timestamp)
+            }
+            None => spl_token_2022::try_ui_amount_into_amount(ui_amount.to_string(), self.decimals),
+        }
+    }
+}
+
+/// `amount` of a mint with `decimals` and no extensions, with trailing zeros trimmed
+pub fn amount_to_ui_amount_string(amount: u64, decimals: u8) -> String {
+    spl_token_2022::amount_to_ui_amount_string_trimmed(amount, decimals)
+}
+
+/// Display config of `mint`
+pub fn get_ui_amount_config(rpc_client: &RpcClient, mint: &Pubkey) -> ClientResult<UiAmountConfig> {
+    let account = rpc_client.get_account(mint)?;
+    UiAmountConfig::from_mint_account(&account.owner, &account.data)
+        .map_err(|err| ClientError::from(ClientErrorKind::Custom(format!("{}: {}", mint, err))))
+}
+
+/// A fractional raw amount, such as a reserve's borrowed liquidity, as shown to users of a mint
+/// with `decimals`, with trailing zeros trimmed
+pub fn decimal_to_ui_amount_string(amount: Decimal, decimals: u8) -> Result<String, ProgramError> {
+    let digits = amount.to_scaled_val()?.to_string();
+    let scale = SCALE + decimals as usize;
+    let (integer, fraction) = if digits.len() > scale {
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        (integer.to_string(), fraction.to_string())
+    } else {
+        ("0".to_string(), format!("{:0>1$}", digits, scale))
+    };
+    let fraction = fraction.trim_end_matches('0');
+    Ok(if fraction.is_empty() {
+        integer
+    } else {
+        format!("{}.{}", integer, fraction)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use spl_token_2022::pod::{OptionalNonZeroPubkey, PodI16, PodI64};
+
+    #[test]
+    fn test_amounts() {
+        let config = UiAmountConfig {
+            decimals: 6,
+            interest_bearing: None,
+        };
+        assert_eq!(config.amount_to_ui_amount_string(1_500_000, 0), "1.5");
+        assert_eq!(config.amount_to_ui_amount_string(1, 0), "0.000001");
+        assert_eq!(config.try_ui_amount_into_amount("1.5", 0), Ok(1_500_000));
+        assert!(config.try_ui_amount_into_amount("0.0000001", 0).is_err());
+    }
+
+    #[test]
+    fn test_interest_bearing_amounts() {
+        const ONE_YEAR: i64 = 31_556_736;
+        let config = UiAmountConfig {
+            decimals: 2,
+            interest_bearing: Some(InterestBearingConfig {
+                rate_authority: OptionalNonZeroPubkey::default(),
+                initialization_timestamp: PodI64::from(0),
+                pre_update_average_rate: PodI16::from(0),
+                last_update_timestamp: PodI64::from(0),
+                current_rate: PodI16::from(1_000),
+            }),
+        };
+        assert_eq!(config.amount_to_ui_amount_string(10_000, 0), "100");
+        // 10% compounded continuously for a year
+        assert_eq!(
+            config.amount_to_ui_amount_string(10_000, ONE_YEAR),
+            "110.51709180756477"
+        );
+        assert_eq!(
+            config.try_ui_amount_into_amount("110.51709180756477", ONE_YEAR),
+            Ok(10_000)
+        );
+    }
+
+    #[test]
+    fn test_decimal_amounts() {
+        assert_eq!(
+            decimal_to_ui_amount_string(Decimal::from(1_500_000u64), 6),
+            Ok("1.5".to_string())
+        );
+        assert_eq!(
+            decimal_to_ui_amount_string(Decimal::from_scaled_val(5), 0),
+            Ok("0.000000000000000005".to_string())
+        );
+        assert_eq!(
+            decimal_to_ui_amount_string(Decimal::from(100u64), 2),
+            Ok("1".to_string())
+        );
+        assert_eq!(
+            decimal_to_ui_amount_string(Decimal::zero(), 9),
+            Ok("0".to_string())
+        );
+    }
+}
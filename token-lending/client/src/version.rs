@@ -0,0 +1,123 @@
+//! Runtime detection of the version and features of a deployed lending program
+
+use solana_client::{
+    client_error::{ClientErrorKind, Result as ClientResult},
+    rpc_client::RpcClient,
+    rpc_config::RpcSimulateTransactionConfig,
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    message::Message,
+    pubkey::Pubkey,
+    transaction::{Transaction, TransactionError},
+};
+use solend_sdk::{instruction::get_version, version::ProgramVersion};
+
+/// Version of the lending program at `lending_program_id`, read by simulating its `GetVersion`
+/// instruction. `payer` pays for the simulation and must exist, but doesn't sign. Programs that
+/// predate the instruction fail it and return `None`.
+pub fn get_program_version(
+    rpc_client: &RpcClient,
+    lending_program_id: &Pubkey,
+    payer: &Pubkey,
+) -> ClientResult<Option<ProgramVersion>> {
+    let transaction = Transaction::new_unsigned(Message::new(
+        &[get_version(*lending_program_id)],
+        Some(payer),
+    ));
+    let result = rpc_client
+        .simulate_transaction_with_config(
+            &transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(rpc_client.commitment()),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )?
+        .value;
+    decode_version(lending_program_id, &result)
+}
+
+/// Decode the result of simulating a `GetVersion` instruction of `lending_program_id`
+pub fn decode_version(
+    lending_program_id: &Pubkey,
+    result: &RpcSimulateTransactionResult,
+) -> ClientResult<Option<ProgramVersion>> {
+    match &result.err {
+        Some(TransactionError::InstructionError(0, _)) => return Ok(None),
+        Some(err) => return Err(ClientErrorKind::TransactionError(err.clone()).into()),
+        None => {}
+    }
+    let return_data = match &result.return_data {
+        Some(return_data) if return_data.program_id == lending_program_id.to_string() => {
+            return_data
+        }
+        _ => return Ok(None),
+    };
+    let data = base64::decode(&return_data.data.0)
+        .map_err(|err| ClientErrorKind::Custom(format!("return data: {}", err)))?;
+    ProgramVersion::unpack(&data)
+        .map(Some)
+        .map_err(|err| ClientErrorKind::Custom(format!("return data: {}", err)).into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_sdk::instruction::InstructionError;
+    use solana_transaction_status::{UiReturnDataEncoding, UiTransactionReturnData};
+    use solend_sdk::{error::LendingError, version::FEATURE_FLASH_LOANS};
+
+    fn simulation(
+        err: Option<TransactionError>,
+        return_data: Option<UiTransactionReturnData>,
+    ) -> RpcSimulateTransactionResult {
+        RpcSimulateTransactionResult {
+            err,
+            logs: None,
+            accounts: None,
+            units_consumed: None,
+            return_data,
+        }
+    }
+
+    #[test]
+    fn test_decode_version() {
+        let program_id = Pubkey::new_unique();
+        let version = ProgramVersion {
+            major: 2,
+            minor: 1,
+            patch: 0,
+            features: FEATURE_FLASH_LOANS,
+        };
+        let return_data = UiTransactionReturnData {
+            program_id: program_id.to_string(),
+            data: (base64::encode(version.pack()), UiReturnDataEncoding::Base64),
+        };
+
+        assert_eq!(
+            decode_version(&program_id, &simulation(None, Some(return_data.clone()))).unwrap(),
+            Some(version)
+        );
+        // return data set by another program isn't the lending program's version
+        assert_eq!(
+            decode_version(&Pubkey::new_unique(), &simulation(None, Some(return_data))).unwrap(),
+            None
+        );
+
+        let unknown_instruction = TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::InstructionUnpackError as u32),
+        );
+        assert_eq!(
+            decode_version(&program_id, &simulation(Some(unknown_instruction), None)).unwrap(),
+            None
+        );
+        assert!(decode_version(
+            &program_id,
+            &simulation(Some(TransactionError::AccountNotFound), None)
+        )
+        .is_err());
+    }
+}
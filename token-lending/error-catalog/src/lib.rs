@@ -0,0 +1,223 @@
+#![deny(missing_docs)]
+
+//! Maps custom error codes returned by Solend programs to their names and a
+//! human-readable explanation, for friendlier client and CLI failure messages.
+
+use num_traits::FromPrimitive;
+use solana_program::{instruction::InstructionError, pubkey::Pubkey};
+use solend_sdk::error::LendingError;
+use std::fmt;
+
+/// Name used for the lending program in error descriptions
+pub const LENDING_PROGRAM_NAME: &str = "Solend lending program";
+
+/// A decoded custom program error
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorDescription {
+    /// Program that returned the error
+    pub program: &'static str,
+    /// Custom error code
+    pub code: u32,
+    /// Error variant name, e.g. `ObligationStale`
+    pub name: String,
+    /// Short message the program logs for this error
+    pub message: String,
+    /// Longer explanation of the likely cause and how to resolve it
+    pub explanation: &'static str,
+}
+
+impl fmt::Display for ErrorDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} error {} ({}): {}. {}",
+            self.program, self.code, self.name, self.message, self.explanation
+        )
+    }
+}
+
+/// Set of program ids whose custom errors can be decoded
+#[derive(Clone, Debug)]
+pub struct ErrorCatalog {
+    lending_program_ids: Vec<Pubkey>,
+}
+
+impl Default for ErrorCatalog {
+    fn default() -> Self {
+        Self {
+            lending_program_ids: vec![
+                solend_sdk::solend_mainnet::id(),
+                solend_sdk::solend_devnet::id(),
+            ],
+        }
+    }
+}
+
+impl ErrorCatalog {
+    /// Also decode errors from a lending program deployed at `program_id`
+    pub fn with_lending_program(mut self, program_id: Pubkey) -> Self {
+        if !self.lending_program_ids.contains(&program_id) {
+            self.lending_program_ids.push(program_id);
+        }
+        self
+    }
+
+    /// Decode a custom error `code` returned by `program_id`
+    pub fn decode(&self, program_id: &Pubkey, code: u32) -> Option<ErrorDescription> {
+        if self.lending_program_ids.contains(program_id) {
+            decode_lending_error(code)
+        } else {
+            None
+        }
+    }
+
+    /// Decode an instruction error returned by `program_id`, if it is a known custom error
+    pub fn decode_instruction_error(
+        &self,
+        program_id: &Pubkey,
+        error: &InstructionError,
+    ) -> Option<ErrorDescription> {
+        match error {
+            InstructionError::Custom(code) => self.decode(program_id, *code),
+            _ => None,
+        }
+    }
+}
+
+/// Decode a custom error `code` returned by `program_id` using the default catalog
+pub fn decode(program_id: &Pubkey, code: u32) -> Option<ErrorDescription> {
+    ErrorCatalog::default().decode(program_id, code)
+}
+
+/// Decode a lending program error code regardless of which program id returned it
+pub fn decode_lending_error(code: u32) -> Option<ErrorDescription> {
+    let error = LendingError::from_u32(code)?;
+    Some(ErrorDescription {
+        program: LENDING_PROGRAM_NAME,
+        code,
+        name: format!("{:?}", error),
+        message: error.to_string(),
+        explanation: lending_error_explanation(&error),
+    })
+}
+
+fn lending_error_explanation(error: &LendingError) -> &'static str {
+    use LendingError::*;
+    match error {
+        // 0
+        InstructionUnpackError => "The instruction data could not be decoded; check that the client and program versions match",
+        AlreadyInitialized => "The account was already initialized; use a fresh account",
+        NotRentExempt => "The account does not hold enough lamports to be rent exempt; fund it with the rent-exempt minimum for its size",
+        InvalidMarketAuthority => "The lending market authority is not the PDA derived from the lending market address",
+        InvalidMarketOwner => "The signer is not the owner of the lending market",
+
+        // 5
+        InvalidAccountOwner => "An account is not owned by the expected program; check that the reserve, obligation and market belong to this lending program",
+        InvalidTokenOwner => "A token account or mint is not owned by the SPL Token program",
+        InvalidTokenAccount => "A token account does not match the one recorded on the reserve, or has the wrong mint or owner",
+        InvalidTokenMint => "A token mint does not match the one recorded on the reserve",
+        InvalidTokenProgram => "The token program account passed is not the SPL Token program",
+
+        // 10
+        InvalidAmount => "The amount must be greater than zero",
+        InvalidConfig => "A reserve or market configuration value is out of range",
+        InvalidSigner => "A required account did not sign the transaction",
+        InvalidAccountInput => "An account does not match what the instruction expects; compare the account list with the instruction documentation",
+        MathOverflow => "An arithmetic operation overflowed; try a smaller amount",
+
+        // 15
+        TokenInitializeMintFailed => "The SPL Token initialize mint call failed; check the program logs for the token program error",
+        TokenInitializeAccountFailed => "The SPL Token initialize account call failed; check the program logs for the token program error",
+        TokenTransferFailed => "The SPL Token transfer failed; check balances, delegation and the transfer authority",
+        TokenMintToFailed => "The SPL Token mint to call failed; check the program logs for the token program error",
+        TokenBurnFailed => "The SPL Token burn failed; check the collateral balance and transfer authority",
+
+        // 20
+        InsufficientLiquidity => "The reserve does not have enough available liquidity for this request",
+        ReserveCollateralDisabled => "This reserve's collateral cannot be used to borrow",
+        ReserveStale => "The reserve must be refreshed in the same slot; add a RefreshReserve instruction before this one",
+        WithdrawTooSmall => "The withdraw amount rounds down to zero",
+        WithdrawTooLarge => "The withdraw amount would leave the obligation unhealthy or exceeds the deposited amount",
+
+        // 25
+        BorrowTooSmall => "The borrow amount is too small to receive any liquidity after fees",
+        BorrowTooLarge => "The borrow amount exceeds the remaining borrowing power of the obligation",
+        RepayTooSmall => "The repay amount rounds down to zero",
+        LiquidationTooSmall => "The liquidation amount is too small to receive any collateral",
+        ObligationHealthy => "The obligation is healthy and cannot be liquidated",
+
+        // 30
+        ObligationStale => "The obligation must be refreshed in the same slot; add RefreshReserve for each of its reserves and then RefreshObligation",
+        ObligationReserveLimit => "The obligation already uses the maximum number of deposit and borrow reserves",
+        InvalidObligationOwner => "The signer is not the owner of the obligation",
+        ObligationDepositsEmpty => "The obligation has no deposits",
+        ObligationBorrowsEmpty => "The obligation has no borrows",
+
+        // 35
+        ObligationDepositsZero => "The obligation deposits are worth nothing at current prices",
+        ObligationBorrowsZero => "The obligation borrows are worth nothing at current prices",
+        InvalidObligationCollateral => "The obligation has no deposit in the given reserve",
+        InvalidObligationLiquidity => "The obligation has no borrow from the given reserve",
+        ObligationCollateralEmpty => "The obligation deposit in the given reserve is empty",
+
+        // 40
+        ObligationLiquidityEmpty => "The obligation borrow from the given reserve is empty",
+        NegativeInterestRate => "The reserve interest rate configuration produced a negative rate",
+        InvalidOracleConfig => "The oracle accounts do not match the reserve, or the price is stale or invalid",
+        InvalidFlashLoanReceiverProgram => "The flash loan receiver program is not valid",
+        NotEnoughLiquidityAfterFlashLoan => "The flash loan receiver did not return the borrowed amount plus fees",
+
+        // 45
+        NullOracleConfig => "Both the Pyth and Switchboard oracles are unset; at least one must be configured",
+        InsufficientProtocolFeesToRedeem => "There are no accumulated protocol fees to redeem, or no liquidity available to pay them",
+        FlashBorrowCpi => "Flash borrows must be top level instructions, not cross-program invocations",
+        NoFlashRepayFound => "A flash borrow must be followed by a matching FlashRepayReserveLiquidity instruction in the same transaction",
+        InvalidFlashRepay => "The flash repay does not match its flash borrow; check the amount, reserve and borrow instruction index",
+
+        // 50
+        FlashRepayCpi => "Flash repays must be top level instructions, not cross-program invocations",
+        MultipleFlashBorrows => "Only one flash borrow is allowed per transaction",
+        FlashLoansDisabled => "Flash loans are disabled for this reserve",
+        DeprecatedInstruction => "This instruction is deprecated; use its replacement documented in the instruction set",
+        OutflowRateLimitExceeded => "The market or reserve outflow rate limit was reached; retry later or with a smaller amount",
+
+        // 55
+        NotWhitelistedLiquidator => "Only the whitelisted liquidator may liquidate on this market",
+        IsolatedTierAssetViolation => "Isolated tier assets can only be borrowed on their own; repay other borrows first",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn every_lending_error_is_decoded() {
+        let mut code = 0;
+        while let Some(error) = LendingError::from_u32(code) {
+            let description = decode(&solend_sdk::solend_mainnet::id(), code).unwrap();
+            assert_eq!(description.name, format!("{:?}", error));
+            assert!(!description.explanation.is_empty());
+            code += 1;
+        }
+        assert_eq!(code, LendingError::IsolatedTierAssetViolation as u32 + 1);
+        assert_eq!(decode_lending_error(code), None);
+    }
+
+    #[test]
+    fn only_known_programs_are_decoded() {
+        let program_id = Pubkey::new_unique();
+        let code = LendingError::ObligationStale as u32;
+        assert_eq!(decode(&program_id, code), None);
+
+        let catalog = ErrorCatalog::default().with_lending_program(program_id);
+        let description = catalog
+            .decode_instruction_error(&program_id, &InstructionError::Custom(code))
+            .unwrap();
+        assert_eq!(description.name, "ObligationStale");
+        assert_eq!(
+            catalog.decode_instruction_error(&program_id, &InstructionError::InvalidArgument),
+            None
+        );
+    }
+}
@@ -0,0 +1,366 @@
+#![deny(missing_docs)]
+
+//! Typed events for indexers, from the logs and instructions of Solend transactions.
+//!
+//! Deposits, redemptions, borrows, repays and flash loans are read from the lending instructions
+//! of a transaction, top level and inner, with [parse_instruction](fn.parse_instruction.html).
+//! Their amounts are the requested amounts, `u64::MAX` meaning "as much as possible".
+//! Liquidations are read from the event records the program logs, with
+//! [parse_logs](fn.parse_logs.html), and carry the amounts actually transferred.
+//!
+//! Only parse successful transactions: a failed transaction still has logs and instructions.
+//!
+//! Newer program versions may add instructions and events, which are skipped or returned as
+//! [LendingEvent::Unknown](enum.LendingEvent.html#variant.Unknown), and may append fields to
+//! existing events, which are ignored.
+
+use solana_program::pubkey::Pubkey;
+use solend_events::{parse_event_record, Event, EventRecord, DISCRIMINATOR_LEN};
+use solend_sdk::{events::ObligationLiquidated, instruction::LendingInstruction};
+use std::mem::size_of;
+
+/// Something that happened in a lending market
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LendingEvent {
+    /// Liquidity was deposited into a reserve for collateral
+    Deposited {
+        /// Reserve deposited into
+        reserve: Pubkey,
+        /// Transfer authority of the depositor
+        user_transfer_authority: Pubkey,
+        /// Amount of liquidity deposited
+        liquidity_amount: u64,
+    },
+    /// Collateral was redeemed from a reserve for liquidity
+    Redeemed {
+        /// Reserve redeemed from
+        reserve: Pubkey,
+        /// Transfer authority of the redeemer
+        user_transfer_authority: Pubkey,
+        /// Amount of collateral redeemed
+        collateral_amount: u64,
+    },
+    /// Collateral was deposited into an obligation
+    CollateralDeposited {
+        /// Obligation deposited into
+        obligation: Pubkey,
+        /// Reserve of the collateral
+        reserve: Pubkey,
+        /// Amount of collateral deposited
+        collateral_amount: u64,
+    },
+    /// Collateral was withdrawn from an obligation
+    CollateralWithdrawn {
+        /// Obligation withdrawn from
+        obligation: Pubkey,
+        /// Reserve of the collateral
+        reserve: Pubkey,
+        /// Amount of collateral withdrawn, `u64::MAX` for as much as possible
+        collateral_amount: u64,
+    },
+    /// Liquidity was deposited into a reserve and the collateral into an obligation
+    DepositedAsCollateral {
+        /// Obligation the collateral was deposited into
+        obligation: Pubkey,
+        /// Reserve deposited into
+        reserve: Pubkey,
+        /// Amount of liquidity deposited
+        liquidity_amount: u64,
+    },
+    /// Collateral was withdrawn from an obligation and redeemed for liquidity
+    WithdrawnAndRedeemed {
+        /// Obligation withdrawn from
+        obligation: Pubkey,
+        /// Reserve of the collateral
+        reserve: Pubkey,
+        /// Amount of collateral withdrawn, `u64::MAX` for as much as possible
+        collateral_amount: u64,
+    },
+    /// Liquidity was borrowed against an obligation
+    Borrowed {
+        /// Borrowing obligation
+        obligation: Pubkey,
+        /// Reserve borrowed from
+        reserve: Pubkey,
+        /// Amount of liquidity borrowed, `u64::MAX` for as much as possible
+        liquidity_amount: u64,
+    },
+    /// Borrowed liquidity was repaid
+    Repaid {
+        /// Repaid obligation
+        obligation: Pubkey,
+        /// Reserve repaid to
+        reserve: Pubkey,
+        /// Amount of liquidity repaid, `u64::MAX` for the whole borrow
+        liquidity_amount: u64,
+    },
+    /// Liquidity was flash borrowed from a reserve
+    FlashBorrowed {
+        /// Reserve borrowed from
+        reserve: Pubkey,
+        /// Amount of liquidity borrowed
+        liquidity_amount: u64,
+    },
+    /// A flash loan was repaid
+    FlashRepaid {
+        /// Reserve repaid to
+        reserve: Pubkey,
+        /// Amount of liquidity repaid, excluding fees
+        liquidity_amount: u64,
+    },
+    /// An obligation was liquidated
+    Liquidated(ObligationLiquidated),
+    /// An event record this version of the crate doesn't know
+    Unknown {
+        /// Event discriminator
+        discriminator: [u8; DISCRIMINATOR_LEN],
+        /// Event payload
+        payload: Vec<u8>,
+    },
+}
+
+/// Event for a lending instruction of `program_id`, or `None` for instructions of other programs,
+/// instructions that don't move funds and instructions this version of the crate doesn't know.
+/// `accounts` are the instruction's accounts, with address lookup tables already resolved.
+pub fn parse_instruction(
+    program_id: &Pubkey,
+    instruction_program_id: &Pubkey,
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Option<LendingEvent> {
+    if instruction_program_id != program_id {
+        return None;
+    }
+    let account = |index: usize| accounts.get(index).copied();
+    let event = match LendingInstruction::unpack(data).ok()? {
+        LendingInstruction::DepositReserveLiquidity { liquidity_amount } => {
+            LendingEvent::Deposited {
+                reserve: account(2)?,
+                user_transfer_authority: account(7)?,
+                liquidity_amount,
+            }
+        }
+        LendingInstruction::RedeemReserveCollateral { collateral_amount } => {
+            LendingEvent::Redeemed {
+                reserve: account(2)?,
+                user_transfer_authority: account(7)?,
+                collateral_amount,
+            }
+        }
+        LendingInstruction::DepositObligationCollateral { collateral_amount } => {
+            LendingEvent::CollateralDeposited {
+                obligation: account(3)?,
+                reserve: account(2)?,
+                collateral_amount,
+            }
+        }
+        LendingInstruction::WithdrawObligationCollateral { collateral_amount } => {
+            LendingEvent::CollateralWithdrawn {
+                obligation: account(3)?,
+                reserve: account(2)?,
+                collateral_amount,
+            }
+        }
+        LendingInstruction::DepositReserveLiquidityAndObligationCollateral { liquidity_amount } => {
+            LendingEvent::DepositedAsCollateral {
+                obligation: account(8)?,
+                reserve: account(2)?,
+                liquidity_amount,
+            }
+        }
+        LendingInstruction::WithdrawObligationCollateralAndRedeemReserveCollateral {
+            collateral_amount,
+        } => LendingEvent::WithdrawnAndRedeemed {
+            obligation: account(3)?,
+            reserve: account(2)?,
+            collateral_amount,
+        },
+        LendingInstruction::BorrowObligationLiquidity { liquidity_amount } => {
+            LendingEvent::Borrowed {
+                obligation: account(4)?,
+                reserve: account(2)?,
+                liquidity_amount,
+            }
+        }
+        LendingInstruction::RepayObligationLiquidity { liquidity_amount } => LendingEvent::Repaid {
+            obligation: account(3)?,
+            reserve: account(2)?,
+            liquidity_amount,
+        },
+        LendingInstruction::FlashBorrowReserveLiquidity { liquidity_amount } => {
+            LendingEvent::FlashBorrowed {
+                reserve: account(2)?,
+                liquidity_amount,
+            }
+        }
+        LendingInstruction::FlashRepayReserveLiquidity {
+            liquidity_amount, ..
+        } => LendingEvent::FlashRepaid {
+            reserve: account(4)?,
+            liquidity_amount,
+        },
+        _ => return None,
+    };
+    Some(event)
+}
+
+/// Events logged by `program_id` in a transaction's log messages. Records logged by other
+/// programs, including programs the lending program invokes, are skipped.
+pub fn parse_logs<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Vec<LendingEvent> {
+    let program_id = program_id.to_string();
+    let mut invoke_stack: Vec<&str> = vec![];
+    let mut events = vec![];
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            if invoke_stack.last() == Some(&program_id.as_str()) {
+                events.extend(
+                    data.split(' ')
+                        .filter_map(|field| base64::decode(field).ok())
+                        .filter_map(|record| parse_event(&record)),
+                );
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            match (words.next(), words.next()) {
+                (Some(id), Some("invoke")) => invoke_stack.push(id),
+                (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                    invoke_stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
+
+/// Decode a single event record, or `None` if it isn't a record
+pub fn parse_event(record: &[u8]) -> Option<LendingEvent> {
+    let record = parse_event_record(record)?;
+    let event = match record.discriminator {
+        ObligationLiquidated::DISCRIMINATOR => LendingEvent::Liquidated(decode(&record)?),
+        discriminator => LendingEvent::Unknown {
+            discriminator,
+            payload: record.payload.to_vec(),
+        },
+    };
+    Some(event)
+}
+
+/// Decode an event whose payload may have fields appended by a newer program version
+fn decode<E: Event>(record: &EventRecord) -> Option<E> {
+    let payload = record.payload.get(..size_of::<E>())?;
+    Some(bytemuck::pod_read_unaligned(payload))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solend_events::{encode_event, EVENT_HEADER_LEN};
+    use solend_sdk::instruction::borrow_obligation_liquidity;
+
+    fn liquidation() -> ObligationLiquidated {
+        ObligationLiquidated {
+            obligation: Pubkey::new_unique(),
+            repay_reserve: Pubkey::new_unique(),
+            withdraw_reserve: Pubkey::new_unique(),
+            liquidator: Pubkey::new_unique(),
+            repay_amount: 100,
+            withdraw_amount: 50,
+        }
+    }
+
+    #[test]
+    fn test_parse_logs() {
+        let program_id = Pubkey::new_unique();
+        let other_program_id = Pubkey::new_unique();
+        let event = liquidation();
+        let record = base64::encode(encode_event(&event));
+
+        // a newer program version appended a field
+        let mut longer = encode_event(&event);
+        longer.extend_from_slice(&[7; 8]);
+        let payload_len = (longer.len() - EVENT_HEADER_LEN) as u32;
+        longer[DISCRIMINATOR_LEN..EVENT_HEADER_LEN].copy_from_slice(&payload_len.to_le_bytes());
+
+        let mut unknown = vec![1; DISCRIMINATOR_LEN];
+        unknown.extend_from_slice(&0u32.to_le_bytes());
+
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            "Program log: Instruction: Liquidate Obligation and Redeem Reserve Collateral"
+                .to_string(),
+            format!("Program {} invoke [2]", other_program_id),
+            format!("Program data: {}", record),
+            format!("Program {} success", other_program_id),
+            format!("Program data: {}", record),
+            format!("Program data: {}", base64::encode(&longer)),
+            format!("Program data: {}", base64::encode(&unknown)),
+            format!(
+                "Program {} consumed 1000 of 200000 compute units",
+                program_id
+            ),
+            format!("Program {} success", program_id),
+            format!("Program data: {}", record),
+        ];
+
+        assert_eq!(
+            parse_logs(&program_id, &logs),
+            vec![
+                LendingEvent::Liquidated(event),
+                LendingEvent::Liquidated(event),
+                LendingEvent::Unknown {
+                    discriminator: [1; DISCRIMINATOR_LEN],
+                    payload: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction() {
+        let program_id = Pubkey::new_unique();
+        let obligation = Pubkey::new_unique();
+        let reserve = Pubkey::new_unique();
+        let instruction = borrow_obligation_liquidity(
+            program_id,
+            10,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            reserve,
+            Pubkey::new_unique(),
+            obligation,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            None,
+        );
+        let accounts: Vec<Pubkey> = instruction.accounts.iter().map(|a| a.pubkey).collect();
+
+        assert_eq!(
+            parse_instruction(&program_id, &program_id, &accounts, &instruction.data),
+            Some(LendingEvent::Borrowed {
+                obligation,
+                reserve,
+                liquidity_amount: 10,
+            })
+        );
+        assert_eq!(
+            parse_instruction(
+                &program_id,
+                &Pubkey::new_unique(),
+                &accounts,
+                &instruction.data
+            ),
+            None
+        );
+        // truncated accounts and unknown instructions are skipped
+        assert_eq!(
+            parse_instruction(&program_id, &program_id, &accounts[..2], &instruction.data),
+            None
+        );
+        assert_eq!(
+            parse_instruction(&program_id, &program_id, &accounts, &[255]),
+            None
+        );
+    }
+}
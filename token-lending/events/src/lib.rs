@@ -0,0 +1,147 @@
+#![deny(missing_docs)]
+
+//! Structured event logs for Solend programs.
+//!
+//! Every event is logged with `sol_log_data` as a single record:
+//!
+//! | bytes | content |
+//! |---|---|
+//! | 8 | event discriminator |
+//! | 4 | payload length, little endian |
+//! | length | payload, the event's bytes |
+//!
+//! Indexers read records from the `Program data:` log lines and dispatch on the discriminator.
+
+use bytemuck::{bytes_of, Pod};
+use solana_program::{hash::hashv, log::sol_log_data};
+use std::{convert::TryInto, mem::size_of};
+
+/// Length of the discriminator at the start of every record
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// Length of the record header: discriminator and payload length
+pub const EVENT_HEADER_LEN: usize = DISCRIMINATOR_LEN + 4;
+
+/// An event that can be logged with [emit_event](macro.emit_event.html)
+pub trait Event: Pod {
+    /// Identifies the event type, see [event_discriminator](fn.event_discriminator.html)
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN];
+}
+
+/// Discriminator for an event named `name`: the first 8 bytes of `sha256("event:" + name)`
+pub fn event_discriminator(name: &str) -> [u8; DISCRIMINATOR_LEN] {
+    hashv(&[b"event:", name.as_bytes()]).to_bytes()[..DISCRIMINATOR_LEN]
+        .try_into()
+        .unwrap()
+}
+
+/// Serialize an event into a record
+pub fn encode_event<E: Event>(event: &E) -> Vec<u8> {
+    let payload = bytes_of(event);
+    let mut record = Vec::with_capacity(EVENT_HEADER_LEN + payload.len());
+    record.extend_from_slice(&E::DISCRIMINATOR);
+    record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    record.extend_from_slice(payload);
+    record
+}
+
+/// Log an event as a record. Prefer [emit_event](macro.emit_event.html).
+pub fn emit<E: Event>(event: &E) {
+    sol_log_data(&[&encode_event(event)]);
+}
+
+/// Log an event, e.g. `emit_event!(ObligationLiquidated { .. })`
+#[macro_export]
+macro_rules! emit_event {
+    ($event:expr) => {
+        $crate::emit(&$event)
+    };
+}
+
+/// A record split into its discriminator and payload
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventRecord<'a> {
+    /// Event discriminator
+    pub discriminator: [u8; DISCRIMINATOR_LEN],
+    /// Event payload
+    pub payload: &'a [u8],
+}
+
+/// Split a record, checking that its length prefix matches the payload
+pub fn parse_event_record(record: &[u8]) -> Option<EventRecord<'_>> {
+    if record.len() < EVENT_HEADER_LEN {
+        return None;
+    }
+    let (header, payload) = record.split_at(EVENT_HEADER_LEN);
+    let length = u32::from_le_bytes(header[DISCRIMINATOR_LEN..].try_into().unwrap());
+    if payload.len() != length as usize {
+        return None;
+    }
+    Some(EventRecord {
+        discriminator: header[..DISCRIMINATOR_LEN].try_into().unwrap(),
+        payload,
+    })
+}
+
+/// Decode a record as `E`, or `None` if it holds another event
+pub fn decode_event<E: Event>(record: &[u8]) -> Option<E> {
+    let record = parse_event_record(record)?;
+    if record.discriminator != E::DISCRIMINATOR || record.payload.len() != size_of::<E>() {
+        return None;
+    }
+    Some(bytemuck::pod_read_unaligned(record.payload))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Deposited {
+        amount: u64,
+        slot: u64,
+    }
+
+    unsafe impl Zeroable for Deposited {}
+    unsafe impl Pod for Deposited {}
+
+    impl Event for Deposited {
+        const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [1, 2, 3, 4, 5, 6, 7, 8];
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        let event = Deposited {
+            amount: 100,
+            slot: 7,
+        };
+        let record = encode_event(&event);
+        assert_eq!(record.len(), EVENT_HEADER_LEN + size_of::<Deposited>());
+        assert_eq!(&record[..DISCRIMINATOR_LEN], &Deposited::DISCRIMINATOR);
+        assert_eq!(decode_event::<Deposited>(&record), Some(event));
+
+        // truncated records and other events aren't decoded
+        assert_eq!(decode_event::<Deposited>(&record[..record.len() - 1]), None);
+        let mut other = record.clone();
+        other[0] = 0;
+        assert_eq!(decode_event::<Deposited>(&other), None);
+        assert_eq!(parse_event_record(&record[..EVENT_HEADER_LEN - 1]), None);
+
+        // emitting works off chain too
+        emit_event!(event);
+    }
+
+    #[test]
+    fn test_event_discriminator() {
+        assert_eq!(
+            event_discriminator("Deposited"),
+            event_discriminator("Deposited")
+        );
+        assert_ne!(
+            event_discriminator("Deposited"),
+            event_discriminator("Withdrawn")
+        );
+    }
+}
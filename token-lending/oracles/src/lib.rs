@@ -0,0 +1,71 @@
+#![deny(missing_docs)]
+
+//! Oracle price backends for the Solend lending program.
+//!
+//! Each backend implements [OraclePrice](trait.OraclePrice.html) for the accounts of one oracle.
+//! A reserve has a Pyth price account and an optional Switchboard feed;
+//! [get_price](fn.get_price.html) reads the Pyth price and falls back to Switchboard. A new oracle
+//! is a new backend plus a branch in `get_price`, and a check in reserve config validation.
+//!
+//! [PythPull](struct.PythPull.html) and [StakePoolRate](struct.StakePoolRate.html) aren't
+//! backends: a pull price update has to be checked against the feed id expected by the caller,
+//! and a stake pool only knows its rate in SOL, which has to be combined with a SOL price.
+//! Reserves have nowhere to store either, so the lending program doesn't read them.
+
+mod pyth;
+mod stake_pool;
+mod switchboard;
+
+pub use pyth::{pyth_receiver, PythPull, PythPush};
+pub use stake_pool::{spl_stake_pool, StakePoolRate};
+pub use switchboard::Switchboard;
+
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, sysvar::clock::Clock,
+};
+use solend_sdk::{error::LendingError, math::Decimal};
+
+/// Prices are rejected once this many slots have passed since they were published
+pub const STALE_AFTER_SLOTS_ELAPSED: u64 = 240; // roughly 2 min
+
+/// Market price and, when the oracle provides one, a smoothed price such as an EMA or TWAP
+pub type OraclePrices = (Decimal, Option<Decimal>);
+
+/// A source of prices read from a single oracle account
+pub trait OraclePrice {
+    /// Read the current price from `price_info`, failing if it's stale or unusable
+    fn get_price(price_info: &AccountInfo, clock: &Clock) -> Result<OraclePrices, ProgramError>;
+}
+
+/// get_price tries to load the price from pyth, and if it fails, uses switchboard.
+/// The first element in the returned tuple is the market price, and the second is the optional
+/// smoothed price (eg ema, twap).
+pub fn get_price(
+    switchboard_feed_info: Option<&AccountInfo>,
+    pyth_price_info: &AccountInfo,
+    clock: &Clock,
+) -> Result<OraclePrices, ProgramError> {
+    if let Ok(prices) = PythPush::get_price(pyth_price_info, clock) {
+        return Ok(prices);
+    }
+
+    // if switchboard was not passed in don't try to grab the price
+    if let Some(switchboard_feed_info) = switchboard_feed_info {
+        return Switchboard::get_price(switchboard_feed_info, clock);
+    }
+
+    Err(LendingError::InvalidOracleConfig.into())
+}
+
+/// Fails once `STALE_AFTER_SLOTS_ELAPSED` slots have passed since `published_slot`
+fn check_staleness(clock: &Clock, published_slot: u64, oracle: &str) -> Result<(), ProgramError> {
+    let slots_elapsed = clock
+        .slot
+        .checked_sub(published_slot)
+        .ok_or(LendingError::MathOverflow)?;
+    if slots_elapsed >= STALE_AFTER_SLOTS_ELAPSED {
+        solana_program::msg!("{} oracle price is stale", oracle);
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+    Ok(())
+}
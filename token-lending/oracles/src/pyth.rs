@@ -0,0 +1,232 @@
+//! Pyth push price accounts and Pyth pull `PriceUpdateV2` accounts
+
+use crate::{check_staleness, OraclePrice, OraclePrices};
+use pyth_sdk_solana::Price;
+use solana_program::{
+    account_info::AccountInfo, msg, program_error::ProgramError, sysvar::clock::Clock,
+};
+use solend_sdk::{
+    error::LendingError,
+    oracles::{get_pyth_price, pyth_price_to_decimal},
+};
+use std::convert::TryInto;
+
+/// Pyth Solana receiver, the program owning pull price update accounts
+pub mod pyth_receiver {
+    solana_program::declare_id!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+}
+
+/// Pyth push oracle price accounts. They are owned by the oracle program set on the lending
+/// market, which is checked when a reserve is configured.
+pub struct PythPush;
+
+impl OraclePrice for PythPush {
+    fn get_price(price_info: &AccountInfo, clock: &Clock) -> Result<OraclePrices, ProgramError> {
+        get_pyth_price(price_info, clock).map(|(price, ema_price)| (price, Some(ema_price)))
+    }
+}
+
+/// Pyth pull oracle `PriceUpdateV2` accounts, posted by the Pyth receiver. Only fully verified
+/// updates for the expected feed are accepted: anyone can post an update, so the account alone
+/// doesn't say which asset it prices.
+pub struct PythPull;
+
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+const VERIFICATION_LEVEL_OFFSET: usize = 8 + 32;
+const PRICE_MESSAGE_OFFSET: usize = VERIFICATION_LEVEL_OFFSET + 1;
+const PRICE_UPDATE_V2_FULL_LEN: usize = PRICE_MESSAGE_OFFSET + 84 + 8;
+
+/// Like Pyth push prices, confidence may be at most a tenth of the price
+const PYTH_CONFIDENCE_RATIO: u64 = 10;
+
+/// The price message of a fully verified `PriceUpdateV2`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PriceUpdate {
+    feed_id: [u8; 32],
+    price: i64,
+    conf: u64,
+    exponent: i32,
+    publish_time: i64,
+    ema_price: i64,
+    ema_conf: u64,
+    posted_slot: u64,
+}
+
+impl PriceUpdate {
+    fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < PRICE_UPDATE_V2_FULL_LEN || data[..8] != PRICE_UPDATE_V2_DISCRIMINATOR {
+            msg!("Account is not a Pyth price update");
+            return Err(LendingError::InvalidOracleConfig.into());
+        }
+        if data[VERIFICATION_LEVEL_OFFSET] != VERIFICATION_LEVEL_FULL {
+            msg!("Pyth price update is not fully verified");
+            return Err(LendingError::InvalidOracleConfig.into());
+        }
+        let feed_id = data[PRICE_MESSAGE_OFFSET..PRICE_MESSAGE_OFFSET + 32]
+            .try_into()
+            .unwrap();
+        let message = &data[PRICE_MESSAGE_OFFSET + 32..];
+        let field = |offset: usize| -> [u8; 8] { message[offset..offset + 8].try_into().unwrap() };
+        Ok(Self {
+            feed_id,
+            price: i64::from_le_bytes(field(0)),
+            conf: u64::from_le_bytes(field(8)),
+            exponent: i32::from_le_bytes(message[16..20].try_into().unwrap()),
+            publish_time: i64::from_le_bytes(field(20)),
+            // skip prev_publish_time
+            ema_price: i64::from_le_bytes(field(36)),
+            ema_conf: u64::from_le_bytes(field(44)),
+            posted_slot: u64::from_le_bytes(field(52)),
+        })
+    }
+}
+
+impl PythPull {
+    /// Read the current price for the feed `feed_id` from `price_info`, failing if the update is
+    /// for another feed, stale or unusable
+    pub fn get_price(
+        price_info: &AccountInfo,
+        feed_id: &[u8; 32],
+        clock: &Clock,
+    ) -> Result<OraclePrices, ProgramError> {
+        if *price_info.owner != pyth_receiver::id() {
+            msg!("Pyth price update is not owned by the Pyth receiver");
+            return Err(LendingError::InvalidOracleConfig.into());
+        }
+        let update = PriceUpdate::unpack(&price_info.try_borrow_data()?)?;
+        if update.feed_id != *feed_id {
+            msg!("Pyth price update is for another feed");
+            return Err(LendingError::InvalidOracleConfig.into());
+        }
+        check_staleness(clock, update.posted_slot, "Pyth")?;
+
+        let price: u64 = update.price.try_into().map_err(|_| {
+            msg!("Oracle price cannot be negative");
+            LendingError::InvalidOracleConfig
+        })?;
+        if update.conf.saturating_mul(PYTH_CONFIDENCE_RATIO) > price {
+            msg!(
+                "Oracle price confidence is too wide. price: {}, conf: {}",
+                price,
+                update.conf,
+            );
+            return Err(LendingError::InvalidOracleConfig.into());
+        }
+
+        let market_price = pyth_price_to_decimal(&Price {
+            price: update.price,
+            conf: update.conf,
+            expo: update.exponent,
+            publish_time: update.publish_time,
+        })?;
+        let ema_price = pyth_price_to_decimal(&Price {
+            price: update.ema_price,
+            conf: update.ema_conf,
+            expo: update.exponent,
+            publish_time: update.publish_time,
+        })?;
+        Ok((market_price, Some(ema_price)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_program::hash::hashv;
+    use solana_program::pubkey::Pubkey;
+    use solend_sdk::math::{Decimal, TryDiv};
+
+    const FEED_ID: [u8; 32] = [9; 32];
+
+    fn price_update(verification_level: u8, price: i64, conf: u64, posted_slot: u64) -> Vec<u8> {
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[7; 32]); // write authority
+        data.push(verification_level);
+        data.extend_from_slice(&FEED_ID);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&conf.to_le_bytes());
+        data.extend_from_slice(&(-2i32).to_le_bytes());
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.extend_from_slice(&1_699_999_999i64.to_le_bytes());
+        data.extend_from_slice(&(price / 2).to_le_bytes());
+        data.extend_from_slice(&conf.to_le_bytes());
+        data.extend_from_slice(&posted_slot.to_le_bytes());
+        data
+    }
+
+    fn get_price(
+        mut data: Vec<u8>,
+        owner: Pubkey,
+        slot: u64,
+    ) -> Result<OraclePrices, ProgramError> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        PythPull::get_price(
+            &account_info,
+            &FEED_ID,
+            &Clock {
+                slot,
+                ..Clock::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_discriminator() {
+        assert_eq!(
+            hashv(&[b"account:PriceUpdateV2"]).to_bytes()[..8],
+            PRICE_UPDATE_V2_DISCRIMINATOR
+        );
+    }
+
+    #[test]
+    fn test_pyth_pull_price() {
+        let receiver = pyth_receiver::id();
+        assert_eq!(
+            get_price(price_update(1, 12_345, 10, 100), receiver, 300),
+            Ok((
+                Decimal::from(12_345u64).try_div(100u64).unwrap(),
+                Some(Decimal::from(6_172u64).try_div(100u64).unwrap())
+            ))
+        );
+
+        let invalid = Err(LendingError::InvalidOracleConfig.into());
+        // stale
+        assert_eq!(
+            get_price(price_update(1, 12_345, 10, 100), receiver, 340),
+            invalid
+        );
+        // partially verified
+        assert_eq!(
+            get_price(price_update(0, 12_345, 10, 100), receiver, 300),
+            invalid
+        );
+        // negative, too wide a confidence, wrong owner, another feed
+        assert_eq!(
+            get_price(price_update(1, -12_345, 10, 100), receiver, 300),
+            invalid
+        );
+        assert_eq!(
+            get_price(price_update(1, 12_345, 2_000, 100), receiver, 300),
+            invalid
+        );
+        assert_eq!(
+            get_price(price_update(1, 12_345, 10, 100), Pubkey::new_unique(), 300),
+            invalid
+        );
+        let mut data = price_update(1, 12_345, 10, 100);
+        data[PRICE_MESSAGE_OFFSET] = 8;
+        assert_eq!(get_price(data, receiver, 300), invalid);
+    }
+}
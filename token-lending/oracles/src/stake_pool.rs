@@ -0,0 +1,167 @@
+//! SPL stake pool exchange rates, for pricing liquid staking tokens in SOL
+
+use crate::OraclePrices;
+use solana_program::{
+    account_info::AccountInfo, msg, program_error::ProgramError, sysvar::clock::Clock,
+};
+use solend_sdk::{
+    error::LendingError,
+    math::{Decimal, TryDiv, TryMul},
+};
+use std::convert::TryInto;
+
+/// SPL stake pool program
+pub mod spl_stake_pool {
+    solana_program::declare_id!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+}
+
+/// Stake pool accounts, read as the SOL each pool token is worth: total lamports over pool token
+/// supply. The rate is only current once the pool has been updated this epoch.
+pub struct StakePoolRate;
+
+const ACCOUNT_TYPE_STAKE_POOL: u8 = 1;
+// account type, manager, staker, stake deposit authority, withdraw bump seed, validator list,
+// reserve stake, pool mint, manager fee account and token program come first
+const TOTAL_LAMPORTS_OFFSET: usize = 1 + 32 * 3 + 1 + 32 * 5;
+const POOL_TOKEN_SUPPLY_OFFSET: usize = TOTAL_LAMPORTS_OFFSET + 8;
+const LAST_UPDATE_EPOCH_OFFSET: usize = POOL_TOKEN_SUPPLY_OFFSET + 8;
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+impl StakePoolRate {
+    /// SOL per pool token of the stake pool in `stake_pool_info`
+    pub fn get_rate(stake_pool_info: &AccountInfo, clock: &Clock) -> Result<Decimal, ProgramError> {
+        let data = stake_pool_info.try_borrow_data()?;
+        if *stake_pool_info.owner != spl_stake_pool::id()
+            || data.len() < LAST_UPDATE_EPOCH_OFFSET + 8
+            || data[0] != ACCOUNT_TYPE_STAKE_POOL
+        {
+            msg!("Account is not a stake pool");
+            return Err(LendingError::InvalidOracleConfig.into());
+        }
+        if read_u64(&data, LAST_UPDATE_EPOCH_OFFSET) != clock.epoch {
+            msg!("Stake pool hasn't been updated this epoch");
+            return Err(LendingError::InvalidOracleConfig.into());
+        }
+
+        let pool_token_supply = read_u64(&data, POOL_TOKEN_SUPPLY_OFFSET);
+        if pool_token_supply == 0 {
+            msg!("Stake pool has no pool tokens");
+            return Err(LendingError::InvalidOracleConfig.into());
+        }
+        // pool tokens have as many decimals as SOL, so the ratio of base units is SOL per token
+        Decimal::from(read_u64(&data, TOTAL_LAMPORTS_OFFSET)).try_div(pool_token_supply)
+    }
+
+    /// Price of a pool token in the quote currency of `sol_prices`, the prices of SOL
+    pub fn get_price(
+        stake_pool_info: &AccountInfo,
+        sol_prices: OraclePrices,
+        clock: &Clock,
+    ) -> Result<OraclePrices, ProgramError> {
+        let rate = Self::get_rate(stake_pool_info, clock)?;
+        let (sol_price, sol_smoothed_price) = sol_prices;
+        Ok((
+            sol_price.try_mul(rate)?,
+            sol_smoothed_price
+                .map(|price| price.try_mul(rate))
+                .transpose()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_program::pubkey::Pubkey;
+
+    fn stake_pool(total_lamports: u64, pool_token_supply: u64, last_update_epoch: u64) -> Vec<u8> {
+        let mut data = vec![0; LAST_UPDATE_EPOCH_OFFSET + 8 + 100];
+        data[0] = ACCOUNT_TYPE_STAKE_POOL;
+        data[TOTAL_LAMPORTS_OFFSET..POOL_TOKEN_SUPPLY_OFFSET]
+            .copy_from_slice(&total_lamports.to_le_bytes());
+        data[POOL_TOKEN_SUPPLY_OFFSET..LAST_UPDATE_EPOCH_OFFSET]
+            .copy_from_slice(&pool_token_supply.to_le_bytes());
+        data[LAST_UPDATE_EPOCH_OFFSET..LAST_UPDATE_EPOCH_OFFSET + 8]
+            .copy_from_slice(&last_update_epoch.to_le_bytes());
+        data
+    }
+
+    fn with_stake_pool<T>(
+        mut data: Vec<u8>,
+        owner: Pubkey,
+        epoch: u64,
+        f: impl FnOnce(&AccountInfo, &Clock) -> T,
+    ) -> T {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        f(
+            &account_info,
+            &Clock {
+                epoch,
+                ..Clock::default()
+            },
+        )
+    }
+
+    fn get_rate(data: Vec<u8>, owner: Pubkey, epoch: u64) -> Result<Decimal, ProgramError> {
+        with_stake_pool(data, owner, epoch, StakePoolRate::get_rate)
+    }
+
+    #[test]
+    fn test_stake_pool_rate() {
+        let program = spl_stake_pool::id();
+        assert_eq!(
+            get_rate(stake_pool(1_100, 1_000, 5), program, 5),
+            Ok(Decimal::from(11u64).try_div(10u64).unwrap())
+        );
+
+        let invalid = Err(LendingError::InvalidOracleConfig.into());
+        // not updated this epoch, empty, wrong owner
+        assert_eq!(get_rate(stake_pool(1_100, 1_000, 4), program, 5), invalid);
+        assert_eq!(get_rate(stake_pool(0, 0, 5), program, 5), invalid);
+        assert_eq!(
+            get_rate(stake_pool(1_100, 1_000, 5), Pubkey::new_unique(), 5),
+            invalid
+        );
+    }
+
+    #[test]
+    fn test_stake_pool_price() {
+        // 1.1 SOL per pool token, SOL at 20 with an EMA of 18
+        let sol_prices = (Decimal::from(20u64), Some(Decimal::from(18u64)));
+        let prices = with_stake_pool(
+            stake_pool(1_100, 1_000, 5),
+            spl_stake_pool::id(),
+            5,
+            |info, clock| StakePoolRate::get_price(info, sol_prices, clock),
+        );
+        assert_eq!(
+            prices,
+            Ok((
+                Decimal::from(22u64),
+                Some(Decimal::from(198u64).try_div(10u64).unwrap())
+            ))
+        );
+
+        let prices = with_stake_pool(
+            stake_pool(1_100, 1_000, 5),
+            spl_stake_pool::id(),
+            5,
+            |info, clock| StakePoolRate::get_price(info, (Decimal::from(20u64), None), clock),
+        );
+        assert_eq!(prices, Ok((Decimal::from(22u64), None)));
+    }
+}
@@ -0,0 +1,91 @@
+//! Switchboard v1 and v2 aggregator feeds
+
+use crate::{check_staleness, OraclePrice, OraclePrices};
+use solana_program::{
+    account_info::AccountInfo, msg, program_error::ProgramError, sysvar::clock::Clock,
+};
+use solend_sdk::{
+    error::LendingError,
+    math::{Decimal, TryDiv},
+    switchboard_v2_devnet, switchboard_v2_mainnet, NULL_PUBKEY,
+};
+use switchboard_program::{
+    get_aggregator, get_aggregator_result, AggregatorState, RoundResult, SwitchboardAccountType,
+};
+use switchboard_v2::AggregatorAccountData;
+
+/// Switchboard aggregator feeds. Feeds owned by a Switchboard v2 program are read as v2, any
+/// other feed as v1, since v1 feeds are owned by the Switchboard program set on the lending market
+/// and checked when a reserve is configured. Switchboard has no smoothed price.
+pub struct Switchboard;
+
+impl OraclePrice for Switchboard {
+    fn get_price(price_info: &AccountInfo, clock: &Clock) -> Result<OraclePrices, ProgramError> {
+        if *price_info.key == NULL_PUBKEY {
+            return Err(LendingError::NullOracleConfig.into());
+        }
+        let price = if price_info.owner == &switchboard_v2_mainnet::id()
+            || price_info.owner == &switchboard_v2_devnet::id()
+        {
+            get_switchboard_price_v2(price_info, clock)?
+        } else {
+            get_switchboard_price_v1(price_info, clock)?
+        };
+        // TODO: add support for switchboard smoothed prices. Probably need to add a new
+        // switchboard account per reserve.
+        Ok((price, None))
+    }
+}
+
+fn get_switchboard_price_v1(
+    switchboard_feed_info: &AccountInfo,
+    clock: &Clock,
+) -> Result<Decimal, ProgramError> {
+    let account_buf = switchboard_feed_info.try_borrow_data()?;
+    // first byte type discriminator
+    if account_buf[0] != SwitchboardAccountType::TYPE_AGGREGATOR as u8 {
+        msg!("switchboard address not of type aggregator");
+        return Err(LendingError::InvalidAccountInput.into());
+    }
+
+    let aggregator: AggregatorState = get_aggregator(switchboard_feed_info)?;
+    // if aggregator.version != 1 {
+    //     msg!("switchboard version incorrect");
+    //     return Err(LendingError::InvalidAccountInput.into());
+    // }
+    let round_result: RoundResult = get_aggregator_result(&aggregator)?;
+
+    check_staleness(clock, round_result.round_open_slot.unwrap(), "Switchboard")?;
+
+    let price_float = round_result.result.unwrap_or(0.0);
+
+    // we just do this so we can parse coins with low usd value
+    // it might be better to just extract the mantissa and exponent from the float directly
+    let price_quotient = 10u64.pow(9);
+    let price = ((price_quotient as f64) * price_float) as u128;
+
+    Decimal::from(price).try_div(price_quotient)
+}
+
+fn get_switchboard_price_v2(
+    switchboard_feed_info: &AccountInfo,
+    clock: &Clock,
+) -> Result<Decimal, ProgramError> {
+    let data = &switchboard_feed_info.try_borrow_data()?;
+    let feed = AggregatorAccountData::new_from_bytes(data)?;
+
+    check_staleness(
+        clock,
+        feed.latest_confirmed_round.round_open_slot,
+        "Switchboard",
+    )?;
+
+    let price_switchboard_desc = feed.get_result()?;
+    if price_switchboard_desc.mantissa < 0 {
+        msg!("Switchboard oracle price is negative which is not allowed");
+        return Err(LendingError::InvalidOracleConfig.into());
+    }
+    let price = Decimal::from(price_switchboard_desc.mantissa as u128);
+    let exp = Decimal::from((10u128).checked_pow(price_switchboard_desc.scale).unwrap());
+    price.try_div(exp)
+}
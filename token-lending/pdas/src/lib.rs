@@ -0,0 +1,98 @@
+#![deny(missing_docs)]
+
+//! Derivations of every program derived address and seeded account used by Solend programs, so
+//! clients, wallets and indexers share one source of seeds with the programs.
+//!
+//! Reserves are plain keypair accounts, so they have no derivation.
+
+use solana_program::pubkey::{Pubkey, PubkeyError};
+
+/// Seed of the lending market metadata account, after the lending market address
+pub const LENDING_MARKET_METADATA_SEED: &[u8] = b"MetaData";
+
+/// Length of the obligation seed, the longest seed `Pubkey::create_with_seed` accepts
+pub const OBLIGATION_SEED_LEN: usize = 32;
+
+/// Lending market authority, which owns the token accounts of every reserve in `lending_market`.
+/// Seeds: `[lending_market]`.
+pub fn find_lending_market_authority_address(
+    lending_market: &Pubkey,
+    lending_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[lending_market.as_ref()], lending_program_id)
+}
+
+/// Metadata account of `lending_market`. Seeds: `[lending_market, "MetaData"]`.
+pub fn find_lending_market_metadata_address(
+    lending_market: &Pubkey,
+    lending_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[lending_market.as_ref(), LENDING_MARKET_METADATA_SEED],
+        lending_program_id,
+    )
+}
+
+/// Seed of the obligation `owner` opens in `lending_market`: the first 32 characters of the
+/// lending market address. Clients create obligations with
+/// `system_instruction::create_account_with_seed` from the owner and this seed, so an owner can
+/// find their obligation in a market without storing its address.
+pub fn obligation_seed(lending_market: &Pubkey) -> String {
+    lending_market.to_string()[..OBLIGATION_SEED_LEN].to_string()
+}
+
+/// Address of the obligation `owner` opens in `lending_market`, see
+/// [obligation_seed](fn.obligation_seed.html)
+pub fn obligation_address(
+    owner: &Pubkey,
+    lending_market: &Pubkey,
+    lending_program_id: &Pubkey,
+) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_with_seed(owner, &obligation_seed(lending_market), lending_program_id)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_lending_market_addresses() {
+        let program_id = Pubkey::new_unique();
+        let lending_market = Pubkey::new_unique();
+
+        let (authority, bump_seed) =
+            find_lending_market_authority_address(&lending_market, &program_id);
+        assert_eq!(
+            Pubkey::create_program_address(&[lending_market.as_ref(), &[bump_seed]], &program_id),
+            Ok(authority)
+        );
+
+        let (metadata, bump_seed) =
+            find_lending_market_metadata_address(&lending_market, &program_id);
+        assert_eq!(
+            Pubkey::create_program_address(
+                &[lending_market.as_ref(), b"MetaData", &[bump_seed]],
+                &program_id
+            ),
+            Ok(metadata)
+        );
+    }
+
+    #[test]
+    fn test_obligation_address() {
+        let lending_market =
+            Pubkey::from_str("4UpD2fh7xH3VP9QQaXtsS1YY3bxzWhtfpks7FatyKvdY").unwrap();
+        assert_eq!(
+            obligation_seed(&lending_market),
+            "4UpD2fh7xH3VP9QQaXtsS1YY3bxzWhtf"
+        );
+
+        let owner = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        assert_eq!(
+            obligation_address(&owner, &lending_market, &program_id),
+            Pubkey::create_with_seed(&owner, "4UpD2fh7xH3VP9QQaXtsS1YY3bxzWhtf", &program_id)
+        );
+    }
+}
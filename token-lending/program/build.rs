@@ -0,0 +1,20 @@
+//! Sets `SOURCE_REVISION` to the git commit being built, for the program's security.txt. CI
+//! builds take it from `GITHUB_SHA`; local builds ask git, and builds outside a checkout leave it
+//! empty.
+
+use std::{env, process::Command};
+
+fn main() {
+    let revision = env::var("GITHUB_SHA")
+        .ok()
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "HEAD"])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+        })
+        .unwrap_or_default();
+    println!("cargo:rustc-env=SOURCE_REVISION={}", revision.trim());
+}
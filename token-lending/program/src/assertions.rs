@@ -0,0 +1,86 @@
+//! Account checks shared by the processor. Each check logs which account failed and maps to a
+//! single `LendingError`, so every instruction reports the same error for the same mistake.
+
+use crate::error::LendingError;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    rent::Rent,
+};
+use std::fmt::Display;
+
+/// Fails with `InvalidAccountOwner` unless `account_info` is owned by `owner`
+pub(crate) fn assert_owned_by(
+    account_info: &AccountInfo,
+    owner: &Pubkey,
+    name: impl Display,
+) -> ProgramResult {
+    if account_info.owner != owner {
+        msg!(
+            "{} provided is not owned by the expected program {} != {}",
+            name,
+            account_info.owner,
+            owner
+        );
+        return Err(LendingError::InvalidAccountOwner.into());
+    }
+    Ok(())
+}
+
+/// Fails with `InvalidTokenProgram` unless `program_info` is the program `expected`. Token
+/// programs are the only programs instructions take as accounts.
+pub(crate) fn assert_program_id(
+    program_info: &AccountInfo,
+    expected: &Pubkey,
+    name: impl Display,
+) -> ProgramResult {
+    if program_info.key != expected {
+        msg!(
+            "{} provided does not match the expected program {} != {}",
+            name,
+            program_info.key,
+            expected
+        );
+        return Err(LendingError::InvalidTokenProgram.into());
+    }
+    Ok(())
+}
+
+/// Fails with `InvalidSigner` unless `account_info` signed the transaction
+pub(crate) fn assert_signer(account_info: &AccountInfo, name: impl Display) -> ProgramResult {
+    if !account_info.is_signer {
+        msg!("{} provided must be a signer", name);
+        return Err(LendingError::InvalidSigner.into());
+    }
+    Ok(())
+}
+
+/// Fails with `NotRentExempt` unless `account_info` holds the rent-exempt minimum for its size
+pub(crate) fn assert_rent_exempt(rent: &Rent, account_info: &AccountInfo) -> ProgramResult {
+    if !rent.is_exempt(account_info.lamports(), account_info.data_len()) {
+        msg!(
+            "Rent exempt balance insufficient got {} expected {}",
+            &account_info.lamports().to_string(),
+            &rent.minimum_balance(account_info.data_len()).to_string(),
+        );
+        Err(LendingError::NotRentExempt.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Unpacks `account_info`, failing with `AlreadyInitialized` if it's initialized
+pub(crate) fn assert_uninitialized<T: Pack + IsInitialized>(
+    account_info: &AccountInfo,
+) -> Result<T, ProgramError> {
+    let account: T = T::unpack_unchecked(&account_info.data.borrow())?;
+    if account.is_initialized() {
+        Err(LendingError::AlreadyInitialized.into())
+    } else {
+        Ok(account)
+    }
+}
@@ -0,0 +1,39 @@
+//! Instruction introspection guard for instructions that must not be invoked through CPI.
+//!
+//! The instructions sysvar only records top-level instructions, so an instruction running as a
+//! CPI sees the top-level instruction that led to it at the current index. An instruction is
+//! top-level when that instruction is for this program and the stack height is the transaction
+//! level; anything else is a CPI.
+
+use crate::error::LendingError;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+/// Fails with `err` unless the running instruction is top-level. Returns the index of the current
+/// top-level instruction.
+pub(crate) fn assert_not_cpi(
+    program_id: &Pubkey,
+    instructions_sysvar_info: &AccountInfo,
+    err: LendingError,
+) -> Result<usize, ProgramError> {
+    let current_index = load_current_index_checked(instructions_sysvar_info)? as usize;
+    let current_ixn = load_instruction_at_checked(current_index, instructions_sysvar_info)?;
+    let stack_height = get_stack_height();
+
+    if current_ixn.program_id == *program_id && stack_height <= TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(current_index);
+    }
+
+    msg!(
+        "Instruction was invoked via CPI from {} at stack height {}",
+        current_ixn.program_id,
+        stack_height
+    );
+    Err(err.into())
+}
@@ -7,6 +7,19 @@ use solana_program::{
     account_info::AccountInfo, entrypoint, entrypoint::ProgramResult,
     program_error::PrintProgramError, pubkey::Pubkey,
 };
+use solana_security_txt::security_txt;
+
+security_txt! {
+    name: "Solend",
+    project_url: "https://solend.fi",
+    contacts: "email:maintainers@solend.fi",
+    policy: "https://github.com/solendprotocol/solana-program-library/blob/master/SECURITY.md",
+    preferred_languages: "en",
+    source_code: "https://github.com/solendprotocol/solana-program-library",
+    source_release: concat!("v", env!("CARGO_PKG_VERSION")),
+    source_revision: env!("SOURCE_REVISION"),
+    auditors: "Kudelski Security"
+}
 
 entrypoint!(process_instruction);
 fn process_instruction(
@@ -2,9 +2,12 @@
 
 //! A lending program for the Solana blockchain.
 
+mod assertions;
+mod cpi_guard;
 pub mod entrypoint;
 pub mod processor;
-pub use solend_sdk::{error, instruction, math, oracles, state};
+pub mod profile;
+pub use solend_sdk::{error, events, instruction, math, oracles, state, version};
 
 // Export current sdk types for downstream users building with a different sdk version
 pub use solana_program;
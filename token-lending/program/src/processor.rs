@@ -2,15 +2,20 @@
 
 use crate::{
     self as solend_program,
+    assertions::{
+        assert_owned_by, assert_program_id, assert_rent_exempt, assert_signer, assert_uninitialized,
+    },
+    cpi_guard::assert_not_cpi,
     error::LendingError,
+    events::ObligationLiquidated,
     instruction::LendingInstruction,
-    math::{Decimal, Rate, TryAdd, TryDiv, TryMul, TrySub},
-    oracles::get_pyth_price,
+    math::{Decimal, Rate, TryAdd, TryMul, TrySub},
+    profile::cu_checkpoint,
     state::{
         validate_reserve_config, CalculateBorrowResult, CalculateLiquidationResult,
         CalculateRepayResult, InitLendingMarketParams, InitObligationParams, InitReserveParams,
         LendingMarket, NewReserveCollateralParams, NewReserveLiquidityParams, Obligation, Reserve,
-        ReserveCollateral, ReserveConfig, ReserveLiquidity,
+        ReserveCollateral, ReserveConfig, ReserveLiquidity, ReserveView,
     },
 };
 use bytemuck::bytes_of;
@@ -18,28 +23,34 @@ use pyth_sdk_solana::{self, state::ProductAccount};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
-    instruction::{get_stack_height, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT},
+    instruction::Instruction,
     msg,
-    program::{invoke, invoke_signed},
+    program::{invoke, invoke_signed, set_return_data},
     program_error::ProgramError,
-    program_pack::{IsInitialized, Pack},
+    program_pack::Pack,
     pubkey::Pubkey,
     system_instruction::create_account,
-    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    sysvar::instructions::load_instruction_at_checked,
     sysvar::{
         clock::{self, Clock},
         rent::Rent,
         Sysvar,
     },
 };
+use solend_events::emit_event;
+use solend_oracles::get_price;
 use solend_sdk::state::{LendingMarketMetadata, RateLimiter, RateLimiterConfig, ReserveType};
+use solend_sdk::version::{
+    ProgramVersion, FEATURE_FLASH_LOANS, FEATURE_FORGIVE_DEBT, FEATURE_ISOLATED_RESERVES,
+    FEATURE_LIQUIDATION_EVENTS, FEATURE_MARKET_METADATA, FEATURE_RATE_LIMITS,
+};
 use solend_sdk::{switchboard_v2_devnet, switchboard_v2_mainnet};
+use spl_forks_pdas::{
+    find_lending_market_authority_address, find_lending_market_metadata_address,
+    LENDING_MARKET_METADATA_SEED,
+};
 use spl_token::state::Mint;
 use std::{cmp::min, result::Result};
-use switchboard_program::{
-    get_aggregator, get_aggregator_result, AggregatorState, RoundResult, SwitchboardAccountType,
-};
-use switchboard_v2::AggregatorAccountData;
 
 /// solend market owner
 pub mod solend_market_owner {
@@ -53,6 +64,7 @@ pub fn process_instruction(
     input: &[u8],
 ) -> ProgramResult {
     let instruction = LendingInstruction::unpack(input)?;
+    cu_checkpoint!("process_instruction: unpack");
     match instruction {
         LendingInstruction::InitLendingMarket {
             owner,
@@ -192,6 +204,10 @@ pub fn process_instruction(
             let metadata = LendingMarketMetadata::new_from_bytes(input)?;
             process_update_market_metadata(program_id, metadata, accounts)
         }
+        LendingInstruction::GetVersion => {
+            msg!("Instruction: Get Version");
+            process_get_version()
+        }
     }
 }
 
@@ -210,13 +226,10 @@ fn process_init_lending_market(
 
     assert_rent_exempt(rent, lending_market_info)?;
     let mut lending_market = assert_uninitialized::<LendingMarket>(lending_market_info)?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
 
     lending_market.init(InitLendingMarketParams {
-        bump_seed: Pubkey::find_program_address(&[lending_market_info.key.as_ref()], program_id).1,
+        bump_seed: find_lending_market_authority_address(lending_market_info.key, program_id).1,
         owner,
         quote_currency,
         token_program_id: *token_program_id.key,
@@ -242,18 +255,12 @@ fn process_set_lending_market_owner_and_config(
     let lending_market_owner_info = next_account_info(account_info_iter)?;
 
     let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
     if &lending_market.owner != lending_market_owner_info.key {
         msg!("Lending market owner does not match the lending market owner provided");
         return Err(LendingError::InvalidMarketOwner.into());
     }
-    if !lending_market_owner_info.is_signer {
-        msg!("Lending market owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    assert_signer(lending_market_owner_info, "Lending market owner")?;
 
     lending_market.owner = new_owner;
     lending_market.risk_authority = risk_authority;
@@ -308,14 +315,7 @@ fn process_init_reserve(
 
     assert_rent_exempt(rent, reserve_info)?;
     let mut reserve = assert_uninitialized::<Reserve>(reserve_info)?;
-    if reserve_info.owner != program_id {
-        msg!(
-            "Reserve provided is not owned by the lending program {} != {}",
-            &reserve_info.owner.to_string(),
-            &program_id.to_string(),
-        );
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(reserve_info, program_id, "Reserve")?;
 
     if reserve_liquidity_supply_info.key == source_liquidity_info.key {
         msg!("Reserve liquidity supply cannot be used as the source liquidity provided");
@@ -323,26 +323,17 @@ fn process_init_reserve(
     }
 
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!(
-            "Lending market provided is not owned by the lending program  {} != {}",
-            &lending_market_info.owner.to_string(),
-            &program_id.to_string(),
-        );
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
     if &lending_market.owner != lending_market_owner_info.key {
         msg!("Lending market owner does not match the lending market owner provided");
         return Err(LendingError::InvalidMarketOwner.into());
     }
-    if !lending_market_owner_info.is_signer {
-        msg!("Lending market owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    assert_signer(lending_market_owner_info, "Lending market owner")?;
     if *switchboard_feed_info.key == solend_program::NULL_PUBKEY
         && (*pyth_price_info.key == solend_program::NULL_PUBKEY
             || *pyth_product_info.key == solend_program::NULL_PUBKEY)
@@ -491,10 +482,7 @@ fn _refresh_reserve<'a>(
     clock: &Clock,
 ) -> ProgramResult {
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
-    if reserve_info.owner != program_id {
-        msg!("Reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(reserve_info, program_id, "Reserve")?;
     if &reserve.liquidity.pyth_oracle_pubkey != pyth_price_info.key {
         msg!("Reserve liquidity pyth oracle does not match the reserve liquidity pyth oracle provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -510,6 +498,7 @@ fn _refresh_reserve<'a>(
 
     let (market_price, smoothed_market_price) =
         get_price(switchboard_feed_info, pyth_price_info, clock)?;
+    cu_checkpoint!("refresh_reserve: get_price");
 
     reserve.liquidity.market_price = market_price;
 
@@ -536,14 +525,12 @@ fn _refresh_reserve_interest<'a>(
     clock: &Clock,
 ) -> ProgramResult {
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
-    if reserve_info.owner != program_id {
-        msg!("Reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(reserve_info, program_id, "Reserve")?;
 
     reserve.accrue_interest(clock.slot)?;
     reserve.last_update.update_slot(clock.slot);
     Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
+    cu_checkpoint!("refresh_reserve: accrue_interest");
 
     Ok(())
 }
@@ -608,19 +595,14 @@ fn _deposit_reserve_liquidity<'a>(
     token_program_id: &AccountInfo<'a>,
 ) -> Result<u64, ProgramError> {
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
-    if reserve_info.owner != program_id {
-        msg!("Reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(reserve_info, program_id, "Reserve")?;
     if &reserve.lending_market != lending_market_info.key {
         msg!("Reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -672,6 +654,7 @@ fn _deposit_reserve_liquidity<'a>(
     let collateral_amount = reserve.deposit_liquidity(liquidity_amount)?;
     reserve.last_update.mark_stale();
     Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
+    cu_checkpoint!("deposit_reserve_liquidity: deposit_liquidity");
 
     spl_token_transfer(TokenTransferParams {
         source: source_liquidity_info.clone(),
@@ -758,20 +741,15 @@ fn _redeem_reserve_collateral<'a>(
     check_rate_limits: bool,
 ) -> Result<u64, ProgramError> {
     let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
 
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
-    if reserve_info.owner != program_id {
-        msg!("Reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(reserve_info, program_id, "Reserve")?;
     if &reserve.lending_market != lending_market_info.key {
         msg!("Reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -836,6 +814,7 @@ fn _redeem_reserve_collateral<'a>(
     reserve.last_update.mark_stale();
     Reserve::pack(reserve, &mut reserve_info.data.borrow_mut())?;
     LendingMarket::pack(lending_market, &mut lending_market_info.data.borrow_mut())?;
+    cu_checkpoint!("redeem_reserve_collateral: redeem_collateral");
 
     spl_token_burn(TokenBurnParams {
         mint: reserve_collateral_mint_info.clone(),
@@ -873,25 +852,17 @@ fn process_init_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
 
     assert_rent_exempt(rent, obligation_info)?;
     let mut obligation = assert_uninitialized::<Obligation>(obligation_info)?;
-    if obligation_info.owner != program_id {
-        msg!("Obligation provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(obligation_info, program_id, "Obligation")?;
 
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
 
-    if !obligation_owner_info.is_signer {
-        msg!("Obligation owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    assert_signer(obligation_owner_info, "Obligation owner")?;
 
     obligation.init(InitObligationParams {
         current_slot: clock.slot,
@@ -914,10 +885,7 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
     }
 
     let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
-    if obligation_info.owner != program_id {
-        msg!("Obligation provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(obligation_info, program_id, "Obligation")?;
 
     let mut deposited_value = Decimal::zero();
     let mut borrowed_value = Decimal::zero();
@@ -928,13 +896,11 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
 
     for (index, collateral) in obligation.deposits.iter_mut().enumerate() {
         let deposit_reserve_info = next_account_info(account_info_iter)?;
-        if deposit_reserve_info.owner != program_id {
-            msg!(
-                "Deposit reserve provided for collateral {} is not owned by the lending program",
-                index
-            );
-            return Err(LendingError::InvalidAccountOwner.into());
-        }
+        assert_owned_by(
+            deposit_reserve_info,
+            program_id,
+            format_args!("Deposit reserve for collateral {}", index),
+        )?;
         if collateral.deposit_reserve != *deposit_reserve_info.key {
             msg!(
                 "Deposit reserve of collateral {} does not match the deposit reserve provided",
@@ -943,8 +909,9 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             return Err(LendingError::InvalidAccountInput.into());
         }
 
-        let deposit_reserve = Reserve::unpack(&deposit_reserve_info.data.borrow())?;
-        if deposit_reserve.last_update.is_stale(clock.slot)? {
+        let deposit_reserve_data = deposit_reserve_info.data.borrow();
+        let deposit_reserve = ReserveView::load(&deposit_reserve_data)?;
+        if deposit_reserve.last_update()?.is_stale(clock.slot)? {
             msg!(
                 "Deposit reserve provided for collateral {} is stale and must be refreshed in the current slot",
                 index
@@ -960,11 +927,11 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
         let market_value_lower_bound =
             deposit_reserve.market_value_lower_bound(liquidity_amount)?;
 
-        let loan_to_value_rate = Rate::from_percent(deposit_reserve.config.loan_to_value_ratio);
+        let loan_to_value_rate = deposit_reserve.loan_to_value_rate();
         let liquidation_threshold_rate =
-            Rate::from_percent(deposit_reserve.config.liquidation_threshold);
+            Rate::from_percent(deposit_reserve.liquidation_threshold());
         let max_liquidation_threshold_rate =
-            Rate::from_percent(deposit_reserve.config.max_liquidation_threshold);
+            Rate::from_percent(deposit_reserve.max_liquidation_threshold());
 
         collateral.market_value = market_value;
         deposited_value = deposited_value.try_add(market_value)?;
@@ -976,17 +943,16 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             .try_add(market_value.try_mul(max_liquidation_threshold_rate)?)?;
     }
 
+    cu_checkpoint!("refresh_obligation: deposits");
     let mut borrowing_isolated_asset = false;
     let mut max_borrow_weight = None;
     for (index, liquidity) in obligation.borrows.iter_mut().enumerate() {
         let borrow_reserve_info = next_account_info(account_info_iter)?;
-        if borrow_reserve_info.owner != program_id {
-            msg!(
-                "Borrow reserve provided for liquidity {} is not owned by the lending program",
-                index
-            );
-            return Err(LendingError::InvalidAccountOwner.into());
-        }
+        assert_owned_by(
+            borrow_reserve_info,
+            program_id,
+            format_args!("Borrow reserve for liquidity {}", index),
+        )?;
         if liquidity.borrow_reserve != *borrow_reserve_info.key {
             msg!(
                 "Borrow reserve of liquidity {} does not match the borrow reserve provided",
@@ -995,8 +961,9 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             return Err(LendingError::InvalidAccountInput.into());
         }
 
-        let borrow_reserve = Reserve::unpack(&borrow_reserve_info.data.borrow())?;
-        if borrow_reserve.last_update.is_stale(clock.slot)? {
+        let borrow_reserve_data = borrow_reserve_info.data.borrow();
+        let borrow_reserve = ReserveView::load(&borrow_reserve_data)?;
+        if borrow_reserve.last_update()?.is_stale(clock.slot)? {
             msg!(
                 "Borrow reserve provided for liquidity {} is stale and must be refreshed in the current slot",
                 index
@@ -1004,14 +971,14 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             return Err(LendingError::ReserveStale.into());
         }
 
-        if borrow_reserve.config.reserve_type == ReserveType::Isolated {
+        if borrow_reserve.reserve_type()? == ReserveType::Isolated {
             borrowing_isolated_asset = true;
         }
 
-        liquidity.accrue_interest(borrow_reserve.liquidity.cumulative_borrow_rate_wads)?;
+        liquidity.accrue_interest(borrow_reserve.cumulative_borrow_rate_wads())?;
 
         let borrow_weight_and_pubkey = (
-            borrow_reserve.config.added_borrow_weight_bps,
+            borrow_reserve.added_borrow_weight_bps(),
             borrow_reserve_info.key,
         );
         max_borrow_weight = match max_borrow_weight {
@@ -1044,6 +1011,7 @@ fn process_refresh_obligation(program_id: &Pubkey, accounts: &[AccountInfo]) ->
             .try_add(market_value_upper_bound.try_mul(borrow_reserve.borrow_weight())?)?;
     }
 
+    cu_checkpoint!("refresh_obligation: borrows");
     if account_info_iter.peek().is_some() {
         msg!("Too many obligation deposit or borrow reserves provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -1141,20 +1109,15 @@ fn _deposit_obligation_collateral<'a>(
     token_program_id: &AccountInfo<'a>,
 ) -> ProgramResult {
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
 
     let deposit_reserve = Reserve::unpack(&deposit_reserve_info.data.borrow())?;
-    if deposit_reserve_info.owner != program_id {
-        msg!("Deposit reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(deposit_reserve_info, program_id, "Deposit reserve")?;
     if &deposit_reserve.lending_market != lending_market_info.key {
         msg!("Deposit reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -1175,10 +1138,7 @@ fn _deposit_obligation_collateral<'a>(
     }
 
     let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
-    if obligation_info.owner != program_id {
-        msg!("Obligation provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(obligation_info, program_id, "Obligation")?;
     if &obligation.lending_market != lending_market_info.key {
         msg!("Obligation lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -1187,10 +1147,7 @@ fn _deposit_obligation_collateral<'a>(
         msg!("Obligation owner does not match the obligation owner provided");
         return Err(LendingError::InvalidObligationOwner.into());
     }
-    if !obligation_owner_info.is_signer {
-        msg!("Obligation owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    assert_signer(obligation_owner_info, "Obligation owner")?;
 
     obligation
         .find_or_add_collateral_to_deposits(*deposit_reserve_info.key)?
@@ -1333,20 +1290,15 @@ fn _withdraw_obligation_collateral<'a>(
     account_for_rate_limiter: bool,
 ) -> Result<u64, ProgramError> {
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
 
     let withdraw_reserve = Reserve::unpack(&withdraw_reserve_info.data.borrow())?;
-    if withdraw_reserve_info.owner != program_id {
-        msg!("Withdraw reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(withdraw_reserve_info, program_id, "Withdraw reserve")?;
     if &withdraw_reserve.lending_market != lending_market_info.key {
         msg!("Withdraw reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -1365,10 +1317,7 @@ fn _withdraw_obligation_collateral<'a>(
     }
 
     let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
-    if obligation_info.owner != program_id {
-        msg!("Obligation provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(obligation_info, program_id, "Obligation")?;
     if &obligation.lending_market != lending_market_info.key {
         msg!("Obligation lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -1377,10 +1326,7 @@ fn _withdraw_obligation_collateral<'a>(
         msg!("Obligation owner does not match the obligation owner provided");
         return Err(LendingError::InvalidObligationOwner.into());
     }
-    if !obligation_owner_info.is_signer {
-        msg!("Obligation owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    assert_signer(obligation_owner_info, "Obligation owner")?;
     if obligation.last_update.is_stale(clock.slot)? {
         msg!("Obligation is stale and must be refreshed in the current slot");
         return Err(LendingError::ObligationStale.into());
@@ -1441,6 +1387,7 @@ fn _withdraw_obligation_collateral<'a>(
     };
 
     let max_withdraw_amount = obligation.max_withdraw_amount(collateral, &withdraw_reserve)?;
+    cu_checkpoint!("withdraw_obligation_collateral: max_withdraw_amount");
     let withdraw_amount = min(
         collateral_amount,
         min(max_withdraw_amount, max_outflow_collateral_amount),
@@ -1494,57 +1441,50 @@ fn process_borrow_obligation_liquidity(
     let token_program_id = next_account_info(account_info_iter)?;
 
     let mut lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
 
-    let mut borrow_reserve = Reserve::unpack(&borrow_reserve_info.data.borrow())?;
-    if borrow_reserve_info.owner != program_id {
-        msg!("Borrow reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &borrow_reserve.lending_market != lending_market_info.key {
+    let borrow_reserve_data = borrow_reserve_info.data.borrow();
+    let borrow_reserve = ReserveView::load(&borrow_reserve_data)?;
+    assert_owned_by(borrow_reserve_info, program_id, "Borrow reserve")?;
+    if &borrow_reserve.lending_market() != lending_market_info.key {
         msg!("Borrow reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if &borrow_reserve.liquidity.supply_pubkey != source_liquidity_info.key {
+    if &borrow_reserve.liquidity_supply_pubkey() != source_liquidity_info.key {
         msg!("Borrow reserve liquidity supply must be used as the source liquidity provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if &borrow_reserve.liquidity.supply_pubkey == destination_liquidity_info.key {
+    if &borrow_reserve.liquidity_supply_pubkey() == destination_liquidity_info.key {
         msg!(
             "Borrow reserve liquidity supply cannot be used as the destination liquidity provided"
         );
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if &borrow_reserve.config.fee_receiver != borrow_reserve_liquidity_fee_receiver_info.key {
+    if &borrow_reserve.fee_receiver() != borrow_reserve_liquidity_fee_receiver_info.key {
         msg!("Borrow reserve liquidity fee receiver does not match the borrow reserve liquidity fee receiver provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if borrow_reserve.last_update.is_stale(clock.slot)? {
+    if borrow_reserve.last_update()?.is_stale(clock.slot)? {
         msg!("Borrow reserve is stale and must be refreshed in the current slot");
         return Err(LendingError::ReserveStale.into());
     }
     if liquidity_amount != u64::MAX
         && Decimal::from(liquidity_amount)
-            .try_add(borrow_reserve.liquidity.borrowed_amount_wads)?
+            .try_add(borrow_reserve.borrowed_amount_wads())?
             .try_floor_u64()?
-            > borrow_reserve.config.borrow_limit
+            > borrow_reserve.borrow_limit()
     {
         msg!("Cannot borrow above the borrow limit");
         return Err(LendingError::InvalidAmount.into());
     }
 
     let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
-    if obligation_info.owner != program_id {
-        msg!("Obligation provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(obligation_info, program_id, "Obligation")?;
     if &obligation.lending_market != lending_market_info.key {
         msg!("Obligation lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -1553,10 +1493,7 @@ fn process_borrow_obligation_liquidity(
         msg!("Obligation owner does not match the obligation owner provided");
         return Err(LendingError::InvalidObligationOwner.into());
     }
-    if !obligation_owner_info.is_signer {
-        msg!("Obligation owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    assert_signer(obligation_owner_info, "Obligation owner")?;
     if obligation.last_update.is_stale(clock.slot)? {
         msg!("Obligation is stale and must be refreshed in the current slot");
         return Err(LendingError::ObligationStale.into());
@@ -1583,7 +1520,7 @@ fn process_borrow_obligation_liquidity(
         return Err(LendingError::InvalidMarketAuthority.into());
     }
 
-    match borrow_reserve.config.reserve_type {
+    match borrow_reserve.reserve_type()? {
         ReserveType::Isolated => match obligation.borrows.len() {
             0 => {}
             1 => {
@@ -1618,14 +1555,15 @@ fn process_borrow_obligation_liquidity(
         return Err(LendingError::BorrowTooLarge.into());
     }
 
-    let remaining_reserve_capacity = Decimal::from(borrow_reserve.config.borrow_limit)
-        .try_sub(borrow_reserve.liquidity.borrowed_amount_wads)
+    let remaining_reserve_capacity = Decimal::from(borrow_reserve.borrow_limit())
+        .try_sub(borrow_reserve.borrowed_amount_wads())
         .unwrap_or_else(|_| Decimal::zero());
 
     // account for rate limiter restrictions when calculating max borrow amount.
+    let mut reserve_rate_limiter = borrow_reserve.rate_limiter()?;
     let max_outflow_liquidity_amount = {
         let max_outflow_usd = lending_market.rate_limiter.remaining_outflow(clock.slot)?;
-        let max_outflow_tokens = borrow_reserve.rate_limiter.remaining_outflow(clock.slot)?;
+        let max_outflow_tokens = reserve_rate_limiter.remaining_outflow(clock.slot)?;
 
         min(
             borrow_reserve.usd_to_liquidity_amount_lower_bound(min(
@@ -1647,13 +1585,14 @@ fn process_borrow_obligation_liquidity(
         remaining_borrow_value,
         min(remaining_reserve_capacity, max_outflow_liquidity_amount),
     )?;
+    cu_checkpoint!("borrow_obligation_liquidity: calculate_borrow");
 
     if receive_amount == 0 {
         msg!("Borrow amount is too small to receive liquidity after fees");
         return Err(LendingError::BorrowTooSmall.into());
     }
 
-    let cumulative_borrow_rate_wads = borrow_reserve.liquidity.cumulative_borrow_rate_wads;
+    let cumulative_borrow_rate_wads = borrow_reserve.cumulative_borrow_rate_wads();
 
     // check outflow rate limits
     {
@@ -1668,8 +1607,7 @@ fn process_borrow_obligation_liquidity(
                 err
             })?;
 
-        borrow_reserve
-            .rate_limiter
+        reserve_rate_limiter
             .update(clock.slot, borrow_amount)
             .map_err(|err| {
                 msg!("Reserve outflow limit exceeded! Please try again later");
@@ -1679,9 +1617,14 @@ fn process_borrow_obligation_liquidity(
 
     LendingMarket::pack(lending_market, &mut lending_market_info.data.borrow_mut())?;
 
-    borrow_reserve.liquidity.borrow(borrow_amount)?;
-    borrow_reserve.last_update.mark_stale();
-    Reserve::pack(borrow_reserve, &mut borrow_reserve_info.data.borrow_mut())?;
+    drop(borrow_reserve_data);
+    {
+        let mut borrow_reserve_data = borrow_reserve_info.data.borrow_mut();
+        let borrow_reserve = ReserveView::load_mut(&mut borrow_reserve_data)?;
+        borrow_reserve.borrow(borrow_amount)?;
+        borrow_reserve.set_rate_limiter(&reserve_rate_limiter);
+        borrow_reserve.mark_stale();
+    }
 
     let obligation_liquidity = obligation
         .find_or_add_liquidity_to_borrows(*borrow_reserve_info.key, cumulative_borrow_rate_wads)?;
@@ -1754,21 +1697,16 @@ fn process_repay_obligation_liquidity(
     let token_program_id = next_account_info(account_info_iter)?;
 
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
 
     _refresh_reserve_interest(program_id, repay_reserve_info, clock)?;
     let mut repay_reserve = Reserve::unpack(&repay_reserve_info.data.borrow())?;
-    if repay_reserve_info.owner != program_id {
-        msg!("Repay reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(repay_reserve_info, program_id, "Repay reserve")?;
     if &repay_reserve.lending_market != lending_market_info.key {
         msg!("Repay reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -1787,10 +1725,7 @@ fn process_repay_obligation_liquidity(
     }
 
     let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
-    if obligation_info.owner != program_id {
-        msg!("Obligation provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(obligation_info, program_id, "Obligation")?;
     if &obligation.lending_market != lending_market_info.key {
         msg!("Obligation lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -1810,6 +1745,7 @@ fn process_repay_obligation_liquidity(
         settle_amount,
         repay_amount,
     } = repay_reserve.calculate_repay(liquidity_amount, liquidity.borrowed_amount_wads)?;
+    cu_checkpoint!("repay_obligation_liquidity: calculate_repay");
 
     if repay_amount == 0 {
         msg!("Repay amount is too small to transfer liquidity");
@@ -1854,20 +1790,15 @@ fn _liquidate_obligation<'a>(
     token_program_id: &AccountInfo<'a>,
 ) -> Result<(u64, Decimal), ProgramError> {
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
 
     let mut repay_reserve = Reserve::unpack(&repay_reserve_info.data.borrow())?;
-    if repay_reserve_info.owner != program_id {
-        msg!("Repay reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(repay_reserve_info, program_id, "Repay reserve")?;
     if &repay_reserve.lending_market != lending_market_info.key {
         msg!("Repay reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -1891,37 +1822,32 @@ fn _liquidate_obligation<'a>(
         return Err(LendingError::ReserveStale.into());
     }
 
-    let withdraw_reserve = Reserve::unpack(&withdraw_reserve_info.data.borrow())?;
-    if withdraw_reserve_info.owner != program_id {
-        msg!("Withdraw reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &withdraw_reserve.lending_market != lending_market_info.key {
+    let withdraw_reserve_data = withdraw_reserve_info.data.borrow();
+    let withdraw_reserve = ReserveView::load(&withdraw_reserve_data)?;
+    assert_owned_by(withdraw_reserve_info, program_id, "Withdraw reserve")?;
+    if &withdraw_reserve.lending_market() != lending_market_info.key {
         msg!("Withdraw reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if &withdraw_reserve.collateral.supply_pubkey != withdraw_reserve_collateral_supply_info.key {
+    if &withdraw_reserve.collateral_supply_pubkey() != withdraw_reserve_collateral_supply_info.key {
         msg!("Withdraw reserve collateral supply does not match the withdraw reserve collateral supply provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if &withdraw_reserve.liquidity.supply_pubkey == source_liquidity_info.key {
+    if &withdraw_reserve.liquidity_supply_pubkey() == source_liquidity_info.key {
         msg!("Withdraw reserve liquidity supply cannot be used as the source liquidity provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if &withdraw_reserve.collateral.supply_pubkey == destination_collateral_info.key {
+    if &withdraw_reserve.collateral_supply_pubkey() == destination_collateral_info.key {
         msg!("Withdraw reserve collateral supply cannot be used as the destination collateral provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
-    if withdraw_reserve.last_update.is_stale(clock.slot)? {
+    if withdraw_reserve.last_update()?.is_stale(clock.slot)? {
         msg!("Withdraw reserve is stale and must be refreshed in the current slot");
         return Err(LendingError::ReserveStale.into());
     }
 
     let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
-    if obligation_info.owner != program_id {
-        msg!("Obligation provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(obligation_info, program_id, "Obligation")?;
     if &obligation.lending_market != lending_market_info.key {
         msg!("Obligation lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -1930,18 +1856,7 @@ fn _liquidate_obligation<'a>(
         msg!("Obligation is stale and must be refreshed in the current slot");
         return Err(LendingError::ObligationStale.into());
     }
-    if obligation.deposited_value == Decimal::zero() {
-        msg!("Obligation deposited value is zero");
-        return Err(LendingError::ObligationDepositsZero.into());
-    }
-    if obligation.borrowed_value == Decimal::zero() {
-        msg!("Obligation borrowed value is zero");
-        return Err(LendingError::ObligationBorrowsZero.into());
-    }
-    if obligation.borrowed_value < obligation.unhealthy_borrow_value {
-        msg!("Obligation is healthy and cannot be liquidated");
-        return Err(LendingError::ObligationHealthy.into());
-    }
+    obligation.check_liquidatable()?;
 
     if let Some(liquidator) = lending_market.whitelisted_liquidator {
         if liquidator != *user_transfer_authority_info.key {
@@ -1992,6 +1907,9 @@ fn _liquidate_obligation<'a>(
         liquidity,
         collateral,
     )?;
+    cu_checkpoint!("liquidate_obligation: calculate_liquidation");
+    // the withdraw reserve can also be the repay reserve, which is written below
+    drop(withdraw_reserve_data);
 
     if repay_amount == 0 {
         msg!("Liquidation is too small to transfer liquidity");
@@ -2029,6 +1947,15 @@ fn _liquidate_obligation<'a>(
         token_program: token_program_id.clone(),
     })?;
 
+    emit_event!(ObligationLiquidated {
+        obligation: *obligation_info.key,
+        repay_reserve: *repay_reserve_info.key,
+        withdraw_reserve: *withdraw_reserve_info.key,
+        liquidator: *user_transfer_authority_info.key,
+        repay_amount,
+        withdraw_amount,
+    });
+
     Ok((withdraw_amount, bonus_rate))
 }
 
@@ -2079,10 +2006,13 @@ fn process_liquidate_obligation_and_redeem_reserve_collateral(
     )?;
 
     _refresh_reserve_interest(program_id, withdraw_reserve_info, clock)?;
-    let withdraw_reserve = Reserve::unpack(&withdraw_reserve_info.data.borrow())?;
-    let collateral_exchange_rate = withdraw_reserve.collateral_exchange_rate()?;
-    let max_redeemable_collateral = collateral_exchange_rate
-        .liquidity_to_collateral(withdraw_reserve.liquidity.available_amount)?;
+    let max_redeemable_collateral = {
+        let withdraw_reserve_data = withdraw_reserve_info.data.borrow();
+        let withdraw_reserve = ReserveView::load(&withdraw_reserve_data)?;
+        withdraw_reserve
+            .collateral_exchange_rate()?
+            .liquidity_to_collateral(withdraw_reserve.available_amount())?
+    };
     let withdraw_collateral_amount = min(withdrawn_collateral_amount, max_redeemable_collateral);
     // if there is liquidity redeem it
     if withdraw_collateral_amount != 0 {
@@ -2101,14 +2031,17 @@ fn process_liquidate_obligation_and_redeem_reserve_collateral(
             token_program_id,
             false,
         )?;
-        let withdraw_reserve = Reserve::unpack(&withdraw_reserve_info.data.borrow())?;
-        if &withdraw_reserve.config.fee_receiver != withdraw_reserve_liquidity_fee_receiver_info.key
-        {
-            msg!("Withdraw reserve liquidity fee receiver does not match the reserve liquidity fee receiver provided");
-            return Err(LendingError::InvalidAccountInput.into());
-        }
-        let protocol_fee = withdraw_reserve
-            .calculate_protocol_liquidation_fee(withdraw_liquidity_amount, bonus_rate)?;
+        let protocol_fee = {
+            let withdraw_reserve_data = withdraw_reserve_info.data.borrow();
+            let withdraw_reserve = ReserveView::load(&withdraw_reserve_data)?;
+            if &withdraw_reserve.fee_receiver() != withdraw_reserve_liquidity_fee_receiver_info.key
+            {
+                msg!("Withdraw reserve liquidity fee receiver does not match the reserve liquidity fee receiver provided");
+                return Err(LendingError::InvalidAccountInput.into());
+            }
+            withdraw_reserve
+                .calculate_protocol_liquidation_fee(withdraw_liquidity_amount, bonus_rate)?
+        };
 
         spl_token_transfer(TokenTransferParams {
             source: destination_liquidity_info.clone(),
@@ -2198,28 +2131,14 @@ fn process_update_reserve_config(
     let switchboard_feed_info = next_account_info(account_info_iter)?;
 
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
-    if reserve_info.owner != program_id {
-        msg!(
-            "Reserve provided is not owned by the lending program {} != {}",
-            &reserve_info.owner.to_string(),
-            &program_id.to_string(),
-        );
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(reserve_info, program_id, "Reserve")?;
     if &reserve.lending_market != lending_market_info.key {
         msg!("Reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
     }
 
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!(
-            "Lending market provided is not owned by the lending program  {} != {}",
-            &lending_market_info.owner.to_string(),
-            &program_id.to_string(),
-        );
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
 
     // if it's a permissionless market
     if &solend_market_owner::id() != signer_info.key {
@@ -2255,10 +2174,7 @@ fn process_update_reserve_config(
         return Err(LendingError::InvalidMarketAuthority.into());
     }
 
-    if !signer_info.is_signer {
-        msg!("Lending market owner or risk authority provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    assert_signer(signer_info, "Lending market owner or risk authority")?;
 
     if signer_info.key == &lending_market.owner {
         // if window duration or max outflow are different, then create a new rate limiter instance.
@@ -2328,14 +2244,7 @@ fn process_redeem_fees(program_id: &Pubkey, accounts: &[AccountInfo]) -> Program
     let clock = &Clock::get()?;
 
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
-    if reserve_info.owner != program_id {
-        msg!(
-            "Reserve provided is not owned by the lending program {} != {}",
-            &reserve_info.owner.to_string(),
-            &program_id.to_string(),
-        );
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(reserve_info, program_id, "Reserve")?;
 
     if &reserve.config.fee_receiver != reserve_liquidity_fee_receiver_info.key {
         msg!("Reserve liquidity fee receiver does not match the reserve liquidity fee receiver provided");
@@ -2355,14 +2264,12 @@ fn process_redeem_fees(program_id: &Pubkey, accounts: &[AccountInfo]) -> Program
     }
 
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
     let authority_signer_seeds = &[
         lending_market_info.key.as_ref(),
         &[lending_market.bump_seed],
@@ -2440,19 +2347,14 @@ fn _flash_borrow_reserve_liquidity<'a>(
     token_program_id: &AccountInfo<'a>,
 ) -> ProgramResult {
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
-    if reserve_info.owner != program_id {
-        msg!("Reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(reserve_info, program_id, "Reserve")?;
     if &reserve.lending_market != lending_market_info.key {
         msg!("Reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -2488,11 +2390,8 @@ fn _flash_borrow_reserve_liquidity<'a>(
     }
 
     // Make sure this isnt a cpi call
-    let current_index = load_current_index_checked(sysvar_info)? as usize;
-    if is_cpi_call(program_id, current_index, sysvar_info)? {
-        msg!("Flash Borrow was called via CPI!");
-        return Err(LendingError::FlashBorrowCpi.into());
-    }
+    let current_index = assert_not_cpi(program_id, sysvar_info, LendingError::FlashBorrowCpi)?;
+    cu_checkpoint!("flash_borrow_reserve_liquidity: assert_not_cpi");
 
     // Find and validate the flash repay instruction.
     //
@@ -2625,19 +2524,14 @@ fn _flash_repay_reserve_liquidity<'a>(
     token_program_id: &AccountInfo<'a>,
 ) -> ProgramResult {
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
-    if &lending_market.token_program_id != token_program_id.key {
-        msg!("Lending market token program does not match the token program provided");
-        return Err(LendingError::InvalidTokenProgram.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
+    assert_program_id(
+        token_program_id,
+        &lending_market.token_program_id,
+        "Token program",
+    )?;
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
-    if reserve_info.owner != program_id {
-        msg!("Reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(reserve_info, program_id, "Reserve")?;
     if &reserve.lending_market != lending_market_info.key {
         msg!("Reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -2664,11 +2558,8 @@ fn _flash_repay_reserve_liquidity<'a>(
         .calculate_flash_loan_fees(flash_loan_amount_decimal)?;
 
     // Make sure this isnt a cpi call
-    let current_index = load_current_index_checked(sysvar_info)? as usize;
-    if is_cpi_call(program_id, current_index, sysvar_info)? {
-        msg!("Flash Repay was called via CPI!");
-        return Err(LendingError::FlashRepayCpi.into());
-    }
+    let current_index = assert_not_cpi(program_id, sysvar_info, LendingError::FlashRepayCpi)?;
+    cu_checkpoint!("flash_repay_reserve_liquidity: assert_not_cpi");
 
     // validate flash borrow
     if (borrow_instruction_index as usize) > current_index {
@@ -2764,28 +2655,15 @@ fn process_forgive_debt(
     let lending_market_owner_info = next_account_info(account_info_iter)?;
 
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!(
-            "Lending market provided is not owned by the lending program  {} != {}",
-            &lending_market_info.owner.to_string(),
-            &program_id.to_string(),
-        );
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
     if &lending_market.owner != lending_market_owner_info.key {
         msg!("Lending market owner does not match the lending market owner provided");
         return Err(LendingError::InvalidMarketOwner.into());
     }
-    if !lending_market_owner_info.is_signer {
-        msg!("Lending market owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    assert_signer(lending_market_owner_info, "Lending market owner")?;
 
     let mut reserve = Reserve::unpack(&reserve_info.data.borrow())?;
-    if reserve_info.owner != program_id {
-        msg!("Reserve provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(reserve_info, program_id, "Reserve")?;
     if &reserve.lending_market != lending_market_info.key {
         msg!("Reserve lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -2796,10 +2674,7 @@ fn process_forgive_debt(
     }
 
     let mut obligation = Obligation::unpack(&obligation_info.data.borrow())?;
-    if obligation_info.owner != program_id {
-        msg!("Obligation provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(obligation_info, program_id, "Obligation")?;
     if &obligation.lending_market != lending_market_info.key {
         msg!("Obligation lending market does not match the lending market provided");
         return Err(LendingError::InvalidAccountInput.into());
@@ -2843,19 +2718,6 @@ fn process_forgive_debt(
     Ok(())
 }
 
-fn assert_rent_exempt(rent: &Rent, account_info: &AccountInfo) -> ProgramResult {
-    if !rent.is_exempt(account_info.lamports(), account_info.data_len()) {
-        msg!(
-            "Rent exempt balance insufficient got {} expected {}",
-            &account_info.lamports().to_string(),
-            &rent.minimum_balance(account_info.data_len()).to_string(),
-        );
-        Err(LendingError::NotRentExempt.into())
-    } else {
-        Ok(())
-    }
-}
-
 fn process_update_market_metadata(
     program_id: &Pubkey,
     metadata: &LendingMarketMetadata,
@@ -2867,23 +2729,17 @@ fn process_update_market_metadata(
     let metadata_info = next_account_info(account_info_iter)?;
 
     let lending_market = LendingMarket::unpack(&lending_market_info.data.borrow())?;
-    if lending_market_info.owner != program_id {
-        msg!("Lending market provided is not owned by the lending program",);
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(lending_market_info, program_id, "Lending market")?;
 
     if &lending_market.owner != lending_market_owner_info.key {
         msg!("Lending market owner does not match the lending market owner provided");
         return Err(LendingError::InvalidMarketOwner.into());
     }
 
-    if !lending_market_owner_info.is_signer {
-        msg!("Lending market owner provided must be a signer");
-        return Err(LendingError::InvalidSigner.into());
-    }
+    assert_signer(lending_market_owner_info, "Lending market owner")?;
 
-    let metadata_seeds = &[lending_market_info.key.as_ref(), b"MetaData"];
-    let (metadata_key, bump_seed) = Pubkey::find_program_address(metadata_seeds, program_id);
+    let (metadata_key, bump_seed) =
+        find_lending_market_metadata_address(lending_market_info.key, program_id);
     if metadata_key != *metadata_info.key {
         msg!("Provided metadata account does not match the expected derived address");
         return Err(LendingError::InvalidAccountInput.into());
@@ -2907,14 +2763,15 @@ fn process_update_market_metadata(
                 program_id,
             ),
             &[lending_market_owner_info.clone(), metadata_info.clone()],
-            &[&[lending_market_info.key.as_ref(), br"MetaData", &[bump_seed]]],
+            &[&[
+                lending_market_info.key.as_ref(),
+                LENDING_MARKET_METADATA_SEED,
+                &[bump_seed],
+            ]],
         )?;
     }
 
-    if metadata_info.owner != program_id {
-        msg!("Metadata provided is not owned by the lending program");
-        return Err(LendingError::InvalidAccountOwner.into());
-    }
+    assert_owned_by(metadata_info, program_id, "Metadata")?;
 
     let mut metadata_account_data = metadata_info.try_borrow_mut_data()?;
     metadata_account_data.copy_from_slice(bytes_of(metadata));
@@ -2922,15 +2779,34 @@ fn process_update_market_metadata(
     Ok(())
 }
 
-fn assert_uninitialized<T: Pack + IsInitialized>(
-    account_info: &AccountInfo,
-) -> Result<T, ProgramError> {
-    let account: T = T::unpack_unchecked(&account_info.data.borrow())?;
-    if account.is_initialized() {
-        Err(LendingError::AlreadyInitialized.into())
-    } else {
-        Ok(account)
+/// Crate version and features of this program, returned by `GetVersion`. Not to be confused with
+/// `state::PROGRAM_VERSION`, the version byte of account layouts
+const PROGRAM_SEMVER: ProgramVersion = ProgramVersion {
+    major: parse_version_number(env!("CARGO_PKG_VERSION_MAJOR")),
+    minor: parse_version_number(env!("CARGO_PKG_VERSION_MINOR")),
+    patch: parse_version_number(env!("CARGO_PKG_VERSION_PATCH")),
+    features: FEATURE_FLASH_LOANS
+        | FEATURE_RATE_LIMITS
+        | FEATURE_MARKET_METADATA
+        | FEATURE_FORGIVE_DEBT
+        | FEATURE_ISOLATED_RESERVES
+        | FEATURE_LIQUIDATION_EVENTS,
+};
+
+const fn parse_version_number(number: &str) -> u16 {
+    let bytes = number.as_bytes();
+    let mut value = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        value = value * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
     }
+    value
+}
+
+fn process_get_version() -> ProgramResult {
+    set_return_data(&PROGRAM_SEMVER.pack());
+    Ok(())
 }
 
 /// Unpacks a spl_token `Mint`.
@@ -2958,106 +2834,6 @@ fn get_pyth_product_quote_currency(
         })
 }
 
-/// get_price tries to load the oracle price from pyth, and if it fails, uses switchboard.
-/// The first element in the returned tuple is the market price, and the second is the optional
-/// smoothed price (eg ema, twap).
-fn get_price(
-    switchboard_feed_info: Option<&AccountInfo>,
-    pyth_price_account_info: &AccountInfo,
-    clock: &Clock,
-) -> Result<(Decimal, Option<Decimal>), ProgramError> {
-    if let Ok(prices) = get_pyth_price(pyth_price_account_info, clock) {
-        return Ok((prices.0, Some(prices.1)));
-    }
-
-    // if switchboard was not passed in don't try to grab the price
-    if let Some(switchboard_feed_info_unwrapped) = switchboard_feed_info {
-        // TODO: add support for switchboard smoothed prices. Probably need to add a new
-        // switchboard account per reserve.
-        return match get_switchboard_price(switchboard_feed_info_unwrapped, clock) {
-            Ok(price) => Ok((price, None)),
-            Err(e) => Err(e),
-        };
-    }
-
-    Err(LendingError::InvalidOracleConfig.into())
-}
-
-fn get_switchboard_price(
-    switchboard_feed_info: &AccountInfo,
-    clock: &Clock,
-) -> Result<Decimal, ProgramError> {
-    const STALE_AFTER_SLOTS_ELAPSED: u64 = 240;
-
-    if *switchboard_feed_info.key == solend_program::NULL_PUBKEY {
-        return Err(LendingError::NullOracleConfig.into());
-    }
-    if switchboard_feed_info.owner == &switchboard_v2_mainnet::id()
-        || switchboard_feed_info.owner == &switchboard_v2_devnet::id()
-    {
-        return get_switchboard_price_v2(switchboard_feed_info, clock);
-    }
-
-    let account_buf = switchboard_feed_info.try_borrow_data()?;
-    // first byte type discriminator
-    if account_buf[0] != SwitchboardAccountType::TYPE_AGGREGATOR as u8 {
-        msg!("switchboard address not of type aggregator");
-        return Err(LendingError::InvalidAccountInput.into());
-    }
-
-    let aggregator: AggregatorState = get_aggregator(switchboard_feed_info)?;
-    // if aggregator.version != 1 {
-    //     msg!("switchboard version incorrect");
-    //     return Err(LendingError::InvalidAccountInput.into());
-    // }
-    let round_result: RoundResult = get_aggregator_result(&aggregator)?;
-
-    let slots_elapsed = clock
-        .slot
-        .checked_sub(round_result.round_open_slot.unwrap())
-        .ok_or(LendingError::MathOverflow)?;
-    if slots_elapsed >= STALE_AFTER_SLOTS_ELAPSED {
-        msg!("Switchboard oracle price is stale");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-
-    let price_float = round_result.result.unwrap_or(0.0);
-
-    // we just do this so we can parse coins with low usd value
-    // it might be better to just extract the mantissa and exponent from the float directly
-    let price_quotient = 10u64.pow(9);
-    let price = ((price_quotient as f64) * price_float) as u128;
-
-    Decimal::from(price).try_div(price_quotient)
-}
-
-fn get_switchboard_price_v2(
-    switchboard_feed_info: &AccountInfo,
-    clock: &Clock,
-) -> Result<Decimal, ProgramError> {
-    const STALE_AFTER_SLOTS_ELAPSED: u64 = 240;
-    let data = &switchboard_feed_info.try_borrow_data()?;
-    let feed = AggregatorAccountData::new_from_bytes(data)?;
-
-    let slots_elapsed = clock
-        .slot
-        .checked_sub(feed.latest_confirmed_round.round_open_slot)
-        .ok_or(LendingError::MathOverflow)?;
-    if slots_elapsed >= STALE_AFTER_SLOTS_ELAPSED {
-        msg!("Switchboard oracle price is stale");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-
-    let price_switchboard_desc = feed.get_result()?;
-    if price_switchboard_desc.mantissa < 0 {
-        msg!("Switchboard oracle price is negative which is not allowed");
-        return Err(LendingError::InvalidOracleConfig.into());
-    }
-    let price = Decimal::from(price_switchboard_desc.mantissa as u128);
-    let exp = Decimal::from((10u128).checked_pow(price_switchboard_desc.scale).unwrap());
-    price.try_div(exp)
-}
-
 /// Issue a spl_token `InitializeAccount` instruction.
 #[inline(always)]
 fn spl_token_init_account(params: TokenInitializeAccountParams<'_>) -> ProgramResult {
@@ -3137,6 +2913,7 @@ fn spl_token_transfer(params: TokenTransferParams<'_, '_>) -> ProgramResult {
         authority_signer_seeds,
     );
 
+    cu_checkpoint!("spl_token_transfer: invoke");
     result.map_err(|_| LendingError::TokenTransferFailed.into())
 }
 
@@ -3162,6 +2939,7 @@ fn spl_token_mint_to(params: TokenMintToParams<'_, '_>) -> ProgramResult {
         &[mint, destination, authority, token_program],
         authority_signer_seeds,
     );
+    cu_checkpoint!("spl_token_mint_to: invoke");
     result.map_err(|_| LendingError::TokenMintToFailed.into())
 }
 
@@ -3188,6 +2966,7 @@ fn spl_token_burn(params: TokenBurnParams<'_, '_>) -> ProgramResult {
         &[source, mint, authority, token_program],
         authority_signer_seeds,
     );
+    cu_checkpoint!("spl_token_burn: invoke");
     result.map_err(|_| LendingError::TokenBurnFailed.into())
 }
 
@@ -3244,35 +3023,6 @@ fn validate_switchboard_keys(
     Ok(())
 }
 
-fn is_cpi_call(
-    program_id: &Pubkey,
-    current_index: usize,
-    sysvar_info: &AccountInfo,
-) -> Result<bool, ProgramError> {
-    // say the tx looks like:
-    // ix 0
-    //   - ix a
-    //   - ix b
-    //   - ix c
-    // ix 1
-    // and we call "load_current_index_checked" from b, we will get 0. And when we
-    // load_instruction_at_checked(0), we will get ix 0.
-    // tldr; instructions sysvar only stores top-level instructions, never CPI instructions.
-    let current_ixn = load_instruction_at_checked(current_index, sysvar_info)?;
-
-    // the current ixn must match the flash_* ix. otherwise, it's a CPI. Comparing program_ids is a
-    // cheaper way of verifying this property, bc token-lending doesn't allow re-entrancy anywhere.
-    if *program_id != current_ixn.program_id {
-        return Ok(true);
-    }
-
-    if get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT {
-        return Ok(true);
-    }
-
-    Ok(false)
-}
-
 struct TokenInitializeMintParams<'a: 'b, 'b> {
     mint: AccountInfo<'a>,
     rent: AccountInfo<'a>,
@@ -0,0 +1,30 @@
+//! Compute unit checkpoints for profiling the processor, compiled in with the `profile-cu`
+//! feature. Each checkpoint logs `CU checkpoint: <label>` followed by the runtime's
+//! `Program consumption: <n> units remaining` line, so the units spent between two checkpoints
+//! can be read back from the transaction logs.
+
+/// Prefix of the log line naming a checkpoint, as a literal `cu_checkpoint!` can `concat!`
+macro_rules! checkpoint_prefix {
+    () => {
+        "CU checkpoint: "
+    };
+}
+
+/// Prefix of the log line naming a checkpoint
+pub const CHECKPOINT_PREFIX: &str = checkpoint_prefix!();
+
+/// Logs a compute unit checkpoint named `$label` with the `profile-cu` feature, and compiles to
+/// nothing without it
+macro_rules! cu_checkpoint {
+    ($label:literal) => {
+        #[cfg(feature = "profile-cu")]
+        {
+            solana_program::log::sol_log(concat!($crate::profile::checkpoint_prefix!(), $label));
+            solana_program::log::sol_log_compute_units();
+        }
+    };
+}
+
+#[cfg(feature = "profile-cu")]
+pub(crate) use checkpoint_prefix;
+pub(crate) use cu_checkpoint;
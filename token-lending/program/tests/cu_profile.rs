@@ -0,0 +1,86 @@
+#![cfg(feature = "test-bpf")]
+
+mod helpers;
+
+use helpers::cu_profile::{parse_checkpoints, Checkpoint, CuReport};
+
+fn logs(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+fn refresh_reserve_logs(remaining: [u64; 3]) -> Vec<String> {
+    logs(&[
+        "Program So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo invoke [1]",
+        "Program log: CU checkpoint: process_instruction: unpack",
+        &format!("Program consumption: {} units remaining", remaining[0]),
+        "Program log: CU checkpoint: refresh_reserve: get_price",
+        &format!("Program consumption: {} units remaining", remaining[1]),
+        "Program log: CU checkpoint: refresh_reserve: accrue_interest",
+        &format!("Program consumption: {} units remaining", remaining[2]),
+        "Program So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo consumed 9000 of 200000 compute units",
+        "Program So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo success",
+    ])
+}
+
+#[test]
+fn test_parse_checkpoints() {
+    let mut lines = logs(&[
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+    ]);
+    lines.extend(refresh_reserve_logs([199_000, 195_000, 193_000]));
+    lines.extend(logs(&[
+        "Program So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo invoke [1]",
+        "Program log: CU checkpoint: process_instruction: unpack",
+        "Program consumption: 190000 units remaining",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+        "Program log: Instruction: Transfer",
+        // not preceded by a checkpoint, so ignored
+        "Program consumption: 188000 units remaining",
+        "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+        "Program log: CU checkpoint: spl_token_transfer: invoke",
+        "Program consumption: 185000 units remaining",
+    ]));
+
+    let checkpoint = |label: &str, remaining| Checkpoint {
+        label: label.to_string(),
+        remaining,
+    };
+    assert_eq!(
+        parse_checkpoints(&lines),
+        vec![
+            vec![
+                checkpoint("process_instruction: unpack", 199_000),
+                checkpoint("refresh_reserve: get_price", 195_000),
+                checkpoint("refresh_reserve: accrue_interest", 193_000),
+            ],
+            vec![
+                checkpoint("process_instruction: unpack", 190_000),
+                checkpoint("spl_token_transfer: invoke", 185_000),
+            ],
+        ]
+    );
+}
+
+#[test]
+fn test_cu_report() {
+    let mut report = CuReport::default();
+    report.record_logs(&refresh_reserve_logs([199_000, 195_000, 193_000]));
+    report.record_logs(&refresh_reserve_logs([199_000, 193_000, 192_000]));
+    assert_eq!(report.paths.len(), 1);
+
+    let path = report.paths.values().next().unwrap();
+    assert_eq!(path.total.runs, 2);
+    assert_eq!(path.total.min, 6_000);
+    assert_eq!(path.total.max, 7_000);
+    assert_eq!(path.segments[0].avg(), 5_000);
+    assert_eq!(path.segments[1].min, 1_000);
+    assert_eq!(path.segments[1].max, 2_000);
+
+    assert_eq!(
+        report.to_string(),
+        "process_instruction: unpack > refresh_reserve: get_price > refresh_reserve: accrue_interest (2 runs): min 6000 avg 6500 max 7000\n\
+         \x20 process_instruction: unpack -> refresh_reserve: get_price: min 4000 avg 5000 max 6000\n\
+         \x20 refresh_reserve: get_price -> refresh_reserve: accrue_interest: min 1000 avg 1500 max 2000\n"
+    );
+}
@@ -10,6 +10,7 @@ use helpers::solend_program_test::{
     setup_world, BalanceChecker, Info, SolendProgramTest, TokenBalanceChange, User,
 };
 use helpers::*;
+use rand::{seq::SliceRandom, Rng};
 use solana_program::instruction::InstructionError;
 use solana_program_test::*;
 use solana_sdk::transaction::TransactionError;
@@ -111,6 +112,51 @@ async fn test_success() {
     );
 }
 
+#[tokio::test]
+async fn test_random_deposits() {
+    let (mut test, lending_market, usdc_reserve, _) = setup().await;
+
+    let mut deposits = vec![];
+    for _ in 0..test.rng().gen_range(1..=5) {
+        let amount = test.rng().gen_range(1..=10_000 * FRACTIONAL_TO_USDC);
+        let user = User::new_with_balances(
+            &mut test,
+            &[
+                (&usdc_mint::id(), amount),
+                (&usdc_reserve.account.collateral.mint_pubkey, 0),
+            ],
+        )
+        .await;
+        deposits.push((user, amount));
+    }
+    deposits.shuffle(test.rng());
+
+    for (user, amount) in &deposits {
+        lending_market
+            .deposit(&mut test, &usdc_reserve, user, *amount)
+            .await
+            .unwrap();
+
+        // the exchange rate stays 1:1 without borrows
+        assert_eq!(
+            user.get_balance(&mut test, &usdc_reserve.account.collateral.mint_pubkey)
+                .await,
+            Some(*amount)
+        );
+    }
+
+    let total: u64 = deposits.iter().map(|(_, amount)| amount).sum();
+    let usdc_reserve_post = test.load_account::<Reserve>(usdc_reserve.pubkey).await;
+    assert_eq!(
+        usdc_reserve_post.account.liquidity.available_amount,
+        usdc_reserve.account.liquidity.available_amount + total
+    );
+    assert_eq!(
+        usdc_reserve_post.account.collateral.mint_total_supply,
+        usdc_reserve.account.collateral.mint_total_supply + total
+    );
+}
+
 #[tokio::test]
 async fn test_fail_exceed_deposit_limit() {
     let (mut test, lending_market, usdc_reserve, user) = setup().await;
@@ -0,0 +1,172 @@
+#![cfg(feature = "test-bpf")]
+
+mod helpers;
+
+use helpers::faults::{OracleFault, TokenFault};
+use helpers::solend_program_test::{setup_world, PriceArgs, SolendProgramTest};
+use helpers::*;
+use pyth_sdk_solana::state::{PriceAccount, PriceStatus};
+use solana_program::{program_option::COption, program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    instruction::InstructionError,
+    transaction::TransactionError,
+};
+use solend_program::error::LendingError;
+use spl_token::state::{Account as Token, AccountState};
+
+async fn get_price_account(test: &mut SolendProgramTest, mint: &Pubkey) -> Option<PriceAccount> {
+    let pubkey = test.mints.get(mint).unwrap().unwrap().pyth_price_pubkey;
+    test.context
+        .banks_client
+        .get_account(pubkey)
+        .await
+        .unwrap()
+        .map(|mut account| *load_mut::<PriceAccount>(&mut account.data).unwrap())
+}
+
+#[tokio::test]
+async fn test_pyth_faults() {
+    let mut test = SolendProgramTest::start_new().await;
+    test.init_pyth_feed(&usdc_mint::id()).await;
+    test.set_price(
+        &usdc_mint::id(),
+        &PriceArgs {
+            price: 1,
+            conf: 0,
+            expo: 0,
+            ema_price: 1,
+            ema_conf: 0,
+        },
+    )
+    .await;
+
+    test.inject_pyth_fault(&usdc_mint::id(), OracleFault::Stale { slots_ago: 300 })
+        .await;
+    let slot = test.get_clock().await.slot;
+    let price = get_price_account(&mut test, &usdc_mint::id())
+        .await
+        .unwrap();
+    assert_eq!(price.agg.pub_slot, slot - 300);
+
+    test.inject_pyth_fault(&usdc_mint::id(), OracleFault::NoPrice)
+        .await;
+    let price = get_price_account(&mut test, &usdc_mint::id())
+        .await
+        .unwrap();
+    assert!(matches!(price.agg.status, PriceStatus::Halted));
+
+    test.inject_pyth_fault(&usdc_mint::id(), OracleFault::Absent)
+        .await;
+    assert!(get_price_account(&mut test, &usdc_mint::id())
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_token_faults() {
+    let mut test = SolendProgramTest::start_new().await;
+    let token_account = Pubkey::new_unique();
+    let mut account = Account::new(1, Token::LEN, &spl_token::id());
+    Token::pack(
+        Token {
+            mint: usdc_mint::id(),
+            owner: Pubkey::new_unique(),
+            amount: 100,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        },
+        &mut account.data,
+    )
+    .unwrap();
+    test.context
+        .set_account(&token_account, &AccountSharedData::from(account));
+
+    test.inject_token_fault(&token_account, TokenFault::Balance(1))
+        .await;
+    test.inject_token_fault(&token_account, TokenFault::Frozen)
+        .await;
+
+    let token = test.load_account::<Token>(token_account).await.account;
+    assert_eq!(token.amount, 1);
+    assert_eq!(token.state, AccountState::Frozen);
+}
+
+#[tokio::test]
+async fn test_skip_clock() {
+    let mut test = SolendProgramTest::start_new().await;
+    let before = test.get_clock().await;
+
+    test.skip_clock(-60).await;
+
+    let after = test.get_clock().await;
+    assert_eq!(after.unix_timestamp, before.unix_timestamp - 60);
+    assert_eq!(after.slot, before.slot);
+}
+
+#[tokio::test]
+async fn test_refresh_reserve_oracle_outage() {
+    let (mut test, lending_market, _usdc_reserve, wsol_reserve, _owner, _user) =
+        setup_world(&test_reserve_config(), &test_reserve_config()).await;
+
+    for fault in [OracleFault::Stale { slots_ago: 241 }, OracleFault::NoPrice] {
+        test.inject_pyth_fault(&wsol_mint::id(), fault).await;
+
+        let err = lending_market
+            .refresh_reserve(&mut test, &wsol_reserve)
+            .await
+            .unwrap_err()
+            .unwrap();
+        // without a switchboard feed to fall back on, the reserve has no price
+        assert_eq!(
+            err,
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(LendingError::NullOracleConfig as u32),
+            ),
+            "{:?}",
+            fault
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_deposit_token_faults() {
+    let (mut test, lending_market, usdc_reserve, _wsol_reserve, _owner, user) =
+        setup_world(&test_reserve_config(), &test_reserve_config()).await;
+    let source = user.get_account(&usdc_mint::id()).unwrap();
+
+    test.inject_token_fault(&source, TokenFault::Balance(10))
+        .await;
+    let err = lending_market
+        .deposit(&mut test, &usdc_reserve, &user, 11)
+        .await
+        .unwrap_err()
+        .unwrap();
+    // the lending program sees the token program's failure as a failed transfer CPI
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(LendingError::TokenTransferFailed as u32)
+        )
+    );
+
+    test.inject_token_fault(&source, TokenFault::Frozen).await;
+    let err = lending_market
+        .deposit(&mut test, &usdc_reserve, &user, 10)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(LendingError::TokenTransferFailed as u32)
+        )
+    );
+}
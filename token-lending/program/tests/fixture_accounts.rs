@@ -0,0 +1,85 @@
+#![cfg(feature = "test-bpf")]
+
+mod helpers;
+
+use helpers::fixtures::{add_fixture_accounts, read_fixture_accounts};
+use helpers::solend_program_test::SolendProgramTest;
+use solana_program::program_pack::Pack;
+use solana_program_test::*;
+use solana_sdk::pubkey::Pubkey;
+use solend_program::state::{LendingMarket, Reserve};
+use solend_sdk::math::Decimal;
+use spl_token::state::Mint;
+use std::str::FromStr;
+
+const FIXTURE_DIR: &str = "tests/fixtures/market";
+
+#[test]
+fn test_read_fixture_accounts() {
+    let accounts = read_fixture_accounts(FIXTURE_DIR);
+    assert_eq!(accounts.len(), 3);
+
+    let market_pubkey = Pubkey::from_str("1111111QLbz7JHiBTspS962RLKV8GndWFwiEaqKM").unwrap();
+    let mint_pubkey = Pubkey::from_str("11111112D1oxKts8YPdTJRG5FzxTNpMtWmq8hkVx3").unwrap();
+    let reserve_pubkey = Pubkey::from_str("11111112cMQwSC9qirWGjZM6gLGwW69X22mqwLLGP").unwrap();
+
+    // files are read in path order
+    assert_eq!(
+        accounts
+            .iter()
+            .map(|(pubkey, _)| *pubkey)
+            .collect::<Vec<_>>(),
+        vec![mint_pubkey, reserve_pubkey, market_pubkey]
+    );
+
+    let (_, market_account) = &accounts[2];
+    assert_eq!(market_account.owner, solend_program::id());
+    let market = LendingMarket::unpack(&market_account.data).unwrap();
+    assert_eq!(market.token_program_id, spl_token::id());
+    assert_eq!(&market.quote_currency[..3], b"USD");
+
+    let (_, mint_account) = &accounts[0];
+    assert_eq!(mint_account.owner, spl_token::id());
+    let mint = Mint::unpack(&mint_account.data).unwrap();
+    assert_eq!(mint.decimals, 6);
+    assert_eq!(mint.supply, 1_000_000_000_000);
+
+    let (_, reserve_account) = &accounts[1];
+    assert_eq!(reserve_account.owner, solend_program::id());
+    assert_eq!(reserve_account.lamports, 9_938_880);
+    let reserve = Reserve::unpack(&reserve_account.data).unwrap();
+    assert_eq!(reserve.lending_market, market_pubkey);
+    assert_eq!(reserve.liquidity.mint_pubkey, mint_pubkey);
+    assert_eq!(reserve.liquidity.mint_decimals, mint.decimals);
+    assert_eq!(reserve.liquidity.available_amount, 750_000_000);
+    assert_eq!(
+        reserve.liquidity.borrowed_amount_wads,
+        Decimal::from(250_000_000u64)
+    );
+    assert_eq!(reserve.config.loan_to_value_ratio, 75);
+    assert_eq!(reserve.config.liquidation_threshold, 80);
+}
+
+#[tokio::test]
+async fn test_add_fixture_accounts() {
+    let mut test = ProgramTest::new(
+        "solend_program",
+        solend_program::id(),
+        processor!(solend_program::processor::process_instruction),
+    );
+    let accounts = add_fixture_accounts(&mut test, FIXTURE_DIR);
+
+    let mut test = SolendProgramTest::start_with_test(test).await;
+    for (pubkey, expected) in accounts {
+        let account = test
+            .context
+            .banks_client
+            .get_account(pubkey)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.owner, expected.owner);
+        assert_eq!(account.lamports, expected.lamports);
+        assert_eq!(account.data, expected.data);
+    }
+}
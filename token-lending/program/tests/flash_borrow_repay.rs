@@ -899,6 +899,52 @@ async fn test_fail_cpi_borrow() {
     );
 }
 
+#[tokio::test]
+async fn test_fail_nested_cpi_borrow() {
+    let (mut test, lending_market, usdc_reserve, user, _, _) = setup(&ReserveConfig {
+        deposit_limit: u64::MAX,
+        borrow_limit: u64::MAX,
+        fees: ReserveFees {
+            borrow_fee_wad: 1,
+            host_fee_percentage: 20,
+            flash_loan_fee_wad: 1,
+        },
+        ..test_reserve_config()
+    })
+    .await;
+
+    const FLASH_LOAN_AMOUNT: u64 = 3_000_000;
+    let res = test
+        .process_transaction(
+            &[helpers::flash_loan_proxy::nested_borrow_proxy(
+                proxy_program::id(),
+                FLASH_LOAN_AMOUNT,
+                usdc_reserve.account.liquidity.supply_pubkey,
+                user.get_account(&usdc_mint::id()).unwrap(),
+                usdc_reserve.pubkey,
+                solend_program::id(),
+                lending_market.pubkey,
+                Pubkey::find_program_address(
+                    &[lending_market.pubkey.as_ref()],
+                    &solend_program::id(),
+                )
+                .0,
+            )],
+            None,
+        )
+        .await
+        .unwrap_err()
+        .unwrap();
+
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::FlashBorrowCpi as u32)
+        )
+    );
+}
+
 #[tokio::test]
 async fn test_fail_cpi_repay() {
     let (mut test, lending_market, usdc_reserve, user, host_fee_receiver, _) =
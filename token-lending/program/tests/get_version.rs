@@ -0,0 +1,38 @@
+#![cfg(feature = "test-bpf")]
+
+mod helpers;
+
+use helpers::solend_program_test::SolendProgramTest;
+use solana_program_test::*;
+use solana_sdk::{signature::Signer, transaction::Transaction};
+use solend_program::version::{ProgramVersion, FEATURE_FLASH_LOANS, FEATURE_RATE_LIMITS};
+use solend_sdk::instruction::get_version;
+
+#[tokio::test]
+async fn test_get_version() {
+    let mut test = SolendProgramTest::start_new().await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[get_version(solend_program::id())],
+        Some(&test.context.payer.pubkey()),
+        &[&test.context.payer],
+        test.context.last_blockhash,
+    );
+    let simulation = test
+        .context
+        .banks_client
+        .simulate_transaction(transaction)
+        .await
+        .unwrap();
+    assert_eq!(simulation.result, Some(Ok(())));
+
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, solend_program::id());
+
+    let version = ProgramVersion::unpack(&return_data.data).unwrap();
+    assert_eq!(
+        format!("{}.{}.{}", version.major, version.minor, version.patch),
+        env!("CARGO_PKG_VERSION")
+    );
+    assert!(version.supports(FEATURE_FLASH_LOANS | FEATURE_RATE_LIMITS));
+}
@@ -0,0 +1,200 @@
+//! Compute unit reports from programs built with the `profile-cu` feature. The checkpoints the
+//! processor logs are read back from the transaction logs, and the units spent between
+//! consecutive checkpoints are aggregated per instruction path.
+
+use super::solend_program_test::SolendProgramTest;
+use solana_program::instruction::Instruction;
+use solana_program_test::BanksClientError;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use solend_program::profile::CHECKPOINT_PREFIX;
+use std::{collections::BTreeMap, fmt};
+
+/// A checkpoint read back from the logs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub label: String,
+    /// Compute units remaining when the checkpoint was logged
+    pub remaining: u64,
+}
+
+/// Checkpoints logged by each top level instruction, in order. Instructions that logged no
+/// checkpoints are left out.
+pub fn parse_checkpoints(logs: &[String]) -> Vec<Vec<Checkpoint>> {
+    let mut invocations = vec![];
+    let mut checkpoints: Vec<Checkpoint> = vec![];
+    let mut label = None;
+
+    for log in logs {
+        if log.starts_with("Program ") && log.ends_with(" invoke [1]") {
+            invocations.push(std::mem::take(&mut checkpoints));
+            label = None;
+        } else if let Some(name) = log
+            .strip_prefix("Program log: ")
+            .and_then(|log| log.strip_prefix(CHECKPOINT_PREFIX))
+        {
+            label = Some(name.to_string());
+        } else if let Some(remaining) = log
+            .strip_prefix("Program consumption: ")
+            .and_then(|log| log.strip_suffix(" units remaining"))
+            .and_then(|remaining| remaining.parse().ok())
+        {
+            // a consumption line only counts when it directly follows a checkpoint label
+            if let Some(label) = label.take() {
+                checkpoints.push(Checkpoint { label, remaining });
+            }
+        }
+    }
+    invocations.push(checkpoints);
+
+    invocations
+        .into_iter()
+        .filter(|checkpoints| !checkpoints.is_empty())
+        .collect()
+}
+
+/// Units spent over a number of runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CuStats {
+    pub runs: u64,
+    pub min: u64,
+    pub max: u64,
+    pub total: u64,
+}
+
+impl CuStats {
+    fn new(units: u64) -> Self {
+        Self {
+            runs: 1,
+            min: units,
+            max: units,
+            total: units,
+        }
+    }
+
+    fn record(&mut self, units: u64) {
+        self.runs += 1;
+        self.min = self.min.min(units);
+        self.max = self.max.max(units);
+        self.total += units;
+    }
+
+    pub fn avg(&self) -> u64 {
+        self.total / self.runs
+    }
+}
+
+/// Units spent along each path through the processor. A path is the sequence of checkpoint
+/// labels one instruction logged, and its segments are the steps between consecutive
+/// checkpoints.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CuReport {
+    pub paths: BTreeMap<Vec<String>, PathReport>,
+}
+
+/// Units spent along one path, from its first checkpoint to its last
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathReport {
+    pub total: CuStats,
+    /// Units spent between checkpoint `i` and `i + 1`
+    pub segments: Vec<CuStats>,
+}
+
+impl CuReport {
+    /// Adds the checkpoints of every instruction in `logs`
+    pub fn record_logs(&mut self, logs: &[String]) {
+        for checkpoints in parse_checkpoints(logs) {
+            self.record(&checkpoints);
+        }
+    }
+
+    /// Adds one run of a path
+    pub fn record(&mut self, checkpoints: &[Checkpoint]) {
+        let path = checkpoints.iter().map(|c| c.label.clone()).collect();
+        let segments: Vec<u64> = checkpoints
+            .windows(2)
+            .map(|pair| pair[0].remaining.saturating_sub(pair[1].remaining))
+            .collect();
+        let total = segments.iter().sum();
+
+        match self.paths.get_mut(&path) {
+            Some(report) => {
+                report.total.record(total);
+                for (stats, units) in report.segments.iter_mut().zip(segments) {
+                    stats.record(units);
+                }
+            }
+            None => {
+                self.paths.insert(
+                    path,
+                    PathReport {
+                        total: CuStats::new(total),
+                        segments: segments.into_iter().map(CuStats::new).collect(),
+                    },
+                );
+            }
+        }
+    }
+}
+
+impl fmt::Display for CuReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (path, report) in &self.paths {
+            writeln!(
+                f,
+                "{} ({} runs): min {} avg {} max {}",
+                path.join(" > "),
+                report.total.runs,
+                report.total.min,
+                report.total.avg(),
+                report.total.max,
+            )?;
+            for (labels, stats) in path.windows(2).zip(&report.segments) {
+                writeln!(
+                    f,
+                    "  {} -> {}: min {} avg {} max {}",
+                    labels[0],
+                    labels[1],
+                    stats.min,
+                    stats.avg(),
+                    stats.max,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SolendProgramTest {
+    /// Simulates a transaction and adds its checkpoints to `report`. The lending program must
+    /// be built with the `profile-cu` feature for it to log any.
+    pub async fn profile_transaction(
+        &mut self,
+        report: &mut CuReport,
+        instructions: &[Instruction],
+        signers: Option<&[&Keypair]>,
+    ) -> Result<(), BanksClientError> {
+        let mut transaction =
+            Transaction::new_with_payer(instructions, Some(&self.context.payer.pubkey()));
+        let mut all_signers = vec![&self.context.payer];
+        if let Some(signers) = signers {
+            all_signers.extend_from_slice(signers);
+        }
+        transaction.sign(&all_signers, self.context.last_blockhash);
+
+        let simulation = self
+            .context
+            .banks_client
+            .simulate_transaction(transaction)
+            .await?;
+        if let Some(details) = simulation.simulation_details {
+            report.record_logs(&details.logs);
+        }
+        simulation
+            .result
+            .unwrap_or(Ok(()))
+            .map_err(BanksClientError::TransactionError)
+    }
+}
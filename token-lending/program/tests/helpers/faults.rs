@@ -0,0 +1,139 @@
+//! Failure injection for error-path tests. Each fault writes the failing state straight into the
+//! account or sysvar, so a scenario can break an oracle, a token account or the clock at any
+//! point without arranging it through instructions.
+
+use super::{load_mut, solend_program_test::SolendProgramTest};
+use pyth_sdk_solana::state::{PriceAccount, PriceStatus};
+use solana_program::{clock::Clock, program_pack::Pack, pubkey::Pubkey};
+use solana_sdk::account::{Account, AccountSharedData};
+use spl_token::state::{Account as Token, AccountState};
+use switchboard_v2::AggregatorAccountData;
+
+/// How an oracle feed fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleFault {
+    /// Last published `slots_ago` slots before the current slot. The lending program rejects
+    /// prices older than 240 slots.
+    Stale { slots_ago: u64 },
+    /// Published but without a usable price: a halted Pyth price, or a Switchboard round with
+    /// fewer results than the feed requires
+    NoPrice,
+    /// The feed account is deleted
+    Absent,
+}
+
+/// How a token account fails when the token program processes it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenFault {
+    /// Frozen, so transfers out of and into it fail
+    Frozen,
+    /// Holds only `amount`, so larger transfers fail with insufficient funds. The mint supply
+    /// isn't adjusted.
+    Balance(u64),
+}
+
+/// The aggregator of a Switchboard v2 feed, which follows the 8 byte account discriminator
+fn aggregator(data: &mut [u8]) -> &mut AggregatorAccountData {
+    bytemuck::from_bytes_mut(&mut data[8..std::mem::size_of::<AggregatorAccountData>() + 8])
+}
+
+impl SolendProgramTest {
+    async fn get_raw_account(&mut self, pubkey: &Pubkey) -> Account {
+        self.context
+            .banks_client
+            .get_account(*pubkey)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {} doesn't exist", pubkey))
+    }
+
+    fn set_raw_account(&mut self, pubkey: &Pubkey, account: Account) {
+        self.context
+            .set_account(pubkey, &AccountSharedData::from(account));
+    }
+
+    /// Warps ahead so the clock is at least `slot`, leaving room to backdate a publish slot
+    async fn warp_to_at_least(&mut self, slot: u64) -> Clock {
+        let clock = self.get_clock().await;
+        if clock.slot < slot {
+            self.context.warp_to_slot(slot).unwrap();
+            return self.get_clock().await;
+        }
+        clock
+    }
+
+    /// Breaks the Pyth price feed of `mint`
+    pub async fn inject_pyth_fault(&mut self, mint: &Pubkey, fault: OracleFault) {
+        let pubkey = self.mints.get(mint).unwrap().unwrap().pyth_price_pubkey;
+        let account = match fault {
+            OracleFault::Stale { slots_ago } => {
+                let published_slot = self.warp_to_at_least(slots_ago).await.slot - slots_ago;
+                let mut account = self.get_raw_account(&pubkey).await;
+                let price: &mut PriceAccount = load_mut(&mut account.data).unwrap();
+                price.agg.pub_slot = published_slot;
+                price.last_slot = published_slot;
+                account
+            }
+            OracleFault::NoPrice => {
+                let mut account = self.get_raw_account(&pubkey).await;
+                let price: &mut PriceAccount = load_mut(&mut account.data).unwrap();
+                price.agg.status = PriceStatus::Halted;
+                account
+            }
+            OracleFault::Absent => Account::default(),
+        };
+        self.set_raw_account(&pubkey, account);
+    }
+
+    /// Breaks the Switchboard feed of `mint`
+    pub async fn inject_switchboard_fault(&mut self, mint: &Pubkey, fault: OracleFault) {
+        let pubkey = self
+            .mints
+            .get(mint)
+            .unwrap()
+            .unwrap()
+            .switchboard_feed_pubkey
+            .expect("switchboard feed not initialized");
+        let account = match fault {
+            OracleFault::Stale { slots_ago } => {
+                let open_slot = self.warp_to_at_least(slots_ago).await.slot - slots_ago;
+                let mut account = self.get_raw_account(&pubkey).await;
+                aggregator(&mut account.data)
+                    .latest_confirmed_round
+                    .round_open_slot = open_slot;
+                account
+            }
+            OracleFault::NoPrice => {
+                let mut account = self.get_raw_account(&pubkey).await;
+                let aggregator = aggregator(&mut account.data);
+                aggregator.min_oracle_results = 2;
+                aggregator.latest_confirmed_round.num_success = 1;
+                account
+            }
+            OracleFault::Absent => Account::default(),
+        };
+        self.set_raw_account(&pubkey, account);
+    }
+
+    /// Breaks `token_account` for the token program
+    pub async fn inject_token_fault(&mut self, token_account: &Pubkey, fault: TokenFault) {
+        let mut account = self.get_raw_account(token_account).await;
+        let mut token = Token::unpack(&account.data).unwrap();
+        match fault {
+            TokenFault::Frozen => token.state = AccountState::Frozen,
+            TokenFault::Balance(amount) => token.amount = amount,
+        }
+        Token::pack(token, &mut account.data).unwrap();
+        self.set_raw_account(token_account, account);
+    }
+
+    /// Jumps the clock's `unix_timestamp` by `seconds`, forwards or backwards, without moving
+    /// the slot, as when a validator's clock drifts from the slot schedule
+    pub async fn skip_clock(&mut self, seconds: i64) {
+        let clock = self.get_clock().await;
+        self.context.set_sysvar(&Clock {
+            unix_timestamp: clock.unix_timestamp + seconds,
+            ..clock
+        });
+    }
+}
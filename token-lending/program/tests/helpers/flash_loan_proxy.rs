@@ -31,6 +31,10 @@ pub enum FlashLoanProxyInstruction {
         liquidity_amount: u64,
         borrow_instruction_index: u8,
     },
+    /// ProxyBorrow invoked through this program again, so the flash borrow is a nested CPI
+    NestedProxyBorrow {
+        liquidity_amount: u64,
+    },
 }
 
 pub fn process_instruction(
@@ -67,6 +71,10 @@ impl Processor {
                     program_id,
                 )
             }
+            FlashLoanProxyInstruction::NestedProxyBorrow { liquidity_amount } => {
+                msg!("Instruction: Proxy Nested Borrow");
+                Self::process_proxy_nested_borrow(accounts, liquidity_amount, program_id)
+            }
         }
     }
 
@@ -133,6 +141,38 @@ impl Processor {
     }
 }
 
+impl Processor {
+    fn process_proxy_nested_borrow(
+        accounts: &[AccountInfo],
+        liquidity_amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let source_liquidity_info = next_account_info(account_info_iter)?;
+        let destination_liquidity_info = next_account_info(account_info_iter)?;
+        let reserve_info = next_account_info(account_info_iter)?;
+        let token_lending_info = next_account_info(account_info_iter)?;
+        let lending_market_info = next_account_info(account_info_iter)?;
+        let lending_market_authority_info = next_account_info(account_info_iter)?;
+
+        invoke(
+            &borrow_proxy(
+                *program_id,
+                liquidity_amount,
+                *source_liquidity_info.key,
+                *destination_liquidity_info.key,
+                *reserve_info.key,
+                *token_lending_info.key,
+                *lending_market_info.key,
+                *lending_market_authority_info.key,
+            ),
+            accounts,
+        )?;
+
+        Ok(())
+    }
+}
+
 impl FlashLoanProxyInstruction {
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
         let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
@@ -149,6 +189,9 @@ impl FlashLoanProxyInstruction {
                     borrow_instruction_index,
                 }
             }
+            2 => Self::NestedProxyBorrow {
+                liquidity_amount: Self::unpack_u64(rest)?.0,
+            },
             _ => return Err(InvalidInstruction.into()),
         })
     }
@@ -263,6 +306,35 @@ pub fn borrow_proxy(
     }
 }
 
+/// Creates a 'NestedBorrowProxy' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn nested_borrow_proxy(
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    source_liquidity_pubkey: Pubkey,
+    destination_liquidity_pubkey: Pubkey,
+    reserve_pubkey: Pubkey,
+    token_lending_pubkey: Pubkey,
+    lending_market_pubkey: Pubkey,
+    lending_market_authority_pubkey: Pubkey,
+) -> Instruction {
+    let mut instruction = borrow_proxy(
+        program_id,
+        liquidity_amount,
+        source_liquidity_pubkey,
+        destination_liquidity_pubkey,
+        reserve_pubkey,
+        token_lending_pubkey,
+        lending_market_pubkey,
+        lending_market_authority_pubkey,
+    );
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(program_id, false));
+    instruction.data = FlashLoanProxyInstruction::NestedProxyBorrow { liquidity_amount }.pack();
+    instruction
+}
+
 impl FlashLoanProxyInstruction {
     pub fn pack(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(size_of::<Self>());
@@ -279,6 +351,10 @@ impl FlashLoanProxyInstruction {
                 buf.extend_from_slice(&liquidity_amount.to_le_bytes());
                 buf.extend_from_slice(&borrow_instruction_index.to_le_bytes());
             }
+            Self::NestedProxyBorrow { liquidity_amount } => {
+                buf.push(2);
+                buf.extend_from_slice(&liquidity_amount.to_le_bytes());
+            }
         }
         buf
     }
@@ -1,5 +1,8 @@
 #![allow(dead_code)]
 
+pub mod cu_profile;
+pub mod faults;
+pub mod fixtures;
 pub mod flash_loan_proxy;
 pub mod flash_loan_receiver;
 pub mod genesis;
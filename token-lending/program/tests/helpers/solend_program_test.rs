@@ -12,8 +12,9 @@ use solend_program::state::RateLimiterConfig;
 use solend_sdk::{instruction::update_reserve_config, NULL_PUBKEY};
 
 use pyth_sdk_solana::state::PROD_ACCT_SIZE;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use solana_program::{
-    clock::Clock,
+    clock::{Clock, Epoch, DEFAULT_MS_PER_SLOT},
     instruction::Instruction,
     program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
@@ -22,7 +23,7 @@ use solana_program::{
 };
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction,
-    signature::{Keypair, Signer},
+    signature::{keypair_from_seed, Keypair, Signer},
     system_instruction::create_account,
     transaction::Transaction,
 };
@@ -40,7 +41,10 @@ use solend_program::{
 use spl_token::state::{Account as Token, Mint};
 use std::{
     collections::{HashMap, HashSet},
+    env,
+    future::Future,
     str::FromStr,
+    sync::Arc,
 };
 
 use super::mock_pyth::{init, mock_pyth_program, set_price};
@@ -53,6 +57,33 @@ pub struct SolendProgramTest {
     authority: Keypair,
 
     pub mints: HashMap<Pubkey, Option<Oracle>>,
+
+    // source of all randomness in a test, see `SolendProgramTest::rng`
+    seed: u64,
+    rng: StdRng,
+}
+
+/// Environment variable that fixes the seed of `SolendProgramTest::rng`, to reproduce a failure
+pub const TEST_SEED_VAR: &str = "SOLEND_TEST_SEED";
+
+fn test_seed() -> u64 {
+    match env::var(TEST_SEED_VAR) {
+        Ok(seed) => seed
+            .parse()
+            .unwrap_or_else(|_| panic!("{} must be a u64, got {}", TEST_SEED_VAR, seed)),
+        Err(_) => rand::random(),
+    }
+}
+
+impl Drop for SolendProgramTest {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            eprintln!(
+                "test failed with seed {}, rerun with {}={} to reproduce",
+                self.seed, TEST_SEED_VAR, self.seed
+            );
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -83,7 +114,9 @@ impl SolendProgramTest {
             processor!(flash_loan_proxy::process_instruction),
         );
 
-        let authority = Keypair::new();
+        let seed = test_seed();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let authority = keypair_from_seed(&rng.gen::<[u8; 32]>()).unwrap();
 
         add_mint(&mut test, usdc_mint::id(), 6, authority.pubkey());
         add_mint(&mut test, usdt_mint::id(), 6, authority.pubkey());
@@ -103,6 +136,8 @@ impl SolendProgramTest {
                 (usdt_mint::id(), None),
                 (bonk_mint::id(), None),
             ]),
+            seed,
+            rng,
         }
     }
 
@@ -126,7 +161,9 @@ impl SolendProgramTest {
             processor!(flash_loan_proxy::process_instruction),
         );
 
-        let authority = Keypair::new();
+        let seed = test_seed();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let authority = keypair_from_seed(&rng.gen::<[u8; 32]>()).unwrap();
 
         add_mint(&mut test, usdc_mint::id(), 6, authority.pubkey());
         add_mint(&mut test, usdt_mint::id(), 6, authority.pubkey());
@@ -146,9 +183,52 @@ impl SolendProgramTest {
                 (usdt_mint::id(), None),
                 (bonk_mint::id(), None),
             ]),
+            seed,
+            rng,
         }
     }
 
+    /// Runs `count` copies of `scenario` at once, passing each its index. Each scenario starts its
+    /// own context, with `start_new`, `setup_world` or the like, so contexts share no accounts or
+    /// clock and cases that would otherwise run one after another can run side by side; they only
+    /// run in parallel on a multi-threaded runtime, `#[tokio::test(flavor = "multi_thread")]`. A
+    /// panic in any scenario fails the test.
+    pub async fn run_parallel<F, Fut>(count: usize, scenario: F)
+    where
+        F: Fn(usize) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let scenario = Arc::new(scenario);
+        let handles: Vec<_> = (0..count)
+            .map(|index| {
+                let scenario = scenario.clone();
+                tokio::spawn(async move { scenario(index).await })
+            })
+            .collect();
+
+        for handle in handles {
+            if let Err(err) = handle.await {
+                std::panic::resume_unwind(err.into_panic());
+            }
+        }
+    }
+
+    /// Seed of `rng`, printed when a test panics
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Seeded RNG for users, amounts and orderings. The seed is random unless `SOLEND_TEST_SEED`
+    /// is set, and is printed when the test fails so the run can be reproduced.
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Keypair drawn from `rng`
+    pub fn new_keypair(&mut self) -> Keypair {
+        keypair_from_seed(&self.rng.gen::<[u8; 32]>()).unwrap()
+    }
+
     pub async fn process_transaction(
         &mut self,
         instructions: &[Instruction],
@@ -250,6 +330,31 @@ impl SolendProgramTest {
         self.context.warp_to_slot(clock.slot + slots).unwrap();
     }
 
+    /// Warps to the first slot of `epoch`
+    pub async fn warp_to_epoch(&mut self, epoch: Epoch) {
+        let slot = self
+            .context
+            .genesis_config()
+            .epoch_schedule
+            .get_first_slot_in_epoch(epoch);
+        self.context.warp_to_slot(slot).unwrap();
+    }
+
+    /// Advances the clock by `seconds`: warps ahead the slots that take at the default slot
+    /// time, then moves `unix_timestamp` forward by exactly `seconds`, since a warp alone doesn't
+    /// advance it.
+    pub async fn warp_seconds(&mut self, seconds: u64) {
+        let clock = self.get_clock().await;
+        let slots = (seconds * 1_000 / DEFAULT_MS_PER_SLOT).max(1);
+        self.context.warp_to_slot(clock.slot + slots).unwrap();
+
+        let warped = self.get_clock().await;
+        self.context.set_sysvar(&Clock {
+            unix_timestamp: clock.unix_timestamp + seconds as i64,
+            ..warped
+        });
+    }
+
     pub async fn create_account(
         &mut self,
         size: usize,
@@ -258,7 +363,7 @@ impl SolendProgramTest {
     ) -> Pubkey {
         let rent = self.rent.minimum_balance(size);
 
-        let new_keypair = Keypair::new();
+        let new_keypair = self.new_keypair();
         let keypair = match keypair {
             None => &new_keypair,
             Some(kp) => kp,
@@ -280,7 +385,7 @@ impl SolendProgramTest {
     }
 
     pub async fn create_mint(&mut self, mint_authority: &Pubkey) -> Pubkey {
-        let keypair = Keypair::new();
+        let keypair = self.new_keypair();
         let rent = self.rent.minimum_balance(Mint::LEN);
 
         let instructions = [
@@ -309,7 +414,7 @@ impl SolendProgramTest {
     }
 
     pub async fn create_token_account(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
-        let keypair = Keypair::new();
+        let keypair = self.new_keypair();
         let instructions = [
             system_instruction::create_account(
                 &self.context.payer.pubkey(),
@@ -334,6 +439,40 @@ impl SolendProgramTest {
         keypair.pubkey()
     }
 
+    /// Creates an spl-token `m` of `n` multisig, with its address and members drawn from `rng`
+    pub async fn create_multisig(&mut self, m: u8, n: usize) -> Multisig {
+        let keypair = self.new_keypair();
+        let members: Vec<Keypair> = (0..n).map(|_| self.new_keypair()).collect();
+        let member_pubkeys: Vec<Pubkey> = members.iter().map(|member| member.pubkey()).collect();
+
+        let instructions = [
+            system_instruction::create_account(
+                &self.context.payer.pubkey(),
+                &keypair.pubkey(),
+                self.rent.minimum_balance(spl_token::state::Multisig::LEN),
+                spl_token::state::Multisig::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_multisig(
+                &spl_token::id(),
+                &keypair.pubkey(),
+                &member_pubkeys.iter().collect::<Vec<_>>(),
+                m,
+            )
+            .unwrap(),
+        ];
+
+        self.process_transaction(&instructions, Some(&[&keypair]))
+            .await
+            .unwrap();
+
+        Multisig {
+            pubkey: keypair.pubkey(),
+            m,
+            members,
+        }
+    }
+
     pub async fn mint_to(&mut self, mint: &Pubkey, dst: &Pubkey, amount: u64) {
         assert!(self.mints.contains_key(mint));
 
@@ -583,7 +722,7 @@ impl User {
         mints_and_balances: &[(&Pubkey, u64)],
     ) -> Self {
         let mut user = User {
-            keypair: Keypair::new(),
+            keypair: test.new_keypair(),
             token_accounts: Vec::new(),
         };
 
@@ -664,6 +803,60 @@ impl User {
     }
 }
 
+/// An spl-token multisig and the keypairs of all its members
+#[derive(Debug)]
+pub struct Multisig {
+    pub pubkey: Pubkey,
+    pub m: u8,
+    pub members: Vec<Keypair>,
+}
+
+impl Multisig {
+    /// The first `count` members
+    pub fn signers(&self, count: usize) -> Vec<&Keypair> {
+        self.members.iter().take(count).collect()
+    }
+
+    /// Runs a token instruction with the multisig as authority, signed by the first
+    /// `signer_count` members. `build` gets the multisig and the signing members' pubkeys.
+    pub async fn sign_token_instruction(
+        &self,
+        test: &mut SolendProgramTest,
+        signer_count: usize,
+        build: impl FnOnce(&Pubkey, &[&Pubkey]) -> Instruction,
+    ) -> Result<(), BanksClientError> {
+        let signers = self.signers(signer_count);
+        let signer_pubkeys: Vec<Pubkey> = signers.iter().map(|signer| signer.pubkey()).collect();
+        let instruction = build(&self.pubkey, &signer_pubkeys.iter().collect::<Vec<_>>());
+        test.process_transaction(&[instruction], Some(&signers))
+            .await
+    }
+
+    /// Transfers from a token account the multisig owns, signed by the first `signer_count`
+    /// members
+    pub async fn transfer(
+        &self,
+        test: &mut SolendProgramTest,
+        source: &Pubkey,
+        destination: &Pubkey,
+        amount: u64,
+        signer_count: usize,
+    ) -> Result<(), BanksClientError> {
+        self.sign_token_instruction(test, signer_count, |multisig, signers| {
+            spl_token::instruction::transfer(
+                &spl_token::id(),
+                source,
+                destination,
+                multisig,
+                signers,
+                amount,
+            )
+            .unwrap()
+        })
+        .await
+    }
+}
+
 pub struct PriceArgs {
     pub price: i64,
     pub conf: u64,
@@ -1456,8 +1649,9 @@ pub async fn setup_world(
     )
     .await;
 
+    let lending_market_keypair = test.new_keypair();
     let lending_market = test
-        .init_lending_market(&lending_market_owner, &Keypair::new())
+        .init_lending_market(&lending_market_owner, &lending_market_keypair)
         .await
         .unwrap();
 
@@ -1489,26 +1683,28 @@ pub async fn setup_world(
     )
     .await;
 
+    let usdc_reserve_keypair = test.new_keypair();
     let usdc_reserve = test
         .init_reserve(
             &lending_market,
             &lending_market_owner,
             &usdc_mint::id(),
             usdc_reserve_config,
-            &Keypair::new(),
+            &usdc_reserve_keypair,
             1_000_000,
             None,
         )
         .await
         .unwrap();
 
+    let wsol_reserve_keypair = test.new_keypair();
     let wsol_reserve = test
         .init_reserve(
             &lending_market,
             &lending_market_owner,
             &wsol_mint::id(),
             wsol_reserve_config,
-            &Keypair::new(),
+            &wsol_reserve_keypair,
             LAMPORTS_TO_SOL,
             None,
         )
@@ -1566,8 +1762,9 @@ pub async fn scenario_1(
         setup_world(usdc_reserve_config, wsol_reserve_config).await;
 
     // init obligation
+    let obligation_keypair = test.new_keypair();
     let obligation = lending_market
-        .init_obligation(&mut test, Keypair::new(), &user)
+        .init_obligation(&mut test, obligation_keypair, &user)
         .await
         .expect("This should succeed");
 
@@ -1686,8 +1883,9 @@ pub async fn custom_scenario(
     let lending_market_owner =
         User::new_with_balances(&mut test, &mints_and_liquidity_amounts).await;
 
+    let lending_market_keypair = test.new_keypair();
     let lending_market = test
-        .init_lending_market(&lending_market_owner, &Keypair::new())
+        .init_lending_market(&lending_market_owner, &lending_market_keypair)
         .await
         .unwrap();
 
@@ -1704,8 +1902,9 @@ pub async fn custom_scenario(
         )
         .await;
 
+        let obligation_keypair = test.new_keypair();
         let obligation = lending_market
-            .init_obligation(&mut test, Keypair::new(), &obligation_owner)
+            .init_obligation(&mut test, obligation_keypair, &obligation_owner)
             .await
             .unwrap();
 
@@ -1721,13 +1920,14 @@ pub async fn custom_scenario(
 
         test.set_price(&reserve_arg.mint, &reserve_arg.price).await;
 
+        let reserve_keypair = test.new_keypair();
         let reserve = test
             .init_reserve(
                 &lending_market,
                 &lending_market_owner,
                 &reserve_arg.mint,
                 &reserve_arg.config,
-                &Keypair::new(),
+                &reserve_keypair,
                 reserve_arg.liquidity_amount,
                 None,
             )
@@ -0,0 +1,217 @@
+#![cfg(feature = "test-bpf")]
+
+mod helpers;
+
+use helpers::solend_program_test::{setup_world, Info, Multisig, SolendProgramTest, User};
+use helpers::*;
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use solend_program::error::LendingError;
+use solend_program::instruction::deposit_reserve_liquidity;
+use solend_program::state::{LendingMarket, RateLimiterConfig, Reserve};
+use spl_token::state::Account as Token;
+
+async fn setup() -> (
+    SolendProgramTest,
+    Info<LendingMarket>,
+    Info<Reserve>,
+    User,
+    Multisig,
+) {
+    let (mut test, lending_market, usdc_reserve, _, lending_market_owner, _) =
+        setup_world(&test_reserve_config(), &test_reserve_config()).await;
+    let multisig = test.create_multisig(2, 3).await;
+
+    (
+        test,
+        lending_market,
+        usdc_reserve,
+        lending_market_owner,
+        multisig,
+    )
+}
+
+async fn token_amount(test: &mut SolendProgramTest, token_account: &Pubkey) -> u64 {
+    test.load_account::<Token>(*token_account)
+        .await
+        .account
+        .amount
+}
+
+#[tokio::test]
+async fn test_multisig_transfer() {
+    let (mut test, _, _, _, multisig) = setup().await;
+
+    let source = test
+        .create_token_account(&multisig.pubkey, &usdc_mint::id())
+        .await;
+    let destination = test
+        .create_token_account(&Pubkey::new_unique(), &usdc_mint::id())
+        .await;
+    test.mint_to(&usdc_mint::id(), &source, 1_000_000).await;
+
+    // fewer than m members
+    let res = multisig
+        .transfer(&mut test, &source, &destination, 1_000, 1)
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature)
+    );
+
+    multisig
+        .transfer(&mut test, &source, &destination, 1_000, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(token_amount(&mut test, &source).await, 999_000);
+    assert_eq!(token_amount(&mut test, &destination).await, 1_000);
+}
+
+#[tokio::test]
+async fn test_multisig_delegate_deposit() {
+    let (mut test, lending_market, usdc_reserve, _, multisig) = setup().await;
+
+    let source_liquidity = test
+        .create_token_account(&multisig.pubkey, &usdc_mint::id())
+        .await;
+    let destination_collateral = test
+        .create_token_account(
+            &multisig.pubkey,
+            &usdc_reserve.account.collateral.mint_pubkey,
+        )
+        .await;
+    test.mint_to(&usdc_mint::id(), &source_liquidity, 1_000_000)
+        .await;
+
+    // a multisig can't sign a lending instruction itself, so it approves a delegate to act as
+    // the transfer authority
+    let delegate = test.new_keypair();
+    multisig
+        .sign_token_instruction(&mut test, 2, |multisig, signers| {
+            spl_token::instruction::approve(
+                &spl_token::id(),
+                &source_liquidity,
+                &delegate.pubkey(),
+                multisig,
+                signers,
+                400_000,
+            )
+            .unwrap()
+        })
+        .await
+        .unwrap();
+
+    let deposit = |amount| {
+        deposit_reserve_liquidity(
+            solend_program::id(),
+            amount,
+            source_liquidity,
+            destination_collateral,
+            usdc_reserve.pubkey,
+            usdc_reserve.account.liquidity.supply_pubkey,
+            usdc_reserve.account.collateral.mint_pubkey,
+            lending_market.pubkey,
+            delegate.pubkey(),
+        )
+    };
+
+    test.process_transaction(&[deposit(400_000)], Some(&[&delegate]))
+        .await
+        .unwrap();
+
+    assert_eq!(token_amount(&mut test, &source_liquidity).await, 600_000);
+    assert_eq!(
+        token_amount(&mut test, &destination_collateral).await,
+        400_000
+    );
+
+    // the allowance is used up
+    let res = test
+        .process_transaction(&[deposit(1)], Some(&[&delegate]))
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        res,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LendingError::TokenTransferFailed as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_multisig_market_owner() {
+    let (mut test, lending_market, usdc_reserve, lending_market_owner, multisig) = setup().await;
+
+    lending_market
+        .set_lending_market_owner_and_config(
+            &mut test,
+            &lending_market_owner,
+            &multisig.pubkey,
+            RateLimiterConfig::default(),
+            None,
+            lending_market.account.risk_authority,
+        )
+        .await
+        .unwrap();
+
+    // spl-token multisigs only authorize token instructions, so no member can act as the
+    // market owner
+    for member in &multisig.members {
+        let member = User::new_with_keypair(Keypair::from_bytes(&member.to_bytes()).unwrap());
+
+        let res = lending_market
+            .set_lending_market_owner_and_config(
+                &mut test,
+                &member,
+                &member.keypair.pubkey(),
+                RateLimiterConfig::default(),
+                None,
+                lending_market.account.risk_authority,
+            )
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            res,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LendingError::InvalidMarketOwner as u32)
+            )
+        );
+
+        let res = lending_market
+            .update_reserve_config(
+                &mut test,
+                &member,
+                &usdc_reserve,
+                usdc_reserve.account.config,
+                RateLimiterConfig::default(),
+                None,
+            )
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            res,
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(LendingError::InvalidSigner as u32)
+            )
+        );
+    }
+
+    let lending_market = test
+        .load_account::<LendingMarket>(lending_market.pubkey)
+        .await;
+    assert_eq!(lending_market.account.owner, multisig.pubkey);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "test-bpf")]
+
+mod helpers;
+
+use helpers::solend_program_test::SolendProgramTest;
+use solana_program_test::*;
+
+#[tokio::test]
+async fn test_warp_to_epoch() {
+    let mut test = SolendProgramTest::start_new().await;
+
+    test.warp_to_epoch(3).await;
+
+    let clock = test.get_clock().await;
+    let epoch_schedule = test.context.genesis_config().epoch_schedule;
+    assert_eq!(clock.epoch, 3);
+    assert_eq!(clock.slot, epoch_schedule.get_first_slot_in_epoch(3));
+}
+
+#[tokio::test]
+async fn test_warp_seconds() {
+    let mut test = SolendProgramTest::start_new().await;
+    let before = test.get_clock().await;
+
+    test.warp_seconds(3_600).await;
+
+    let after = test.get_clock().await;
+    assert_eq!(after.unix_timestamp, before.unix_timestamp + 3_600);
+    // 400ms slots
+    assert_eq!(after.slot, before.slot + 9_000);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_run_parallel() {
+    SolendProgramTest::run_parallel(4, |index| async move {
+        let mut test = SolendProgramTest::start_new().await;
+        let epoch = index as u64 + 1;
+        test.warp_to_epoch(epoch).await;
+
+        // every context has its own clock
+        assert_eq!(test.get_clock().await.epoch, epoch);
+    })
+    .await;
+}
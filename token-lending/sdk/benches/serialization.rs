@@ -0,0 +1,645 @@
+//! Compares the manual `Pack` layouts of `Reserve` and `Obligation` against borsh and a
+//! zero-copy bytemuck view of the same bytes. Each format is measured decoding a whole account,
+//! encoding a whole account, and the refresh pattern: decode, update the fields a refresh
+//! touches, encode.
+//!
+//! `reserve_view` measures what refreshing an obligation reads from each deposit reserve, what a
+//! borrow reads and writes on the borrow reserve, and what a liquidation reads from the withdraw
+//! reserve, through `Reserve::unpack` and `Pack` and through `ReserveView`.
+//!
+//! Run with `cargo bench -p solend-sdk --bench serialization`; results are summarized in
+//! `benches/README.md`.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{from_bytes, from_bytes_mut, Pod, Zeroable};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solend_sdk::{
+    math::Decimal,
+    state::{
+        LastUpdate, Obligation, ObligationCollateral, ObligationLiquidity, Reserve,
+        ReserveCollateral, ReserveLiquidity, ReserveView,
+    },
+};
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+const SLOT: u64 = 1_000;
+const PRICE: u128 = 21_500_000_000_000_000_000;
+
+fn sample_reserve() -> Reserve {
+    Reserve {
+        version: 1,
+        last_update: LastUpdate {
+            slot: 1,
+            stale: true,
+        },
+        lending_market: Pubkey::new_unique(),
+        liquidity: ReserveLiquidity {
+            mint_pubkey: Pubkey::new_unique(),
+            mint_decimals: 9,
+            supply_pubkey: Pubkey::new_unique(),
+            pyth_oracle_pubkey: Pubkey::new_unique(),
+            switchboard_oracle_pubkey: Pubkey::new_unique(),
+            available_amount: 1_000_000_000,
+            borrowed_amount_wads: Decimal::from(500_000_000u64),
+            cumulative_borrow_rate_wads: Decimal::one(),
+            accumulated_protocol_fees_wads: Decimal::from(1_000u64),
+            market_price: Decimal::from(20u64),
+            smoothed_market_price: Decimal::from(20u64),
+        },
+        collateral: ReserveCollateral {
+            mint_pubkey: Pubkey::new_unique(),
+            mint_total_supply: 1_000_000_000,
+            supply_pubkey: Pubkey::new_unique(),
+        },
+        ..Reserve::default()
+    }
+}
+
+fn sample_obligation() -> Obligation {
+    Obligation {
+        version: 1,
+        last_update: LastUpdate {
+            slot: 1,
+            stale: true,
+        },
+        lending_market: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        deposits: (0..5)
+            .map(|_| ObligationCollateral {
+                deposit_reserve: Pubkey::new_unique(),
+                deposited_amount: 1_000_000,
+                market_value: Decimal::from(100u64),
+            })
+            .collect(),
+        borrows: (0..5)
+            .map(|_| ObligationLiquidity {
+                borrow_reserve: Pubkey::new_unique(),
+                cumulative_borrow_rate_wads: Decimal::one(),
+                borrowed_amount_wads: Decimal::from(10u64),
+                market_value: Decimal::from(10u64),
+            })
+            .collect(),
+        deposited_value: Decimal::from(500u64),
+        borrowed_value: Decimal::from(50u64),
+        ..Obligation::default()
+    }
+}
+
+fn scaled(value: Decimal) -> u128 {
+    value.to_scaled_val().unwrap()
+}
+
+// BORSH
+//
+// Typed mirrors of the packed fields, as a borsh redesign would declare them. Decimals are stored
+// as their u128 scaled value like `Pack` does, and the reserved padding is left out.
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct BorshReserve {
+    version: u8,
+    last_update_slot: u64,
+    last_update_stale: bool,
+    lending_market: Pubkey,
+    liquidity_mint_pubkey: Pubkey,
+    liquidity_mint_decimals: u8,
+    liquidity_supply_pubkey: Pubkey,
+    liquidity_pyth_oracle_pubkey: Pubkey,
+    liquidity_switchboard_oracle_pubkey: Pubkey,
+    liquidity_available_amount: u64,
+    liquidity_borrowed_amount_wads: u128,
+    liquidity_cumulative_borrow_rate_wads: u128,
+    liquidity_market_price: u128,
+    collateral_mint_pubkey: Pubkey,
+    collateral_mint_total_supply: u64,
+    collateral_supply_pubkey: Pubkey,
+    config_optimal_utilization_rate: u8,
+    config_loan_to_value_ratio: u8,
+    config_liquidation_bonus: u8,
+    config_liquidation_threshold: u8,
+    config_min_borrow_rate: u8,
+    config_optimal_borrow_rate: u8,
+    config_max_borrow_rate: u8,
+    config_fees_borrow_fee_wad: u64,
+    config_fees_flash_loan_fee_wad: u64,
+    config_fees_host_fee_percentage: u8,
+    config_deposit_limit: u64,
+    config_borrow_limit: u64,
+    config_fee_receiver: Pubkey,
+    config_protocol_liquidation_fee: u8,
+    config_protocol_take_rate: u8,
+    liquidity_accumulated_protocol_fees_wads: u128,
+    rate_limiter_window_duration: u64,
+    rate_limiter_max_outflow: u64,
+    rate_limiter_prev_qty: u128,
+    rate_limiter_window_start: u64,
+    rate_limiter_cur_qty: u128,
+    config_added_borrow_weight_bps: u64,
+    liquidity_smoothed_market_price: u128,
+    config_reserve_type: u8,
+    config_max_utilization_rate: u8,
+    config_super_max_borrow_rate: u64,
+    config_max_liquidation_bonus: u8,
+    config_max_liquidation_threshold: u8,
+}
+
+impl From<&Reserve> for BorshReserve {
+    fn from(reserve: &Reserve) -> Self {
+        Self {
+            version: reserve.version,
+            last_update_slot: reserve.last_update.slot,
+            last_update_stale: reserve.last_update.stale,
+            lending_market: reserve.lending_market,
+            liquidity_mint_pubkey: reserve.liquidity.mint_pubkey,
+            liquidity_mint_decimals: reserve.liquidity.mint_decimals,
+            liquidity_supply_pubkey: reserve.liquidity.supply_pubkey,
+            liquidity_pyth_oracle_pubkey: reserve.liquidity.pyth_oracle_pubkey,
+            liquidity_switchboard_oracle_pubkey: reserve.liquidity.switchboard_oracle_pubkey,
+            liquidity_available_amount: reserve.liquidity.available_amount,
+            liquidity_borrowed_amount_wads: scaled(reserve.liquidity.borrowed_amount_wads),
+            liquidity_cumulative_borrow_rate_wads: scaled(
+                reserve.liquidity.cumulative_borrow_rate_wads,
+            ),
+            liquidity_market_price: scaled(reserve.liquidity.market_price),
+            collateral_mint_pubkey: reserve.collateral.mint_pubkey,
+            collateral_mint_total_supply: reserve.collateral.mint_total_supply,
+            collateral_supply_pubkey: reserve.collateral.supply_pubkey,
+            config_optimal_utilization_rate: reserve.config.optimal_utilization_rate,
+            config_loan_to_value_ratio: reserve.config.loan_to_value_ratio,
+            config_liquidation_bonus: reserve.config.liquidation_bonus,
+            config_liquidation_threshold: reserve.config.liquidation_threshold,
+            config_min_borrow_rate: reserve.config.min_borrow_rate,
+            config_optimal_borrow_rate: reserve.config.optimal_borrow_rate,
+            config_max_borrow_rate: reserve.config.max_borrow_rate,
+            config_fees_borrow_fee_wad: reserve.config.fees.borrow_fee_wad,
+            config_fees_flash_loan_fee_wad: reserve.config.fees.flash_loan_fee_wad,
+            config_fees_host_fee_percentage: reserve.config.fees.host_fee_percentage,
+            config_deposit_limit: reserve.config.deposit_limit,
+            config_borrow_limit: reserve.config.borrow_limit,
+            config_fee_receiver: reserve.config.fee_receiver,
+            config_protocol_liquidation_fee: reserve.config.protocol_liquidation_fee,
+            config_protocol_take_rate: reserve.config.protocol_take_rate,
+            liquidity_accumulated_protocol_fees_wads: scaled(
+                reserve.liquidity.accumulated_protocol_fees_wads,
+            ),
+            rate_limiter_window_duration: reserve.rate_limiter.config.window_duration,
+            rate_limiter_max_outflow: reserve.rate_limiter.config.max_outflow,
+            rate_limiter_prev_qty: 0,
+            rate_limiter_window_start: 0,
+            rate_limiter_cur_qty: 0,
+            config_added_borrow_weight_bps: reserve.config.added_borrow_weight_bps,
+            liquidity_smoothed_market_price: scaled(reserve.liquidity.smoothed_market_price),
+            config_reserve_type: reserve.config.reserve_type as u8,
+            config_max_utilization_rate: reserve.config.max_utilization_rate,
+            config_super_max_borrow_rate: reserve.config.super_max_borrow_rate,
+            config_max_liquidation_bonus: reserve.config.max_liquidation_bonus,
+            config_max_liquidation_threshold: reserve.config.max_liquidation_threshold,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct BorshObligationCollateral {
+    deposit_reserve: Pubkey,
+    deposited_amount: u64,
+    market_value: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct BorshObligationLiquidity {
+    borrow_reserve: Pubkey,
+    cumulative_borrow_rate_wads: u128,
+    borrowed_amount_wads: u128,
+    market_value: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct BorshObligation {
+    version: u8,
+    last_update_slot: u64,
+    last_update_stale: bool,
+    lending_market: Pubkey,
+    owner: Pubkey,
+    deposited_value: u128,
+    borrowed_value: u128,
+    allowed_borrow_value: u128,
+    unhealthy_borrow_value: u128,
+    borrowed_value_upper_bound: u128,
+    borrowing_isolated_asset: bool,
+    super_unhealthy_borrow_value: u128,
+    deposits: Vec<BorshObligationCollateral>,
+    borrows: Vec<BorshObligationLiquidity>,
+}
+
+impl From<&Obligation> for BorshObligation {
+    fn from(obligation: &Obligation) -> Self {
+        Self {
+            version: obligation.version,
+            last_update_slot: obligation.last_update.slot,
+            last_update_stale: obligation.last_update.stale,
+            lending_market: obligation.lending_market,
+            owner: obligation.owner,
+            deposited_value: scaled(obligation.deposited_value),
+            borrowed_value: scaled(obligation.borrowed_value),
+            allowed_borrow_value: scaled(obligation.allowed_borrow_value),
+            unhealthy_borrow_value: scaled(obligation.unhealthy_borrow_value),
+            borrowed_value_upper_bound: scaled(obligation.borrowed_value_upper_bound),
+            borrowing_isolated_asset: obligation.borrowing_isolated_asset,
+            super_unhealthy_borrow_value: scaled(obligation.super_unhealthy_borrow_value),
+            deposits: obligation
+                .deposits
+                .iter()
+                .map(|deposit| BorshObligationCollateral {
+                    deposit_reserve: deposit.deposit_reserve,
+                    deposited_amount: deposit.deposited_amount,
+                    market_value: scaled(deposit.market_value),
+                })
+                .collect(),
+            borrows: obligation
+                .borrows
+                .iter()
+                .map(|borrow| BorshObligationLiquidity {
+                    borrow_reserve: borrow.borrow_reserve,
+                    cumulative_borrow_rate_wads: scaled(borrow.cumulative_borrow_rate_wads),
+                    borrowed_amount_wads: scaled(borrow.borrowed_amount_wads),
+                    market_value: scaled(borrow.market_value),
+                })
+                .collect(),
+        }
+    }
+}
+
+// ZERO-COPY
+//
+// Byte-for-byte views of the existing packed layouts. Every field is a byte array so the structs
+// have an alignment of 1 and can be cast from account data at any offset.
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct PodReserve {
+    version: u8,
+    last_update_slot: [u8; 8],
+    last_update_stale: u8,
+    lending_market: [u8; 32],
+    liquidity_mint_pubkey: [u8; 32],
+    liquidity_mint_decimals: u8,
+    liquidity_supply_pubkey: [u8; 32],
+    liquidity_pyth_oracle_pubkey: [u8; 32],
+    liquidity_switchboard_oracle_pubkey: [u8; 32],
+    liquidity_available_amount: [u8; 8],
+    liquidity_borrowed_amount_wads: [u8; 16],
+    liquidity_cumulative_borrow_rate_wads: [u8; 16],
+    liquidity_market_price: [u8; 16],
+    collateral_mint_pubkey: [u8; 32],
+    collateral_mint_total_supply: [u8; 8],
+    collateral_supply_pubkey: [u8; 32],
+    config_rates: [u8; 7],
+    config_fees_borrow_fee_wad: [u8; 8],
+    config_fees_flash_loan_fee_wad: [u8; 8],
+    config_fees_host_fee_percentage: u8,
+    config_deposit_limit: [u8; 8],
+    config_borrow_limit: [u8; 8],
+    config_fee_receiver: [u8; 32],
+    config_protocol_liquidation_fee: u8,
+    config_protocol_take_rate: u8,
+    liquidity_accumulated_protocol_fees_wads: [u8; 16],
+    rate_limiter: [u8; 56],
+    config_added_borrow_weight_bps: [u8; 8],
+    liquidity_smoothed_market_price: [u8; 16],
+    config_reserve_type: u8,
+    config_max_utilization_rate: u8,
+    config_super_max_borrow_rate: [u8; 8],
+    config_max_liquidation_bonus: u8,
+    config_max_liquidation_threshold: u8,
+    padding: [[u8; 46]; 3],
+}
+
+const_assert_eq!(size_of::<PodReserve>(), Reserve::LEN);
+unsafe impl Zeroable for PodReserve {}
+unsafe impl Pod for PodReserve {}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct PodObligationHeader {
+    version: u8,
+    last_update_slot: [u8; 8],
+    last_update_stale: u8,
+    lending_market: [u8; 32],
+    owner: [u8; 32],
+    deposited_value: [u8; 16],
+    borrowed_value: [u8; 16],
+    allowed_borrow_value: [u8; 16],
+    unhealthy_borrow_value: [u8; 16],
+    borrowed_value_upper_bound: [u8; 16],
+    borrowing_isolated_asset: u8,
+    super_unhealthy_borrow_value: [u8; 16],
+    padding: [u8; 31],
+    deposits_len: u8,
+    borrows_len: u8,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct PodObligationCollateral {
+    deposit_reserve: [u8; 32],
+    deposited_amount: [u8; 8],
+    market_value: [u8; 16],
+    padding: [u8; 32],
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct PodObligationLiquidity {
+    borrow_reserve: [u8; 32],
+    cumulative_borrow_rate_wads: [u8; 16],
+    borrowed_amount_wads: [u8; 16],
+    market_value: [u8; 16],
+    padding: [u8; 32],
+}
+
+const_assert_eq!(
+    size_of::<PodObligationHeader>()
+        + size_of::<PodObligationCollateral>()
+        + 9 * size_of::<PodObligationLiquidity>(),
+    Obligation::LEN
+);
+unsafe impl Zeroable for PodObligationHeader {}
+unsafe impl Pod for PodObligationHeader {}
+unsafe impl Zeroable for PodObligationCollateral {}
+unsafe impl Pod for PodObligationCollateral {}
+unsafe impl Zeroable for PodObligationLiquidity {}
+unsafe impl Pod for PodObligationLiquidity {}
+
+/// Updates the deposit and borrow market values of a packed obligation in place
+fn refresh_pod_obligation(data: &mut [u8]) {
+    let (header, mut entries) = data.split_at_mut(size_of::<PodObligationHeader>());
+    let header: &mut PodObligationHeader = from_bytes_mut(header);
+    header.last_update_slot = SLOT.to_le_bytes();
+    header.last_update_stale = 0;
+    for _ in 0..header.deposits_len {
+        let (entry, rest) = entries.split_at_mut(size_of::<PodObligationCollateral>());
+        let deposit: &mut PodObligationCollateral = from_bytes_mut(entry);
+        deposit.market_value = PRICE.to_le_bytes();
+        entries = rest;
+    }
+    for _ in 0..header.borrows_len {
+        let (entry, rest) = entries.split_at_mut(size_of::<PodObligationLiquidity>());
+        let borrow: &mut PodObligationLiquidity = from_bytes_mut(entry);
+        borrow.market_value = PRICE.to_le_bytes();
+        entries = rest;
+    }
+}
+
+fn bench_reserve(c: &mut Criterion) {
+    let reserve = sample_reserve();
+    let mut packed = vec![0u8; Reserve::LEN];
+    Reserve::pack(reserve.clone(), &mut packed).unwrap();
+    let borsh = BorshReserve::from(&reserve).try_to_vec().unwrap();
+
+    let mut group = c.benchmark_group("reserve");
+
+    group.bench_function("pack/unpack", |b| {
+        b.iter(|| Reserve::unpack(black_box(&packed)).unwrap())
+    });
+    group.bench_function("borsh/unpack", |b| {
+        b.iter(|| BorshReserve::try_from_slice(black_box(&borsh)).unwrap())
+    });
+    group.bench_function("zero_copy/unpack", |b| {
+        b.iter(|| *from_bytes::<PodReserve>(black_box(&packed)))
+    });
+
+    group.bench_function("pack/pack", |b| {
+        let mut output = vec![0u8; Reserve::LEN];
+        b.iter(|| reserve.pack_into_slice(black_box(&mut output)))
+    });
+    group.bench_function("borsh/pack", |b| {
+        let mirror = BorshReserve::from(&reserve);
+        let mut output = vec![0u8; borsh.len()];
+        b.iter(|| mirror.serialize(&mut black_box(&mut output[..])).unwrap())
+    });
+
+    group.bench_function("pack/refresh", |b| {
+        b.iter_batched_ref(
+            || packed.clone(),
+            |data| {
+                let mut reserve = Reserve::unpack(data).unwrap();
+                reserve.liquidity.market_price = Decimal::from_scaled_val(PRICE);
+                reserve.liquidity.smoothed_market_price = Decimal::from_scaled_val(PRICE);
+                reserve.last_update.slot = SLOT;
+                reserve.last_update.stale = false;
+                Reserve::pack(reserve, data).unwrap();
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("borsh/refresh", |b| {
+        b.iter_batched_ref(
+            || borsh.clone(),
+            |data| {
+                let mut reserve = BorshReserve::try_from_slice(data).unwrap();
+                reserve.liquidity_market_price = PRICE;
+                reserve.liquidity_smoothed_market_price = PRICE;
+                reserve.last_update_slot = SLOT;
+                reserve.last_update_stale = false;
+                reserve.serialize(&mut &mut data[..]).unwrap();
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("zero_copy/refresh", |b| {
+        b.iter_batched_ref(
+            || packed.clone(),
+            |data| {
+                let reserve: &mut PodReserve = from_bytes_mut(data);
+                reserve.liquidity_market_price = PRICE.to_le_bytes();
+                reserve.liquidity_smoothed_market_price = PRICE.to_le_bytes();
+                reserve.last_update_slot = SLOT.to_le_bytes();
+                reserve.last_update_stale = 0;
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn bench_obligation(c: &mut Criterion) {
+    let obligation = sample_obligation();
+    let mut packed = vec![0u8; Obligation::LEN];
+    Obligation::pack(obligation.clone(), &mut packed).unwrap();
+    let borsh = BorshObligation::from(&obligation).try_to_vec().unwrap();
+
+    let mut group = c.benchmark_group("obligation");
+
+    group.bench_function("pack/unpack", |b| {
+        b.iter(|| Obligation::unpack(black_box(&packed)).unwrap())
+    });
+    group.bench_function("borsh/unpack", |b| {
+        b.iter(|| BorshObligation::try_from_slice(black_box(&borsh)).unwrap())
+    });
+    group.bench_function("zero_copy/unpack", |b| {
+        b.iter(|| {
+            *from_bytes::<PodObligationHeader>(black_box(
+                &packed[..size_of::<PodObligationHeader>()],
+            ))
+        })
+    });
+
+    group.bench_function("pack/pack", |b| {
+        let mut output = vec![0u8; Obligation::LEN];
+        b.iter(|| obligation.pack_into_slice(black_box(&mut output)))
+    });
+    group.bench_function("borsh/pack", |b| {
+        let mirror = BorshObligation::from(&obligation);
+        let mut output = vec![0u8; borsh.len()];
+        b.iter(|| mirror.serialize(&mut black_box(&mut output[..])).unwrap())
+    });
+
+    group.bench_function("pack/refresh", |b| {
+        b.iter_batched_ref(
+            || packed.clone(),
+            |data| {
+                let mut obligation = Obligation::unpack(data).unwrap();
+                for deposit in &mut obligation.deposits {
+                    deposit.market_value = Decimal::from_scaled_val(PRICE);
+                }
+                for borrow in &mut obligation.borrows {
+                    borrow.market_value = Decimal::from_scaled_val(PRICE);
+                }
+                obligation.last_update.slot = SLOT;
+                obligation.last_update.stale = false;
+                Obligation::pack(obligation, data).unwrap();
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("borsh/refresh", |b| {
+        b.iter_batched_ref(
+            || borsh.clone(),
+            |data| {
+                let mut obligation = BorshObligation::try_from_slice(data).unwrap();
+                for deposit in &mut obligation.deposits {
+                    deposit.market_value = PRICE;
+                }
+                for borrow in &mut obligation.borrows {
+                    borrow.market_value = PRICE;
+                }
+                obligation.last_update_slot = SLOT;
+                obligation.last_update_stale = false;
+                obligation.serialize(&mut &mut data[..]).unwrap();
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("zero_copy/refresh", |b| {
+        b.iter_batched_ref(
+            || packed.clone(),
+            |data| refresh_pod_obligation(data),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn bench_reserve_view(c: &mut Criterion) {
+    let mut packed = vec![0u8; Reserve::LEN];
+    Reserve::pack(sample_reserve(), &mut packed).unwrap();
+    let deposited_amount = Decimal::from(1_000_000u64);
+
+    let mut group = c.benchmark_group("reserve_view");
+
+    group.bench_function("unpack/refresh_obligation", |b| {
+        b.iter(|| {
+            let reserve = Reserve::unpack(black_box(&packed)).unwrap();
+            black_box(reserve.last_update.is_stale(SLOT).unwrap());
+            let liquidity_amount = reserve
+                .collateral_exchange_rate()
+                .unwrap()
+                .decimal_collateral_to_liquidity(deposited_amount)
+                .unwrap();
+            (
+                reserve.market_value(liquidity_amount).unwrap(),
+                reserve.market_value_lower_bound(liquidity_amount).unwrap(),
+                reserve.loan_to_value_ratio(),
+            )
+        })
+    });
+    group.bench_function("view/refresh_obligation", |b| {
+        b.iter(|| {
+            let reserve = ReserveView::load(black_box(&packed)).unwrap();
+            black_box(reserve.last_update().unwrap().is_stale(SLOT).unwrap());
+            let liquidity_amount = reserve
+                .collateral_exchange_rate()
+                .unwrap()
+                .decimal_collateral_to_liquidity(deposited_amount)
+                .unwrap();
+            (
+                reserve.market_value(liquidity_amount).unwrap(),
+                reserve.market_value_lower_bound(liquidity_amount).unwrap(),
+                reserve.loan_to_value_rate(),
+            )
+        })
+    });
+
+    let max_borrow_value = Decimal::from(1_000u64);
+    let remaining_reserve_borrow = Decimal::from(u64::MAX);
+    group.bench_function("unpack/borrow", |b| {
+        b.iter_batched_ref(
+            || packed.clone(),
+            |data| {
+                let mut reserve = Reserve::unpack(data).unwrap();
+                let borrow = reserve
+                    .calculate_borrow(1_000_000, max_borrow_value, remaining_reserve_borrow)
+                    .unwrap();
+                reserve.liquidity.borrow(borrow.borrow_amount).unwrap();
+                reserve.last_update.mark_stale();
+                Reserve::pack(reserve, data).unwrap();
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("view/borrow", |b| {
+        b.iter_batched_ref(
+            || packed.clone(),
+            |data| {
+                let borrow = ReserveView::load(data)
+                    .unwrap()
+                    .calculate_borrow(1_000_000, max_borrow_value, remaining_reserve_borrow)
+                    .unwrap();
+                let reserve = ReserveView::load_mut(data).unwrap();
+                reserve.borrow(borrow.borrow_amount).unwrap();
+                reserve.mark_stale();
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    let mut obligation = sample_obligation();
+    obligation.unhealthy_borrow_value = Decimal::from(40u64);
+    obligation.super_unhealthy_borrow_value = Decimal::from(60u64);
+    let liquidity = obligation.borrows[0].clone();
+    let collateral = obligation.deposits[0].clone();
+    group.bench_function("unpack/liquidate", |b| {
+        b.iter(|| {
+            let reserve = Reserve::unpack(black_box(&packed)).unwrap();
+            reserve
+                .calculate_liquidation(u64::MAX, &obligation, &liquidity, &collateral)
+                .unwrap()
+        })
+    });
+    group.bench_function("view/liquidate", |b| {
+        b.iter(|| {
+            let reserve = ReserveView::load(black_box(&packed)).unwrap();
+            reserve
+                .calculate_liquidation(u64::MAX, &obligation, &liquidity, &collateral)
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_reserve, bench_obligation, bench_reserve_view);
+criterion_main!(benches);
@@ -0,0 +1,278 @@
+//! Typed instruction builders.
+//!
+//! Each account role of a builder is a type parameter that starts out as [Missing] and becomes
+//! a `Pubkey` once its setter is called. `build` is only implemented once every role is set, so
+//! leaving an account out is a compile error rather than a failed transaction, and roles are
+//! set by name instead of by position. Setters can't be called twice for the same role.
+//!
+//! ```compile_fail
+//! # use solend_sdk::{builder::BorrowBuilder, solana_program::pubkey::Pubkey};
+//! let key = Pubkey::new_unique();
+//! // no fee receiver
+//! let ix = BorrowBuilder::new(solend_sdk::solend_mainnet::id(), 100)
+//!     .source_liquidity(key)
+//!     .destination_liquidity(key)
+//!     .borrow_reserve(key)
+//!     .obligation(key)
+//!     .lending_market(key)
+//!     .obligation_owner(key)
+//!     .build();
+//! ```
+
+use crate::instruction::borrow_obligation_liquidity;
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+/// An account role that hasn't been set yet
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Missing;
+
+/// Builds a `BorrowObligationLiquidity` instruction. The host fee receiver is optional; every
+/// other account must be set before `build`.
+///
+/// ```
+/// # use solend_sdk::{builder::BorrowBuilder, solana_program::pubkey::Pubkey};
+/// # let key = Pubkey::new_unique();
+/// let ix = BorrowBuilder::new(solend_sdk::solend_mainnet::id(), 100)
+///     .source_liquidity(key)
+///     .destination_liquidity(key)
+///     .borrow_reserve(key)
+///     .borrow_reserve_liquidity_fee_receiver(key)
+///     .obligation(key)
+///     .lending_market(key)
+///     .obligation_owner(key)
+///     .build();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BorrowBuilder<
+    SourceLiquidity = Missing,
+    DestinationLiquidity = Missing,
+    BorrowReserve = Missing,
+    FeeReceiver = Missing,
+    Obligation = Missing,
+    LendingMarket = Missing,
+    ObligationOwner = Missing,
+> {
+    program_id: Pubkey,
+    liquidity_amount: u64,
+    source_liquidity: SourceLiquidity,
+    destination_liquidity: DestinationLiquidity,
+    borrow_reserve: BorrowReserve,
+    fee_receiver: FeeReceiver,
+    obligation: Obligation,
+    lending_market: LendingMarket,
+    obligation_owner: ObligationOwner,
+    host_fee_receiver: Option<Pubkey>,
+}
+
+impl BorrowBuilder {
+    /// Borrow `liquidity_amount` from a reserve of `program_id`. Pass `u64::MAX` to borrow as
+    /// much as the obligation allows.
+    pub fn new(program_id: Pubkey, liquidity_amount: u64) -> Self {
+        Self {
+            program_id,
+            liquidity_amount,
+            source_liquidity: Missing,
+            destination_liquidity: Missing,
+            borrow_reserve: Missing,
+            fee_receiver: Missing,
+            obligation: Missing,
+            lending_market: Missing,
+            obligation_owner: Missing,
+            host_fee_receiver: None,
+        }
+    }
+}
+
+impl<S, D, R, F, O, M, A> BorrowBuilder<S, D, R, F, O, M, A> {
+    /// Token account receiving the host's share of the borrow fee
+    pub fn host_fee_receiver(mut self, pubkey: Pubkey) -> Self {
+        self.host_fee_receiver = Some(pubkey);
+        self
+    }
+}
+
+impl<D, R, F, O, M, A> BorrowBuilder<Missing, D, R, F, O, M, A> {
+    /// The reserve's liquidity supply
+    pub fn source_liquidity(self, pubkey: Pubkey) -> BorrowBuilder<Pubkey, D, R, F, O, M, A> {
+        BorrowBuilder {
+            program_id: self.program_id,
+            liquidity_amount: self.liquidity_amount,
+            source_liquidity: pubkey,
+            destination_liquidity: self.destination_liquidity,
+            borrow_reserve: self.borrow_reserve,
+            fee_receiver: self.fee_receiver,
+            obligation: self.obligation,
+            lending_market: self.lending_market,
+            obligation_owner: self.obligation_owner,
+            host_fee_receiver: self.host_fee_receiver,
+        }
+    }
+}
+
+impl<S, R, F, O, M, A> BorrowBuilder<S, Missing, R, F, O, M, A> {
+    /// The borrower's token account receiving the liquidity
+    pub fn destination_liquidity(self, pubkey: Pubkey) -> BorrowBuilder<S, Pubkey, R, F, O, M, A> {
+        BorrowBuilder {
+            program_id: self.program_id,
+            liquidity_amount: self.liquidity_amount,
+            source_liquidity: self.source_liquidity,
+            destination_liquidity: pubkey,
+            borrow_reserve: self.borrow_reserve,
+            fee_receiver: self.fee_receiver,
+            obligation: self.obligation,
+            lending_market: self.lending_market,
+            obligation_owner: self.obligation_owner,
+            host_fee_receiver: self.host_fee_receiver,
+        }
+    }
+}
+
+impl<S, D, F, O, M, A> BorrowBuilder<S, D, Missing, F, O, M, A> {
+    /// The reserve being borrowed from
+    pub fn borrow_reserve(self, pubkey: Pubkey) -> BorrowBuilder<S, D, Pubkey, F, O, M, A> {
+        BorrowBuilder {
+            program_id: self.program_id,
+            liquidity_amount: self.liquidity_amount,
+            source_liquidity: self.source_liquidity,
+            destination_liquidity: self.destination_liquidity,
+            borrow_reserve: pubkey,
+            fee_receiver: self.fee_receiver,
+            obligation: self.obligation,
+            lending_market: self.lending_market,
+            obligation_owner: self.obligation_owner,
+            host_fee_receiver: self.host_fee_receiver,
+        }
+    }
+}
+
+impl<S, D, R, O, M, A> BorrowBuilder<S, D, R, Missing, O, M, A> {
+    /// The reserve's liquidity fee receiver
+    pub fn borrow_reserve_liquidity_fee_receiver(
+        self,
+        pubkey: Pubkey,
+    ) -> BorrowBuilder<S, D, R, Pubkey, O, M, A> {
+        BorrowBuilder {
+            program_id: self.program_id,
+            liquidity_amount: self.liquidity_amount,
+            source_liquidity: self.source_liquidity,
+            destination_liquidity: self.destination_liquidity,
+            borrow_reserve: self.borrow_reserve,
+            fee_receiver: pubkey,
+            obligation: self.obligation,
+            lending_market: self.lending_market,
+            obligation_owner: self.obligation_owner,
+            host_fee_receiver: self.host_fee_receiver,
+        }
+    }
+}
+
+impl<S, D, R, F, M, A> BorrowBuilder<S, D, R, F, Missing, M, A> {
+    /// The obligation taking on the borrow
+    pub fn obligation(self, pubkey: Pubkey) -> BorrowBuilder<S, D, R, F, Pubkey, M, A> {
+        BorrowBuilder {
+            program_id: self.program_id,
+            liquidity_amount: self.liquidity_amount,
+            source_liquidity: self.source_liquidity,
+            destination_liquidity: self.destination_liquidity,
+            borrow_reserve: self.borrow_reserve,
+            fee_receiver: self.fee_receiver,
+            obligation: pubkey,
+            lending_market: self.lending_market,
+            obligation_owner: self.obligation_owner,
+            host_fee_receiver: self.host_fee_receiver,
+        }
+    }
+}
+
+impl<S, D, R, F, O, A> BorrowBuilder<S, D, R, F, O, Missing, A> {
+    /// The lending market of the reserve and obligation
+    pub fn lending_market(self, pubkey: Pubkey) -> BorrowBuilder<S, D, R, F, O, Pubkey, A> {
+        BorrowBuilder {
+            program_id: self.program_id,
+            liquidity_amount: self.liquidity_amount,
+            source_liquidity: self.source_liquidity,
+            destination_liquidity: self.destination_liquidity,
+            borrow_reserve: self.borrow_reserve,
+            fee_receiver: self.fee_receiver,
+            obligation: self.obligation,
+            lending_market: pubkey,
+            obligation_owner: self.obligation_owner,
+            host_fee_receiver: self.host_fee_receiver,
+        }
+    }
+}
+
+impl<S, D, R, F, O, M> BorrowBuilder<S, D, R, F, O, M, Missing> {
+    /// The obligation owner, who signs the borrow
+    pub fn obligation_owner(self, pubkey: Pubkey) -> BorrowBuilder<S, D, R, F, O, M, Pubkey> {
+        BorrowBuilder {
+            program_id: self.program_id,
+            liquidity_amount: self.liquidity_amount,
+            source_liquidity: self.source_liquidity,
+            destination_liquidity: self.destination_liquidity,
+            borrow_reserve: self.borrow_reserve,
+            fee_receiver: self.fee_receiver,
+            obligation: self.obligation,
+            lending_market: self.lending_market,
+            obligation_owner: pubkey,
+            host_fee_receiver: self.host_fee_receiver,
+        }
+    }
+}
+
+impl BorrowBuilder<Pubkey, Pubkey, Pubkey, Pubkey, Pubkey, Pubkey, Pubkey> {
+    /// The instruction, with every account in the order the program expects
+    pub fn build(self) -> Instruction {
+        borrow_obligation_liquidity(
+            self.program_id,
+            self.liquidity_amount,
+            self.source_liquidity,
+            self.destination_liquidity,
+            self.borrow_reserve,
+            self.fee_receiver,
+            self.obligation,
+            self.lending_market,
+            self.obligation_owner,
+            self.host_fee_receiver,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_borrow_builder() {
+        let program_id = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..8).map(|_| Pubkey::new_unique()).collect();
+
+        // setters can come in any order
+        let ix = BorrowBuilder::new(program_id, 100)
+            .obligation_owner(keys[6])
+            .lending_market(keys[5])
+            .host_fee_receiver(keys[7])
+            .obligation(keys[4])
+            .borrow_reserve_liquidity_fee_receiver(keys[3])
+            .borrow_reserve(keys[2])
+            .destination_liquidity(keys[1])
+            .source_liquidity(keys[0])
+            .build();
+
+        assert_eq!(
+            ix,
+            borrow_obligation_liquidity(
+                program_id,
+                100,
+                keys[0],
+                keys[1],
+                keys[2],
+                keys[3],
+                keys[4],
+                keys[5],
+                keys[6],
+                Some(keys[7]),
+            )
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! Events logged by the lending program, see `solend_events` for the wire format
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use solend_events::{Event, DISCRIMINATOR_LEN};
+
+/// An obligation was liquidated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct ObligationLiquidated {
+    /// Liquidated obligation
+    pub obligation: Pubkey,
+    /// Reserve the liquidator repaid
+    pub repay_reserve: Pubkey,
+    /// Reserve the liquidator withdrew collateral from
+    pub withdraw_reserve: Pubkey,
+    /// Transfer authority of the liquidator
+    pub liquidator: Pubkey,
+    /// Amount of liquidity repaid
+    pub repay_amount: u64,
+    /// Amount of collateral withdrawn
+    pub withdraw_amount: u64,
+}
+
+unsafe impl Zeroable for ObligationLiquidated {}
+unsafe impl Pod for ObligationLiquidated {}
+
+impl Event for ObligationLiquidated {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = [164, 115, 79, 147, 204, 253, 177, 124];
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solend_events::event_discriminator;
+
+    #[test]
+    fn test_discriminators() {
+        assert_eq!(
+            ObligationLiquidated::DISCRIMINATOR,
+            event_discriminator("ObligationLiquidated")
+        );
+    }
+}
@@ -0,0 +1,1859 @@
+//! Machine readable description of the lending program: its instructions, the types their
+//! arguments use and the layouts of its accounts, for generating clients in other languages.
+//!
+//! [INSTRUCTIONS] lists the `#[account(...)]` attributes and fields of
+//! [LendingInstruction](crate::instruction::LendingInstruction), and a test checks it against them. Arguments and accounts are laid
+//! out field after field in little endian, like Borsh, in the order listed here, which isn't
+//! always the order of the Rust struct fields. Errors are described by
+//! [LendingError](crate::error::LendingError).
+
+/// The type of an instruction argument or account field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdlType {
+    /// One byte, 0 or 1
+    Bool,
+    /// `u8`
+    U8,
+    /// `u16`
+    U16,
+    /// `u32`
+    U32,
+    /// `u64`
+    U64,
+    /// `u128`, also used for `Decimal` wads
+    U128,
+    /// 32 byte public key
+    PublicKey,
+    /// Fixed length byte array
+    Bytes(usize),
+    /// One byte tag, followed by the value if the tag is 1
+    Option(&'static IdlType),
+    /// One of [TYPES]
+    Defined(&'static str),
+}
+
+impl IdlType {
+    /// Size in bytes, or `None` for options, whose size depends on the value
+    pub fn size(&self) -> Option<usize> {
+        match self {
+            IdlType::Bool | IdlType::U8 => Some(1),
+            IdlType::U16 => Some(2),
+            IdlType::U32 => Some(4),
+            IdlType::U64 => Some(8),
+            IdlType::U128 => Some(16),
+            IdlType::PublicKey => Some(32),
+            IdlType::Bytes(len) => Some(*len),
+            IdlType::Option(_) => None,
+            IdlType::Defined(name) => match find_type(name)?.kind {
+                IdlTypeKind::Struct(fields) => fields_size(fields),
+                IdlTypeKind::Enum(_) => Some(1),
+            },
+        }
+    }
+}
+
+/// A named instruction argument, type field or account field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdlField {
+    /// Name, in snake case
+    pub name: &'static str,
+    /// Type
+    pub ty: IdlType,
+}
+
+/// An account an instruction expects
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdlAccountMeta {
+    /// Name, in snake case
+    pub name: &'static str,
+    /// Whether the instruction writes to the account
+    pub writable: bool,
+    /// Whether the account must sign
+    pub signer: bool,
+    /// Whether the account may be left out
+    pub optional: bool,
+    /// Description
+    pub desc: &'static str,
+}
+
+/// An instruction, identified by its first data byte
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdlInstruction {
+    /// Name of the `LendingInstruction` variant
+    pub name: &'static str,
+    /// First byte of the instruction data
+    pub discriminant: u8,
+    /// Accounts, in order
+    pub accounts: &'static [IdlAccountMeta],
+    /// Arguments, in order after the discriminant
+    pub args: &'static [IdlField],
+}
+
+/// The shape of a defined type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdlTypeKind {
+    /// Fields, in order
+    Struct(&'static [IdlField]),
+    /// Variants without fields, encoded as a `u8` index
+    Enum(&'static [&'static str]),
+}
+
+/// A type used by instruction arguments or account fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdlTypeDef {
+    /// Name, referenced by [IdlType::Defined]
+    pub name: &'static str,
+    /// Shape
+    pub kind: IdlTypeKind,
+}
+
+/// The layout of a program account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdlAccount {
+    /// Name of the state type
+    pub name: &'static str,
+    /// Fields, in order from the start of the account data
+    pub fields: &'static [IdlField],
+}
+
+impl IdlAccount {
+    /// Size of the account data in bytes
+    pub fn size(&self) -> usize {
+        fields_size(self.fields).expect("account fields have fixed sizes")
+    }
+
+    /// Offset of field `name` from the start of the account data
+    pub fn offset_of(&self, name: &str) -> Option<usize> {
+        let mut offset = 0;
+        for field in self.fields {
+            if field.name == name {
+                return Some(offset);
+            }
+            offset += field.ty.size()?;
+        }
+        None
+    }
+}
+
+fn fields_size(fields: &[IdlField]) -> Option<usize> {
+    fields.iter().map(|field| field.ty.size()).sum()
+}
+
+/// The defined type called `name`
+pub fn find_type(name: &str) -> Option<&'static IdlTypeDef> {
+    TYPES.iter().find(|ty| ty.name == name)
+}
+
+/// The account layout of state type `name`
+pub fn find_account(name: &str) -> Option<&'static IdlAccount> {
+    ACCOUNTS.iter().find(|account| account.name == name)
+}
+
+const fn field(name: &'static str, ty: IdlType) -> IdlField {
+    IdlField { name, ty }
+}
+
+const fn account(
+    name: &'static str,
+    writable: bool,
+    signer: bool,
+    desc: &'static str,
+) -> IdlAccountMeta {
+    IdlAccountMeta {
+        name,
+        writable,
+        signer,
+        optional: false,
+        desc,
+    }
+}
+
+/// Every lending instruction, by discriminant, as annotated on `LendingInstruction`
+pub const INSTRUCTIONS: &[IdlInstruction] = &[
+    IdlInstruction {
+        name: "InitLendingMarket",
+        discriminant: 0,
+        accounts: &[
+            account(
+                "lending_market",
+                true,
+                false,
+                "Lending market account - uninitialized",
+            ),
+            account("rent", false, false, "Rent sysvar"),
+            account("token_program", false, false, "Token program id"),
+            account("oracle_program", false, false, "Pyth oracle program id"),
+            account(
+                "switchboard_oracle_program",
+                false,
+                false,
+                "Switchboard oracle program id",
+            ),
+        ],
+        args: &[
+            field("owner", IdlType::PublicKey),
+            field("quote_currency", IdlType::Bytes(32)),
+        ],
+    },
+    IdlInstruction {
+        name: "SetLendingMarketOwnerAndConfig",
+        discriminant: 1,
+        accounts: &[
+            account("lending_market", true, false, "Lending market account"),
+            account("lending_market_owner", false, true, "Current owner"),
+        ],
+        args: &[
+            field("new_owner", IdlType::PublicKey),
+            field("rate_limiter_config", IdlType::Defined("RateLimiterConfig")),
+            field(
+                "whitelisted_liquidator",
+                IdlType::Option(&IdlType::PublicKey),
+            ),
+            field("risk_authority", IdlType::PublicKey),
+        ],
+    },
+    IdlInstruction {
+        name: "InitReserve",
+        discriminant: 2,
+        accounts: &[
+            account(
+                "source_liquidity",
+                true,
+                false,
+                "Source liquidity token account",
+            ),
+            account(
+                "destination_collateral",
+                true,
+                false,
+                "Destination collateral token account - uninitialized",
+            ),
+            account("reserve", true, false, "Reserve account - uninitialized"),
+            account(
+                "reserve_liquidity_mint",
+                false,
+                false,
+                "Reserve liquidity SPL Token mint",
+            ),
+            account(
+                "reserve_liquidity_supply",
+                true,
+                false,
+                "Reserve liquidity supply SPL Token account - uninitialized",
+            ),
+            account(
+                "reserve_liquidity_fee_receiver",
+                true,
+                false,
+                "Reserve liquidity fee receiver - uninitialized",
+            ),
+            account(
+                "reserve_collateral_mint",
+                true,
+                false,
+                "Reserve collateral SPL Token mint - uninitialized",
+            ),
+            account(
+                "reserve_collateral_supply",
+                true,
+                false,
+                "Reserve collateral token supply - uninitialized",
+            ),
+            account("pyth_product", false, false, "Pyth product account"),
+            account("pyth_price", false, false, "Pyth price account"),
+            account(
+                "switchboard_feed",
+                false,
+                false,
+                "Switchboard price feed account",
+            ),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account("lending_market_owner", false, true, "Lending market owner"),
+            account(
+                "user_transfer_authority",
+                false,
+                true,
+                "User transfer authority",
+            ),
+            account("rent", false, false, "Rent sysvar"),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[
+            field("liquidity_amount", IdlType::U64),
+            field("config", IdlType::Defined("ReserveConfig")),
+        ],
+    },
+    IdlInstruction {
+        name: "RefreshReserve",
+        discriminant: 3,
+        accounts: &[
+            account("reserve", true, false, "Reserve account"),
+            account(
+                "pyth_price",
+                false,
+                false,
+                "Pyth reserve liquidity oracle account",
+            ),
+            account(
+                "switchboard_feed",
+                false,
+                false,
+                "Switchboard reserve liquidity oracle account",
+            ),
+        ],
+        args: &[],
+    },
+    IdlInstruction {
+        name: "DepositReserveLiquidity",
+        discriminant: 4,
+        accounts: &[
+            account(
+                "source_liquidity",
+                true,
+                false,
+                "Source liquidity token account",
+            ),
+            account(
+                "destination_collateral",
+                true,
+                false,
+                "Destination collateral token account",
+            ),
+            account("reserve", true, false, "Reserve account"),
+            account(
+                "reserve_liquidity_supply",
+                true,
+                false,
+                "Reserve liquidity supply SPL Token account",
+            ),
+            account(
+                "reserve_collateral_mint",
+                true,
+                false,
+                "Reserve collateral SPL Token mint",
+            ),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account(
+                "user_transfer_authority",
+                false,
+                true,
+                "User transfer authority",
+            ),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[field("liquidity_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "RedeemReserveCollateral",
+        discriminant: 5,
+        accounts: &[
+            account(
+                "source_collateral",
+                true,
+                false,
+                "Source collateral token account",
+            ),
+            account(
+                "destination_liquidity",
+                true,
+                false,
+                "Destination liquidity token account",
+            ),
+            account("reserve", true, false, "Reserve account"),
+            account(
+                "reserve_collateral_mint",
+                true,
+                false,
+                "Reserve collateral SPL Token mint",
+            ),
+            account(
+                "reserve_liquidity_supply",
+                true,
+                false,
+                "Reserve liquidity supply SPL Token account",
+            ),
+            account("lending_market", true, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account(
+                "user_transfer_authority",
+                false,
+                true,
+                "User transfer authority",
+            ),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[field("collateral_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "InitObligation",
+        discriminant: 6,
+        accounts: &[
+            account(
+                "obligation",
+                true,
+                false,
+                "Obligation account - uninitialized",
+            ),
+            account("lending_market", false, false, "Lending market account"),
+            account("obligation_owner", false, true, "Obligation owner"),
+            account("rent", false, false, "Rent sysvar"),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[],
+    },
+    IdlInstruction {
+        name: "RefreshObligation",
+        discriminant: 7,
+        accounts: &[account(
+            "obligation",
+            true,
+            false,
+            "Obligation account, followed by all deposit then borrow reserves in order",
+        )],
+        args: &[],
+    },
+    IdlInstruction {
+        name: "DepositObligationCollateral",
+        discriminant: 8,
+        accounts: &[
+            account(
+                "source_collateral",
+                true,
+                false,
+                "Source collateral token account",
+            ),
+            account(
+                "destination_collateral",
+                true,
+                false,
+                "Destination deposit reserve collateral supply SPL Token account",
+            ),
+            account("deposit_reserve", true, false, "Deposit reserve account"),
+            account("obligation", true, false, "Obligation account"),
+            account("lending_market", false, false, "Lending market account"),
+            account("obligation_owner", false, true, "Obligation owner"),
+            account(
+                "user_transfer_authority",
+                false,
+                true,
+                "User transfer authority",
+            ),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[field("collateral_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "WithdrawObligationCollateral",
+        discriminant: 9,
+        accounts: &[
+            account(
+                "source_collateral",
+                true,
+                false,
+                "Source withdraw reserve collateral supply SPL Token account",
+            ),
+            account(
+                "destination_collateral",
+                true,
+                false,
+                "Destination collateral token account",
+            ),
+            account(
+                "withdraw_reserve",
+                false,
+                false,
+                "Withdraw reserve account - refreshed",
+            ),
+            account("obligation", true, false, "Obligation account - refreshed"),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account("obligation_owner", false, true, "Obligation owner"),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[field("collateral_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "BorrowObligationLiquidity",
+        discriminant: 10,
+        accounts: &[
+            account(
+                "source_liquidity",
+                true,
+                false,
+                "Source borrow reserve liquidity supply SPL Token account",
+            ),
+            account(
+                "destination_liquidity",
+                true,
+                false,
+                "Destination liquidity token account",
+            ),
+            account(
+                "borrow_reserve",
+                true,
+                false,
+                "Borrow reserve account - refreshed",
+            ),
+            account(
+                "borrow_reserve_liquidity_fee_receiver",
+                true,
+                false,
+                "Borrow reserve liquidity fee receiver account",
+            ),
+            account("obligation", true, false, "Obligation account - refreshed"),
+            account("lending_market", true, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account("obligation_owner", false, true, "Obligation owner"),
+            account("token_program", false, false, "Token program id"),
+            IdlAccountMeta {
+                optional: true,
+                ..account(
+                    "host_fee_receiver",
+                    true,
+                    false,
+                    "Host fee receiver account",
+                )
+            },
+        ],
+        args: &[field("liquidity_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "RepayObligationLiquidity",
+        discriminant: 11,
+        accounts: &[
+            account(
+                "source_liquidity",
+                true,
+                false,
+                "Source liquidity token account",
+            ),
+            account(
+                "destination_liquidity",
+                true,
+                false,
+                "Destination repay reserve liquidity supply SPL Token account",
+            ),
+            account(
+                "repay_reserve",
+                true,
+                false,
+                "Repay reserve account - refreshed",
+            ),
+            account("obligation", true, false, "Obligation account - refreshed"),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "user_transfer_authority",
+                false,
+                true,
+                "User transfer authority",
+            ),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[field("liquidity_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "LiquidateObligation",
+        discriminant: 12,
+        accounts: &[
+            account(
+                "source_liquidity",
+                true,
+                false,
+                "Source liquidity token account",
+            ),
+            account(
+                "destination_collateral",
+                true,
+                false,
+                "Destination collateral token account",
+            ),
+            account(
+                "repay_reserve",
+                true,
+                false,
+                "Repay reserve account - refreshed",
+            ),
+            account(
+                "repay_reserve_liquidity_supply",
+                true,
+                false,
+                "Repay reserve liquidity supply SPL Token account",
+            ),
+            account(
+                "withdraw_reserve",
+                false,
+                false,
+                "Withdraw reserve account - refreshed",
+            ),
+            account(
+                "withdraw_reserve_collateral_supply",
+                true,
+                false,
+                "Withdraw reserve collateral supply SPL Token account",
+            ),
+            account("obligation", true, false, "Obligation account - refreshed"),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account(
+                "user_transfer_authority",
+                false,
+                true,
+                "User transfer authority",
+            ),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[field("liquidity_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "FlashLoan",
+        discriminant: 13,
+        accounts: &[
+            account(
+                "source_liquidity",
+                true,
+                false,
+                "Source liquidity token account",
+            ),
+            account(
+                "destination_liquidity",
+                true,
+                false,
+                "Destination liquidity token account",
+            ),
+            account("reserve", true, false, "Reserve account"),
+            account(
+                "flash_loan_fee_receiver",
+                true,
+                false,
+                "Flash loan fee receiver account",
+            ),
+            account("host_fee_receiver", true, false, "Host fee receiver"),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account("token_program", false, false, "Token program id"),
+            account(
+                "flash_loan_receiver_program",
+                false,
+                false,
+                "Flash loan receiver program id",
+            ),
+        ],
+        args: &[field("amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "DepositReserveLiquidityAndObligationCollateral",
+        discriminant: 14,
+        accounts: &[
+            account(
+                "source_liquidity",
+                true,
+                false,
+                "Source liquidity token account",
+            ),
+            account(
+                "user_collateral",
+                true,
+                false,
+                "Destination collateral token account",
+            ),
+            account("reserve", true, false, "Reserve account"),
+            account(
+                "reserve_liquidity_supply",
+                true,
+                false,
+                "Reserve liquidity supply SPL Token account",
+            ),
+            account(
+                "reserve_collateral_mint",
+                true,
+                false,
+                "Reserve collateral SPL Token mint",
+            ),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account(
+                "destination_deposit_collateral",
+                true,
+                false,
+                "Destination deposit reserve collateral supply SPL Token account",
+            ),
+            account("obligation", true, false, "Obligation account"),
+            account("obligation_owner", true, true, "Obligation owner"),
+            account("pyth_price", false, false, "Pyth price oracle account"),
+            account(
+                "switchboard_feed",
+                false,
+                false,
+                "Switchboard price feed oracle account",
+            ),
+            account(
+                "user_transfer_authority",
+                false,
+                true,
+                "User transfer authority",
+            ),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[field("liquidity_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "WithdrawObligationCollateralAndRedeemReserveCollateral",
+        discriminant: 15,
+        accounts: &[
+            account(
+                "source_collateral",
+                true,
+                false,
+                "Source withdraw reserve collateral supply SPL Token account",
+            ),
+            account(
+                "destination_collateral",
+                true,
+                false,
+                "Destination collateral token account",
+            ),
+            account(
+                "withdraw_reserve",
+                true,
+                false,
+                "Withdraw reserve account - refreshed",
+            ),
+            account("obligation", true, false, "Obligation account - refreshed"),
+            account("lending_market", true, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account(
+                "destination_liquidity",
+                true,
+                false,
+                "User liquidity token account",
+            ),
+            account(
+                "reserve_collateral_mint",
+                true,
+                false,
+                "Reserve collateral SPL Token mint",
+            ),
+            account(
+                "reserve_liquidity_supply",
+                true,
+                false,
+                "Reserve liquidity supply SPL Token account",
+            ),
+            account("obligation_owner", false, true, "Obligation owner"),
+            account(
+                "user_transfer_authority",
+                false,
+                true,
+                "User transfer authority",
+            ),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[field("collateral_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "UpdateReserveConfig",
+        discriminant: 16,
+        accounts: &[
+            account("reserve", true, false, "Reserve account - refreshed"),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account(
+                "lending_market_owner",
+                false,
+                true,
+                "Lending market owner or risk authority",
+            ),
+            account("pyth_product", false, false, "Pyth product key"),
+            account("pyth_price", false, false, "Pyth price key"),
+            account("switchboard_feed", false, false, "Switchboard key"),
+        ],
+        args: &[
+            field("config", IdlType::Defined("ReserveConfig")),
+            field("rate_limiter_config", IdlType::Defined("RateLimiterConfig")),
+        ],
+    },
+    IdlInstruction {
+        name: "LiquidateObligationAndRedeemReserveCollateral",
+        discriminant: 17,
+        accounts: &[
+            account(
+                "source_liquidity",
+                true,
+                false,
+                "Source liquidity token account",
+            ),
+            account(
+                "destination_collateral",
+                true,
+                false,
+                "Destination collateral token account",
+            ),
+            account(
+                "destination_liquidity",
+                true,
+                false,
+                "Destination liquidity token account",
+            ),
+            account(
+                "repay_reserve",
+                true,
+                false,
+                "Repay reserve account - refreshed",
+            ),
+            account(
+                "repay_reserve_liquidity_supply",
+                true,
+                false,
+                "Repay reserve liquidity supply SPL Token account",
+            ),
+            account(
+                "withdraw_reserve",
+                true,
+                false,
+                "Withdraw reserve account - refreshed",
+            ),
+            account(
+                "withdraw_reserve_collateral_mint",
+                true,
+                false,
+                "Withdraw reserve collateral SPL Token mint",
+            ),
+            account(
+                "withdraw_reserve_collateral_supply",
+                true,
+                false,
+                "Withdraw reserve collateral supply SPL Token account",
+            ),
+            account(
+                "withdraw_reserve_liquidity_supply",
+                true,
+                false,
+                "Withdraw reserve liquidity supply SPL Token account",
+            ),
+            account(
+                "withdraw_reserve_liquidity_fee_receiver",
+                true,
+                false,
+                "Withdraw reserve liquidity fee receiver account",
+            ),
+            account("obligation", true, false, "Obligation account - refreshed"),
+            account("lending_market", true, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account(
+                "user_transfer_authority",
+                false,
+                true,
+                "User transfer authority",
+            ),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[field("liquidity_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "RedeemFees",
+        discriminant: 18,
+        accounts: &[
+            account("reserve", true, false, "Reserve account"),
+            account(
+                "reserve_liquidity_fee_receiver",
+                true,
+                false,
+                "Reserve liquidity fee receiver account",
+            ),
+            account(
+                "reserve_liquidity_supply",
+                true,
+                false,
+                "Reserve liquidity supply SPL Token account",
+            ),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[],
+    },
+    IdlInstruction {
+        name: "FlashBorrowReserveLiquidity",
+        discriminant: 19,
+        accounts: &[
+            account(
+                "source_liquidity",
+                true,
+                false,
+                "Source liquidity token account",
+            ),
+            account(
+                "destination_liquidity",
+                true,
+                false,
+                "Destination liquidity token account",
+            ),
+            account("reserve", true, false, "Reserve account"),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "lending_market_authority",
+                false,
+                false,
+                "Derived lending market authority",
+            ),
+            account("instructions_sysvar", false, false, "Instructions sysvar"),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[field("liquidity_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "FlashRepayReserveLiquidity",
+        discriminant: 20,
+        accounts: &[
+            account(
+                "source_liquidity",
+                true,
+                false,
+                "Source liquidity token account",
+            ),
+            account(
+                "destination_liquidity",
+                true,
+                false,
+                "Destination liquidity token account",
+            ),
+            account(
+                "reserve_liquidity_fee_receiver",
+                true,
+                false,
+                "Flash loan fee receiver account",
+            ),
+            account("host_fee_receiver", true, false, "Host fee receiver"),
+            account("reserve", true, false, "Reserve account"),
+            account("lending_market", false, false, "Lending market account"),
+            account(
+                "user_transfer_authority",
+                false,
+                true,
+                "User transfer authority",
+            ),
+            account("instructions_sysvar", false, false, "Instructions sysvar"),
+            account("token_program", false, false, "Token program id"),
+        ],
+        args: &[
+            field("liquidity_amount", IdlType::U64),
+            field("borrow_instruction_index", IdlType::U8),
+        ],
+    },
+    IdlInstruction {
+        name: "ForgiveDebt",
+        discriminant: 21,
+        accounts: &[
+            account("obligation", true, false, "Obligation account - refreshed"),
+            account("reserve", true, false, "Reserve account - refreshed"),
+            account("lending_market", false, false, "Lending market account"),
+            account("lending_market_owner", false, true, "Lending market owner"),
+        ],
+        args: &[field("liquidity_amount", IdlType::U64)],
+    },
+    IdlInstruction {
+        name: "UpdateMarketMetadata",
+        discriminant: 22,
+        accounts: &[
+            account("lending_market", false, false, "Lending market account"),
+            account("lending_market_owner", true, true, "Lending market owner"),
+            account(
+                "lending_market_metadata",
+                true,
+                false,
+                "Lending market metadata account",
+            ),
+            account("system_program", false, false, "System program"),
+        ],
+        args: &[],
+    },
+    IdlInstruction {
+        name: "GetVersion",
+        discriminant: 23,
+        accounts: &[],
+        args: &[],
+    },
+];
+
+/// Types used by instruction arguments and account fields
+pub const TYPES: &[IdlTypeDef] = &[
+    // as packed by InitReserve and UpdateReserveConfig
+    IdlTypeDef {
+        name: "ReserveConfig",
+        kind: IdlTypeKind::Struct(&[
+            field("optimal_utilization_rate", IdlType::U8),
+            field("max_utilization_rate", IdlType::U8),
+            field("loan_to_value_ratio", IdlType::U8),
+            field("liquidation_bonus", IdlType::U8),
+            field("liquidation_threshold", IdlType::U8),
+            field("min_borrow_rate", IdlType::U8),
+            field("optimal_borrow_rate", IdlType::U8),
+            field("max_borrow_rate", IdlType::U8),
+            field("super_max_borrow_rate", IdlType::U64),
+            field("fees", IdlType::Defined("ReserveFees")),
+            field("deposit_limit", IdlType::U64),
+            field("borrow_limit", IdlType::U64),
+            field("fee_receiver", IdlType::PublicKey),
+            field("protocol_liquidation_fee", IdlType::U8),
+            field("protocol_take_rate", IdlType::U8),
+            field("added_borrow_weight_bps", IdlType::U64),
+            field("reserve_type", IdlType::Defined("ReserveType")),
+            field("max_liquidation_bonus", IdlType::U8),
+            field("max_liquidation_threshold", IdlType::U8),
+        ]),
+    },
+    IdlTypeDef {
+        name: "ReserveFees",
+        kind: IdlTypeKind::Struct(&[
+            field("borrow_fee_wad", IdlType::U64),
+            field("flash_loan_fee_wad", IdlType::U64),
+            field("host_fee_percentage", IdlType::U8),
+        ]),
+    },
+    IdlTypeDef {
+        name: "ReserveType",
+        kind: IdlTypeKind::Enum(&["Regular", "Isolated"]),
+    },
+    // as packed by instructions; the rate limiter state stores max_outflow first
+    IdlTypeDef {
+        name: "RateLimiterConfig",
+        kind: IdlTypeKind::Struct(&[
+            field("window_duration", IdlType::U64),
+            field("max_outflow", IdlType::U64),
+        ]),
+    },
+    IdlTypeDef {
+        name: "RateLimiter",
+        kind: IdlTypeKind::Struct(&[
+            field("max_outflow", IdlType::U64),
+            field("window_duration", IdlType::U64),
+            field("prev_qty", IdlType::U128),
+            field("window_start", IdlType::U64),
+            field("cur_qty", IdlType::U128),
+        ]),
+    },
+    IdlTypeDef {
+        name: "ObligationCollateral",
+        kind: IdlTypeKind::Struct(&[
+            field("deposit_reserve", IdlType::PublicKey),
+            field("deposited_amount", IdlType::U64),
+            field("market_value", IdlType::U128),
+            field("_padding", IdlType::Bytes(32)),
+        ]),
+    },
+    IdlTypeDef {
+        name: "ObligationLiquidity",
+        kind: IdlTypeKind::Struct(&[
+            field("borrow_reserve", IdlType::PublicKey),
+            field("cumulative_borrow_rate_wads", IdlType::U128),
+            field("borrowed_amount_wads", IdlType::U128),
+            field("market_value", IdlType::U128),
+            field("_padding", IdlType::Bytes(32)),
+        ]),
+    },
+];
+
+/// Layouts of the program's accounts
+pub const ACCOUNTS: &[IdlAccount] = &[
+    IdlAccount {
+        name: "LendingMarket",
+        fields: &[
+            field("version", IdlType::U8),
+            field("bump_seed", IdlType::U8),
+            field("owner", IdlType::PublicKey),
+            field("quote_currency", IdlType::Bytes(32)),
+            field("token_program_id", IdlType::PublicKey),
+            field("oracle_program_id", IdlType::PublicKey),
+            field("switchboard_oracle_program_id", IdlType::PublicKey),
+            field("rate_limiter", IdlType::Defined("RateLimiter")),
+            // all zeroes when unset
+            field("whitelisted_liquidator", IdlType::PublicKey),
+            field("risk_authority", IdlType::PublicKey),
+            field("_padding", IdlType::Bytes(8)),
+        ],
+    },
+    IdlAccount {
+        name: "Reserve",
+        fields: &[
+            field("version", IdlType::U8),
+            field("last_update_slot", IdlType::U64),
+            field("last_update_stale", IdlType::Bool),
+            field("lending_market", IdlType::PublicKey),
+            field("liquidity_mint_pubkey", IdlType::PublicKey),
+            field("liquidity_mint_decimals", IdlType::U8),
+            field("liquidity_supply_pubkey", IdlType::PublicKey),
+            field("liquidity_pyth_oracle_pubkey", IdlType::PublicKey),
+            field("liquidity_switchboard_oracle_pubkey", IdlType::PublicKey),
+            field("liquidity_available_amount", IdlType::U64),
+            field("liquidity_borrowed_amount_wads", IdlType::U128),
+            field("liquidity_cumulative_borrow_rate_wads", IdlType::U128),
+            field("liquidity_market_price", IdlType::U128),
+            field("collateral_mint_pubkey", IdlType::PublicKey),
+            field("collateral_mint_total_supply", IdlType::U64),
+            field("collateral_supply_pubkey", IdlType::PublicKey),
+            field("config_optimal_utilization_rate", IdlType::U8),
+            field("config_loan_to_value_ratio", IdlType::U8),
+            field("config_liquidation_bonus", IdlType::U8),
+            field("config_liquidation_threshold", IdlType::U8),
+            field("config_min_borrow_rate", IdlType::U8),
+            field("config_optimal_borrow_rate", IdlType::U8),
+            field("config_max_borrow_rate", IdlType::U8),
+            field("config_fees_borrow_fee_wad", IdlType::U64),
+            field("config_fees_flash_loan_fee_wad", IdlType::U64),
+            field("config_fees_host_fee_percentage", IdlType::U8),
+            field("config_deposit_limit", IdlType::U64),
+            field("config_borrow_limit", IdlType::U64),
+            field("config_fee_receiver", IdlType::PublicKey),
+            field("config_protocol_liquidation_fee", IdlType::U8),
+            field("config_protocol_take_rate", IdlType::U8),
+            field("liquidity_accumulated_protocol_fees_wads", IdlType::U128),
+            field("rate_limiter", IdlType::Defined("RateLimiter")),
+            field("config_added_borrow_weight_bps", IdlType::U64),
+            field("liquidity_smoothed_market_price", IdlType::U128),
+            field("config_reserve_type", IdlType::Defined("ReserveType")),
+            field("config_max_utilization_rate", IdlType::U8),
+            field("config_super_max_borrow_rate", IdlType::U64),
+            field("config_max_liquidation_bonus", IdlType::U8),
+            field("config_max_liquidation_threshold", IdlType::U8),
+            field("_padding", IdlType::Bytes(138)),
+        ],
+    },
+    IdlAccount {
+        name: "Obligation",
+        fields: &[
+            field("version", IdlType::U8),
+            field("last_update_slot", IdlType::U64),
+            field("last_update_stale", IdlType::Bool),
+            field("lending_market", IdlType::PublicKey),
+            field("owner", IdlType::PublicKey),
+            field("deposited_value", IdlType::U128),
+            field("borrowed_value", IdlType::U128),
+            field("allowed_borrow_value", IdlType::U128),
+            field("unhealthy_borrow_value", IdlType::U128),
+            field("borrowed_value_upper_bound", IdlType::U128),
+            field("borrowing_isolated_asset", IdlType::Bool),
+            field("super_unhealthy_borrow_value", IdlType::U128),
+            field("_padding", IdlType::Bytes(31)),
+            field("deposits_len", IdlType::U8),
+            field("borrows_len", IdlType::U8),
+            // deposits_len ObligationCollateral followed by borrows_len ObligationLiquidity
+            field("data_flat", IdlType::Bytes(88 + 112 * 9)),
+        ],
+    },
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        instruction::*,
+        state::{
+            LendingMarket, LendingMarketMetadata, Obligation, ObligationCollateral,
+            RateLimiterConfig, Reserve, ReserveConfig, ReserveFees, ReserveType,
+        },
+    };
+    use bytemuck::Zeroable;
+    use shank_macro_impl::{
+        instruction::{Instruction as ShankInstruction, InstructionVariantFields},
+        types::{Composite, Primitive, RustType, TypeKind, Value},
+    };
+    use solana_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey};
+
+    /// Encodes some value of `ty` as the IDL lays it out, each integer a distinct repeated byte
+    fn encode(ty: &IdlType, seed: &mut u8, out: &mut Vec<u8>) {
+        match ty {
+            IdlType::Option(inner) => {
+                out.push(1);
+                encode(inner, seed, out);
+            }
+            IdlType::Defined(name) => match find_type(name).unwrap().kind {
+                IdlTypeKind::Struct(fields) => {
+                    for field in fields {
+                        encode(&field.ty, seed, out);
+                    }
+                }
+                IdlTypeKind::Enum(_) => out.push(1),
+            },
+            ty => {
+                *seed = seed.wrapping_add(1);
+                out.resize(out.len() + ty.size().unwrap(), *seed);
+            }
+        }
+    }
+
+    /// The IDL type shank sees for a `LendingInstruction` field, formatted like `IdlType`'s Debug
+    fn shank_type(ty: &RustType) -> String {
+        match &ty.kind {
+            TypeKind::Primitive(primitive) => match primitive {
+                Primitive::Bool => "Bool",
+                Primitive::U8 => "U8",
+                Primitive::U16 => "U16",
+                Primitive::U32 => "U32",
+                Primitive::U64 => "U64",
+                Primitive::U128 => "U128",
+                other => panic!("{:?} arguments aren't supported in the IDL", other),
+            }
+            .to_string(),
+            TypeKind::Value(Value::Custom(name)) if name == "Pubkey" => "PublicKey".to_string(),
+            TypeKind::Value(Value::Custom(name)) => format!("Defined({:?})", name),
+            TypeKind::Composite(Composite::Array(len), inner)
+                if matches!(inner[0].kind, TypeKind::Primitive(Primitive::U8)) =>
+            {
+                format!("Bytes({})", len)
+            }
+            TypeKind::Composite(Composite::Option, inner) => {
+                format!("Option({})", shank_type(&inner[0]))
+            }
+            other => panic!("{:?} arguments aren't supported in the IDL", other),
+        }
+    }
+
+    #[test]
+    fn test_instructions_match_shank_annotations() {
+        let file = syn::parse_file(include_str!("instruction.rs")).unwrap();
+        let item_enum = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Enum(item_enum) if item_enum.ident == "LendingInstruction" => {
+                    Some(item_enum)
+                }
+                _ => None,
+            })
+            .unwrap();
+        let shank = ShankInstruction::try_from_item_enum(item_enum, false)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(INSTRUCTIONS.len(), shank.variants.len());
+        for (instruction, variant) in INSTRUCTIONS.iter().zip(&shank.variants) {
+            assert_eq!(instruction.name, variant.ident.to_string());
+            assert_eq!(
+                instruction.discriminant as usize, variant.discriminant,
+                "{}",
+                instruction.name
+            );
+
+            let accounts: Vec<_> = variant
+                .accounts
+                .iter()
+                .map(|account| {
+                    (
+                        account.name.as_str(),
+                        account.writable,
+                        account.signer,
+                        account.optional,
+                        account.desc.clone().unwrap_or_default(),
+                    )
+                })
+                .collect();
+            let idl_accounts: Vec<_> = instruction
+                .accounts
+                .iter()
+                .map(|account| {
+                    (
+                        account.name,
+                        account.writable,
+                        account.signer,
+                        account.optional,
+                        account.desc.to_string(),
+                    )
+                })
+                .collect();
+            assert_eq!(idl_accounts, accounts, "{}", instruction.name);
+
+            let args: Vec<_> = match &variant.field_tys {
+                InstructionVariantFields::Named(fields) => fields
+                    .iter()
+                    .map(|(name, ty)| (name.clone(), shank_type(ty)))
+                    .collect(),
+                InstructionVariantFields::Unnamed(tys) if tys.is_empty() => vec![],
+                InstructionVariantFields::Unnamed(_) => {
+                    panic!("{} has unnamed fields", instruction.name)
+                }
+            };
+            let idl_args: Vec<_> = instruction
+                .args
+                .iter()
+                .map(|arg| (arg.name.to_string(), format!("{:?}", arg.ty)))
+                .collect();
+            assert_eq!(idl_args, args, "{}", instruction.name);
+        }
+    }
+
+    #[test]
+    fn test_instructions_match_unpack() {
+        for (i, instruction) in INSTRUCTIONS.iter().enumerate() {
+            assert_eq!(instruction.discriminant as usize, i);
+            // UpdateMarketMetadata data is the raw metadata, unpacked by the processor
+            if instruction.name == "UpdateMarketMetadata" {
+                continue;
+            }
+
+            let mut data = vec![instruction.discriminant];
+            let mut seed = 0;
+            for arg in instruction.args {
+                encode(&arg.ty, &mut seed, &mut data);
+            }
+            let unpacked = LendingInstruction::unpack(&data).unwrap();
+            assert!(
+                format!("{:?}", unpacked).starts_with(instruction.name),
+                "{} unpacked as {:?}",
+                instruction.name,
+                unpacked
+            );
+            // every byte is accounted for
+            assert_eq!(unpacked.pack(), data, "{}", instruction.name);
+        }
+    }
+
+    /// Checks `ix`, built by the sdk, has the accounts the IDL lists for its discriminant
+    fn assert_accounts_match(ix: Instruction) {
+        let idl = &INSTRUCTIONS[ix.data[0] as usize];
+        let required = idl
+            .accounts
+            .iter()
+            .filter(|account| !account.optional)
+            .count();
+        assert!(
+            (required..=idl.accounts.len()).contains(&ix.accounts.len()),
+            "{} has {} accounts, the IDL lists {}",
+            idl.name,
+            ix.accounts.len(),
+            idl.accounts.len()
+        );
+        for (meta, account) in ix.accounts.iter().zip(idl.accounts) {
+            assert_eq!(
+                (meta.is_writable, meta.is_signer),
+                (account.writable, account.signer),
+                "{} account {}",
+                idl.name,
+                account.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_instruction_accounts_match_builders() {
+        let program_id = Pubkey::new_unique();
+        let k = Pubkey::new_unique;
+        let config = ReserveConfig::default();
+        let rate_limiter_config = RateLimiterConfig::default();
+
+        assert_accounts_match(init_lending_market(program_id, k(), [0; 32], k(), k(), k()));
+        assert_accounts_match(set_lending_market_owner_and_config(
+            program_id,
+            k(),
+            k(),
+            k(),
+            rate_limiter_config,
+            None,
+            k(),
+        ));
+        assert_accounts_match(init_reserve(
+            program_id,
+            1,
+            config,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(refresh_reserve(program_id, k(), k(), k()));
+        assert_accounts_match(deposit_reserve_liquidity(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(redeem_reserve_collateral(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(init_obligation(program_id, k(), k(), k()));
+        assert_accounts_match(refresh_obligation(program_id, k(), vec![]));
+        assert_accounts_match(deposit_obligation_collateral(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(withdraw_obligation_collateral(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(borrow_obligation_liquidity(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            None,
+        ));
+        assert_accounts_match(borrow_obligation_liquidity(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            Some(k()),
+        ));
+        assert_accounts_match(repay_obligation_liquidity(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(liquidate_obligation(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(deposit_reserve_liquidity_and_obligation_collateral(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(
+            withdraw_obligation_collateral_and_redeem_reserve_collateral(
+                program_id,
+                1,
+                k(),
+                k(),
+                k(),
+                k(),
+                k(),
+                k(),
+                k(),
+                k(),
+                k(),
+                k(),
+            ),
+        );
+        assert_accounts_match(update_reserve_config(
+            program_id,
+            config,
+            rate_limiter_config,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(liquidate_obligation_and_redeem_reserve_collateral(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(redeem_fees(program_id, k(), k(), k(), k()));
+        assert_accounts_match(flash_borrow_reserve_liquidity(
+            program_id,
+            1,
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(flash_repay_reserve_liquidity(
+            program_id,
+            1,
+            0,
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(forgive_debt(program_id, 1, k(), k(), k(), k()));
+        assert_accounts_match(update_market_metadata(
+            program_id,
+            LendingMarketMetadata::zeroed(),
+            k(),
+            k(),
+        ));
+        assert_accounts_match(get_version(program_id));
+    }
+
+    #[test]
+    fn test_reserve_config_layout() {
+        let config = ReserveConfig {
+            optimal_utilization_rate: 1,
+            max_utilization_rate: 2,
+            loan_to_value_ratio: 3,
+            liquidation_bonus: 4,
+            max_liquidation_bonus: 18,
+            liquidation_threshold: 5,
+            max_liquidation_threshold: 19,
+            min_borrow_rate: 6,
+            optimal_borrow_rate: 7,
+            max_borrow_rate: 8,
+            super_max_borrow_rate: 9,
+            fees: ReserveFees {
+                borrow_fee_wad: 10,
+                flash_loan_fee_wad: 11,
+                host_fee_percentage: 12,
+            },
+            deposit_limit: 13,
+            borrow_limit: 14,
+            fee_receiver: Pubkey::new_from_array([15; 32]),
+            protocol_liquidation_fee: 16,
+            protocol_take_rate: 17,
+            added_borrow_weight_bps: 20,
+            reserve_type: ReserveType::Isolated,
+        };
+        let data = LendingInstruction::UpdateReserveConfig {
+            config,
+            rate_limiter_config: RateLimiterConfig {
+                window_duration: 21,
+                max_outflow: 22,
+            },
+        }
+        .pack();
+
+        let mut offset = 1;
+        let mut read = |ty: IdlType| {
+            let size = ty.size().unwrap();
+            let bytes = &data[offset..offset + size];
+            offset += size;
+            bytes.to_vec()
+        };
+        let fields = match find_type("ReserveConfig").unwrap().kind {
+            IdlTypeKind::Struct(fields) => fields,
+            _ => unreachable!(),
+        };
+        for field in fields {
+            let bytes = read(field.ty);
+            let expected: u64 = match field.name {
+                "optimal_utilization_rate" => 1,
+                "max_utilization_rate" => 2,
+                "loan_to_value_ratio" => 3,
+                "liquidation_bonus" => 4,
+                "liquidation_threshold" => 5,
+                "min_borrow_rate" => 6,
+                "optimal_borrow_rate" => 7,
+                "max_borrow_rate" => 8,
+                "super_max_borrow_rate" => 9,
+                "fees" => {
+                    assert_eq!(bytes[..8], 10u64.to_le_bytes());
+                    assert_eq!(bytes[8..16], 11u64.to_le_bytes());
+                    assert_eq!(bytes[16], 12);
+                    continue;
+                }
+                "deposit_limit" => 13,
+                "borrow_limit" => 14,
+                "fee_receiver" => {
+                    assert_eq!(bytes, [15; 32]);
+                    continue;
+                }
+                "protocol_liquidation_fee" => 16,
+                "protocol_take_rate" => 17,
+                "added_borrow_weight_bps" => 20,
+                "reserve_type" => 1,
+                "max_liquidation_bonus" => 18,
+                "max_liquidation_threshold" => 19,
+                name => panic!("unexpected field {}", name),
+            };
+            assert_eq!(
+                bytes,
+                expected.to_le_bytes()[..bytes.len()],
+                "{}",
+                field.name
+            );
+        }
+        assert_eq!(read(IdlType::U64), 21u64.to_le_bytes());
+        assert_eq!(read(IdlType::U64), 22u64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_offsets_match_layouts() {
+        use crate::state::*;
+
+        for (account, field, offset) in [
+            ("LendingMarket", "owner", LENDING_MARKET_OWNER_OFFSET),
+            (
+                "LendingMarket",
+                "quote_currency",
+                LENDING_MARKET_QUOTE_CURRENCY_OFFSET,
+            ),
+            (
+                "LendingMarket",
+                "risk_authority",
+                LENDING_MARKET_RISK_AUTHORITY_OFFSET,
+            ),
+            ("Reserve", "lending_market", RESERVE_LENDING_MARKET_OFFSET),
+            (
+                "Reserve",
+                "liquidity_mint_pubkey",
+                RESERVE_LIQUIDITY_MINT_OFFSET,
+            ),
+            (
+                "Reserve",
+                "liquidity_supply_pubkey",
+                RESERVE_LIQUIDITY_SUPPLY_OFFSET,
+            ),
+            (
+                "Reserve",
+                "liquidity_pyth_oracle_pubkey",
+                RESERVE_PYTH_ORACLE_OFFSET,
+            ),
+            (
+                "Reserve",
+                "liquidity_switchboard_oracle_pubkey",
+                RESERVE_SWITCHBOARD_ORACLE_OFFSET,
+            ),
+            (
+                "Reserve",
+                "collateral_mint_pubkey",
+                RESERVE_COLLATERAL_MINT_OFFSET,
+            ),
+            (
+                "Reserve",
+                "collateral_supply_pubkey",
+                RESERVE_COLLATERAL_SUPPLY_OFFSET,
+            ),
+            (
+                "Reserve",
+                "config_fee_receiver",
+                RESERVE_FEE_RECEIVER_OFFSET,
+            ),
+            (
+                "Obligation",
+                "lending_market",
+                OBLIGATION_LENDING_MARKET_OFFSET,
+            ),
+            ("Obligation", "owner", OBLIGATION_OWNER_OFFSET),
+        ] {
+            assert_eq!(
+                find_account(account).unwrap().offset_of(field),
+                Some(offset),
+                "{}.{}",
+                account,
+                field
+            );
+        }
+    }
+
+    #[test]
+    fn test_account_layouts() {
+        assert_eq!(
+            find_account("LendingMarket").unwrap().size(),
+            LendingMarket::LEN
+        );
+        assert_eq!(find_account("Reserve").unwrap().size(), Reserve::LEN);
+        assert_eq!(find_account("Obligation").unwrap().size(), Obligation::LEN);
+
+        let pubkey_at = |data: &[u8], account: &str, field: &str| {
+            let offset = find_account(account).unwrap().offset_of(field).unwrap();
+            Pubkey::new(&data[offset..offset + 32])
+        };
+
+        let market = LendingMarket {
+            version: 1,
+            owner: Pubkey::new_unique(),
+            oracle_program_id: Pubkey::new_unique(),
+            risk_authority: Pubkey::new_unique(),
+            ..LendingMarket::default()
+        };
+        let mut data = vec![0; LendingMarket::LEN];
+        market.pack_into_slice(&mut data);
+        assert_eq!(pubkey_at(&data, "LendingMarket", "owner"), market.owner);
+        assert_eq!(
+            pubkey_at(&data, "LendingMarket", "oracle_program_id"),
+            market.oracle_program_id
+        );
+        assert_eq!(
+            pubkey_at(&data, "LendingMarket", "risk_authority"),
+            market.risk_authority
+        );
+
+        let mut reserve = Reserve {
+            version: 1,
+            lending_market: Pubkey::new_unique(),
+            ..Reserve::default()
+        };
+        reserve.liquidity.mint_pubkey = Pubkey::new_unique();
+        reserve.liquidity.pyth_oracle_pubkey = Pubkey::new_unique();
+        reserve.collateral.mint_pubkey = Pubkey::new_unique();
+        reserve.config.fee_receiver = Pubkey::new_unique();
+        reserve.config.super_max_borrow_rate = 0x0102_0304_0506_0708;
+        let mut data = vec![0; Reserve::LEN];
+        reserve.pack_into_slice(&mut data);
+        for (field, pubkey) in [
+            ("lending_market", reserve.lending_market),
+            ("liquidity_mint_pubkey", reserve.liquidity.mint_pubkey),
+            (
+                "liquidity_pyth_oracle_pubkey",
+                reserve.liquidity.pyth_oracle_pubkey,
+            ),
+            ("collateral_mint_pubkey", reserve.collateral.mint_pubkey),
+            ("config_fee_receiver", reserve.config.fee_receiver),
+        ] {
+            assert_eq!(pubkey_at(&data, "Reserve", field), pubkey, "{}", field);
+        }
+        let offset = find_account("Reserve")
+            .unwrap()
+            .offset_of("config_super_max_borrow_rate")
+            .unwrap();
+        assert_eq!(
+            data[offset..offset + 8],
+            reserve.config.super_max_borrow_rate.to_le_bytes()
+        );
+
+        let deposit_reserve = Pubkey::new_unique();
+        let obligation = Obligation {
+            version: 1,
+            lending_market: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            deposits: vec![ObligationCollateral::new(deposit_reserve)],
+            ..Obligation::default()
+        };
+        let mut data = vec![0; Obligation::LEN];
+        obligation.pack_into_slice(&mut data);
+        assert_eq!(
+            pubkey_at(&data, "Obligation", "lending_market"),
+            obligation.lending_market
+        );
+        assert_eq!(pubkey_at(&data, "Obligation", "owner"), obligation.owner);
+        assert_eq!(pubkey_at(&data, "Obligation", "data_flat"), deposit_reserve);
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
 use bytemuck::bytes_of;
 
 use num_traits::FromPrimitive;
+use shank::ShankInstruction;
 use solana_program::system_program;
 use solana_program::{
     instruction::{AccountMeta, Instruction},
@@ -16,10 +17,11 @@ use solana_program::{
     pubkey::{Pubkey, PUBKEY_BYTES},
     sysvar,
 };
+use spl_forks_pdas::{find_lending_market_authority_address, find_lending_market_metadata_address};
 use std::{convert::TryInto, mem::size_of};
 
 /// Instructions supported by the lending program.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, ShankInstruction)]
 // #[allow(clippy::large_enum_variant)]
 pub enum LendingInstruction {
     // 0
@@ -32,6 +34,20 @@ pub enum LendingInstruction {
     ///   2. `[]` Token program id.
     ///   3. `[]` Oracle program id.
     ///   4. `[]` Switchboard Oracle program id.
+    #[account(
+        0,
+        writable,
+        name = "lending_market",
+        desc = "Lending market account - uninitialized"
+    )]
+    #[account(1, name = "rent", desc = "Rent sysvar")]
+    #[account(2, name = "token_program", desc = "Token program id")]
+    #[account(3, name = "oracle_program", desc = "Pyth oracle program id")]
+    #[account(
+        4,
+        name = "switchboard_oracle_program",
+        desc = "Switchboard oracle program id"
+    )]
     InitLendingMarket {
         /// Owner authority which can add new reserves
         owner: Pubkey,
@@ -47,6 +63,8 @@ pub enum LendingInstruction {
     ///
     ///   0. `[writable]` Lending market account.
     ///   1. `[signer]` Current owner.
+    #[account(0, writable, name = "lending_market", desc = "Lending market account")]
+    #[account(1, signer, name = "lending_market_owner", desc = "Current owner")]
     SetLendingMarketOwnerAndConfig {
         /// The new owner
         new_owner: Pubkey,
@@ -83,6 +101,76 @@ pub enum LendingInstruction {
     ///   15 `[]` Clock sysvar (optional, will be removed soon).
     ///   16 `[]` Rent sysvar.
     ///   17 `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_liquidity",
+        desc = "Source liquidity token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_collateral",
+        desc = "Destination collateral token account - uninitialized"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "reserve",
+        desc = "Reserve account - uninitialized"
+    )]
+    #[account(
+        3,
+        name = "reserve_liquidity_mint",
+        desc = "Reserve liquidity SPL Token mint"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "reserve_liquidity_supply",
+        desc = "Reserve liquidity supply SPL Token account - uninitialized"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "reserve_liquidity_fee_receiver",
+        desc = "Reserve liquidity fee receiver - uninitialized"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "reserve_collateral_mint",
+        desc = "Reserve collateral SPL Token mint - uninitialized"
+    )]
+    #[account(
+        7,
+        writable,
+        name = "reserve_collateral_supply",
+        desc = "Reserve collateral token supply - uninitialized"
+    )]
+    #[account(8, name = "pyth_product", desc = "Pyth product account")]
+    #[account(9, name = "pyth_price", desc = "Pyth price account")]
+    #[account(10, name = "switchboard_feed", desc = "Switchboard price feed account")]
+    #[account(11, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        12,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(
+        13,
+        signer,
+        name = "lending_market_owner",
+        desc = "Lending market owner"
+    )]
+    #[account(
+        14,
+        signer,
+        name = "user_transfer_authority",
+        desc = "User transfer authority"
+    )]
+    #[account(15, name = "rent", desc = "Rent sysvar")]
+    #[account(16, name = "token_program", desc = "Token program id")]
     InitReserve {
         /// Initial amount of liquidity to deposit into the new reserve
         liquidity_amount: u64,
@@ -101,6 +189,13 @@ pub enum LendingInstruction {
     ///   2. `[]` Switchboard Reserve liquidity oracle account.
     ///             Must be the Switchboard price feed account specified at InitReserve.
     ///   3. `[]` Clock sysvar (optional, will be removed soon).
+    #[account(0, writable, name = "reserve", desc = "Reserve account")]
+    #[account(1, name = "pyth_price", desc = "Pyth reserve liquidity oracle account")]
+    #[account(
+        2,
+        name = "switchboard_feed",
+        desc = "Switchboard reserve liquidity oracle account"
+    )]
     RefreshReserve,
 
     // 4
@@ -120,6 +215,44 @@ pub enum LendingInstruction {
     ///   7. `[signer]` User transfer authority ($authority).
     ///   8. `[]` Clock sysvar (optional, will be removed soon).
     ///   9. `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_liquidity",
+        desc = "Source liquidity token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_collateral",
+        desc = "Destination collateral token account"
+    )]
+    #[account(2, writable, name = "reserve", desc = "Reserve account")]
+    #[account(
+        3,
+        writable,
+        name = "reserve_liquidity_supply",
+        desc = "Reserve liquidity supply SPL Token account"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "reserve_collateral_mint",
+        desc = "Reserve collateral SPL Token mint"
+    )]
+    #[account(5, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        6,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(
+        7,
+        signer,
+        name = "user_transfer_authority",
+        desc = "User transfer authority"
+    )]
+    #[account(8, name = "token_program", desc = "Token program id")]
     DepositReserveLiquidity {
         /// Amount of liquidity to deposit in exchange for collateral tokens
         liquidity_amount: u64,
@@ -141,6 +274,44 @@ pub enum LendingInstruction {
     ///   7. `[signer]` User transfer authority ($authority).
     ///   8. `[]` Clock sysvar (optional, will be removed soon).
     ///   9. `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_collateral",
+        desc = "Source collateral token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_liquidity",
+        desc = "Destination liquidity token account"
+    )]
+    #[account(2, writable, name = "reserve", desc = "Reserve account")]
+    #[account(
+        3,
+        writable,
+        name = "reserve_collateral_mint",
+        desc = "Reserve collateral SPL Token mint"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "reserve_liquidity_supply",
+        desc = "Reserve liquidity supply SPL Token account"
+    )]
+    #[account(5, writable, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        6,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(
+        7,
+        signer,
+        name = "user_transfer_authority",
+        desc = "User transfer authority"
+    )]
+    #[account(8, name = "token_program", desc = "Token program id")]
     RedeemReserveCollateral {
         /// Amount of collateral tokens to redeem in exchange for liquidity
         collateral_amount: u64,
@@ -157,6 +328,16 @@ pub enum LendingInstruction {
     ///   3. `[]` Clock sysvar (optional, will be removed soon).
     ///   4. `[]` Rent sysvar.
     ///   5. `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "obligation",
+        desc = "Obligation account - uninitialized"
+    )]
+    #[account(1, name = "lending_market", desc = "Lending market account")]
+    #[account(2, signer, name = "obligation_owner", desc = "Obligation owner")]
+    #[account(3, name = "rent", desc = "Rent sysvar")]
+    #[account(4, name = "token_program", desc = "Token program id")]
     InitObligation,
 
     // 7
@@ -170,6 +351,12 @@ pub enum LendingInstruction {
     ///   1. `[]` Clock sysvar (optional, will be removed soon).
     ///   .. `[]` Collateral deposit reserve accounts - refreshed, all, in order.
     ///   .. `[]` Liquidity borrow reserve accounts - refreshed, all, in order.
+    #[account(
+        0,
+        writable,
+        name = "obligation",
+        desc = "Obligation account, followed by all deposit then borrow reserves in order"
+    )]
     RefreshObligation,
 
     // 8
@@ -188,6 +375,34 @@ pub enum LendingInstruction {
     ///   6. `[signer]` User transfer authority ($authority).
     ///   7. `[]` Clock sysvar (optional, will be removed soon).
     ///   8. `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_collateral",
+        desc = "Source collateral token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_collateral",
+        desc = "Destination deposit reserve collateral supply SPL Token account"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "deposit_reserve",
+        desc = "Deposit reserve account"
+    )]
+    #[account(3, writable, name = "obligation", desc = "Obligation account")]
+    #[account(4, name = "lending_market", desc = "Lending market account")]
+    #[account(5, signer, name = "obligation_owner", desc = "Obligation owner")]
+    #[account(
+        6,
+        signer,
+        name = "user_transfer_authority",
+        desc = "User transfer authority"
+    )]
+    #[account(7, name = "token_program", desc = "Token program id")]
     DepositObligationCollateral {
         /// Amount of collateral tokens to deposit
         collateral_amount: u64,
@@ -208,6 +423,37 @@ pub enum LendingInstruction {
     ///   6. `[signer]` Obligation owner.
     ///   7. `[]` Clock sysvar (optional, will be removed soon).
     ///   8. `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_collateral",
+        desc = "Source withdraw reserve collateral supply SPL Token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_collateral",
+        desc = "Destination collateral token account"
+    )]
+    #[account(
+        2,
+        name = "withdraw_reserve",
+        desc = "Withdraw reserve account - refreshed"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "obligation",
+        desc = "Obligation account - refreshed"
+    )]
+    #[account(4, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        5,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(6, signer, name = "obligation_owner", desc = "Obligation owner")]
+    #[account(7, name = "token_program", desc = "Token program id")]
     WithdrawObligationCollateral {
         /// Amount of collateral tokens to withdraw - u64::MAX for up to 100% of deposited amount
         collateral_amount: u64,
@@ -232,6 +478,51 @@ pub enum LendingInstruction {
     ///   8. `[]` Clock sysvar (optional, will be removed soon).
     ///   9. `[]` Token program id.
     ///   10 `[optional, writable]` Host fee receiver account.
+    #[account(
+        0,
+        writable,
+        name = "source_liquidity",
+        desc = "Source borrow reserve liquidity supply SPL Token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_liquidity",
+        desc = "Destination liquidity token account"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "borrow_reserve",
+        desc = "Borrow reserve account - refreshed"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "borrow_reserve_liquidity_fee_receiver",
+        desc = "Borrow reserve liquidity fee receiver account"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "obligation",
+        desc = "Obligation account - refreshed"
+    )]
+    #[account(5, writable, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        6,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(7, signer, name = "obligation_owner", desc = "Obligation owner")]
+    #[account(8, name = "token_program", desc = "Token program id")]
+    #[account(
+        9,
+        writable,
+        optional,
+        name = "host_fee_receiver",
+        desc = "Host fee receiver account"
+    )]
     BorrowObligationLiquidity {
         /// Amount of liquidity to borrow - u64::MAX for 100% of borrowing power
         liquidity_amount: u64,
@@ -253,6 +544,38 @@ pub enum LendingInstruction {
     ///   5. `[signer]` User transfer authority ($authority).
     ///   6. `[]` Clock sysvar (optional, will be removed soon).
     ///   7. `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_liquidity",
+        desc = "Source liquidity token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_liquidity",
+        desc = "Destination repay reserve liquidity supply SPL Token account"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "repay_reserve",
+        desc = "Repay reserve account - refreshed"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "obligation",
+        desc = "Obligation account - refreshed"
+    )]
+    #[account(4, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        5,
+        signer,
+        name = "user_transfer_authority",
+        desc = "User transfer authority"
+    )]
+    #[account(6, name = "token_program", desc = "Token program id")]
     RepayObligationLiquidity {
         /// Amount of liquidity to repay - u64::MAX for 100% of borrowed amount
         liquidity_amount: u64,
@@ -279,6 +602,60 @@ pub enum LendingInstruction {
     ///   9. `[signer]` User transfer authority ($authority).
     ///   10 `[]` Clock sysvar (optional, will be removed soon).
     ///   11 `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_liquidity",
+        desc = "Source liquidity token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_collateral",
+        desc = "Destination collateral token account"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "repay_reserve",
+        desc = "Repay reserve account - refreshed"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "repay_reserve_liquidity_supply",
+        desc = "Repay reserve liquidity supply SPL Token account"
+    )]
+    #[account(
+        4,
+        name = "withdraw_reserve",
+        desc = "Withdraw reserve account - refreshed"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "withdraw_reserve_collateral_supply",
+        desc = "Withdraw reserve collateral supply SPL Token account"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "obligation",
+        desc = "Obligation account - refreshed"
+    )]
+    #[account(7, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        8,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(
+        9,
+        signer,
+        name = "user_transfer_authority",
+        desc = "User transfer authority"
+    )]
+    #[account(10, name = "token_program", desc = "Token program id")]
     LiquidateObligation {
         /// Amount of liquidity to repay - u64::MAX for up to 100% of borrowed amount
         liquidity_amount: u64,
@@ -321,6 +698,38 @@ pub enum LendingInstruction {
     ///       // Amount that must be repaid by the receiver program
     ///       amount: u64
     ///   }
+    #[account(
+        0,
+        writable,
+        name = "source_liquidity",
+        desc = "Source liquidity token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_liquidity",
+        desc = "Destination liquidity token account"
+    )]
+    #[account(2, writable, name = "reserve", desc = "Reserve account")]
+    #[account(
+        3,
+        writable,
+        name = "flash_loan_fee_receiver",
+        desc = "Flash loan fee receiver account"
+    )]
+    #[account(4, writable, name = "host_fee_receiver", desc = "Host fee receiver")]
+    #[account(5, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        6,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(7, name = "token_program", desc = "Token program id")]
+    #[account(
+        8,
+        name = "flash_loan_receiver_program",
+        desc = "Flash loan receiver program id"
+    )]
     FlashLoan {
         /// The amount that is to be borrowed - u64::MAX for up to 100% of available liquidity
         amount: u64,
@@ -347,6 +756,64 @@ pub enum LendingInstruction {
     ///   12 `[signer]` User transfer authority ($authority).
     ///   13 `[]` Clock sysvar (optional, will be removed soon).
     ///   14 `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_liquidity",
+        desc = "Source liquidity token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "user_collateral",
+        desc = "Destination collateral token account"
+    )]
+    #[account(2, writable, name = "reserve", desc = "Reserve account")]
+    #[account(
+        3,
+        writable,
+        name = "reserve_liquidity_supply",
+        desc = "Reserve liquidity supply SPL Token account"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "reserve_collateral_mint",
+        desc = "Reserve collateral SPL Token mint"
+    )]
+    #[account(5, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        6,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(
+        7,
+        writable,
+        name = "destination_deposit_collateral",
+        desc = "Destination deposit reserve collateral supply SPL Token account"
+    )]
+    #[account(8, writable, name = "obligation", desc = "Obligation account")]
+    #[account(
+        9,
+        writable,
+        signer,
+        name = "obligation_owner",
+        desc = "Obligation owner"
+    )]
+    #[account(10, name = "pyth_price", desc = "Pyth price oracle account")]
+    #[account(
+        11,
+        name = "switchboard_feed",
+        desc = "Switchboard price feed oracle account"
+    )]
+    #[account(
+        12,
+        signer,
+        name = "user_transfer_authority",
+        desc = "User transfer authority"
+    )]
+    #[account(13, name = "token_program", desc = "Token program id")]
     DepositReserveLiquidityAndObligationCollateral {
         /// Amount of liquidity to deposit in exchange
         liquidity_amount: u64,
@@ -371,6 +838,62 @@ pub enum LendingInstruction {
     ///   10 `[signer]` User transfer authority ($authority).
     ///   11. `[]` Clock sysvar (optional, will be removed soon).
     ///   12. `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_collateral",
+        desc = "Source withdraw reserve collateral supply SPL Token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_collateral",
+        desc = "Destination collateral token account"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "withdraw_reserve",
+        desc = "Withdraw reserve account - refreshed"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "obligation",
+        desc = "Obligation account - refreshed"
+    )]
+    #[account(4, writable, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        5,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "destination_liquidity",
+        desc = "User liquidity token account"
+    )]
+    #[account(
+        7,
+        writable,
+        name = "reserve_collateral_mint",
+        desc = "Reserve collateral SPL Token mint"
+    )]
+    #[account(
+        8,
+        writable,
+        name = "reserve_liquidity_supply",
+        desc = "Reserve liquidity supply SPL Token account"
+    )]
+    #[account(9, signer, name = "obligation_owner", desc = "Obligation owner")]
+    #[account(
+        10,
+        signer,
+        name = "user_transfer_authority",
+        desc = "User transfer authority"
+    )]
+    #[account(11, name = "token_program", desc = "Token program id")]
     WithdrawObligationCollateralAndRedeemReserveCollateral {
         /// liquidity_amount is the amount of collateral tokens to withdraw
         collateral_amount: u64,
@@ -388,6 +911,22 @@ pub enum LendingInstruction {
     ///   5 `[]` Pyth product key.
     ///   6 `[]` Pyth price key.
     ///   7 `[]` Switchboard key.
+    #[account(0, writable, name = "reserve", desc = "Reserve account - refreshed")]
+    #[account(1, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        2,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(
+        3,
+        signer,
+        name = "lending_market_owner",
+        desc = "Lending market owner or risk authority"
+    )]
+    #[account(4, name = "pyth_product", desc = "Pyth product key")]
+    #[account(5, name = "pyth_price", desc = "Pyth price key")]
+    #[account(6, name = "switchboard_feed", desc = "Switchboard key")]
     UpdateReserveConfig {
         /// Reserve config to update to
         config: ReserveConfig,
@@ -419,6 +958,85 @@ pub enum LendingInstruction {
     ///   12 `[]` Derived lending market authority.
     ///   13 `[signer]` User transfer authority ($authority).
     ///   14 `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_liquidity",
+        desc = "Source liquidity token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_collateral",
+        desc = "Destination collateral token account"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "destination_liquidity",
+        desc = "Destination liquidity token account"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "repay_reserve",
+        desc = "Repay reserve account - refreshed"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "repay_reserve_liquidity_supply",
+        desc = "Repay reserve liquidity supply SPL Token account"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "withdraw_reserve",
+        desc = "Withdraw reserve account - refreshed"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "withdraw_reserve_collateral_mint",
+        desc = "Withdraw reserve collateral SPL Token mint"
+    )]
+    #[account(
+        7,
+        writable,
+        name = "withdraw_reserve_collateral_supply",
+        desc = "Withdraw reserve collateral supply SPL Token account"
+    )]
+    #[account(
+        8,
+        writable,
+        name = "withdraw_reserve_liquidity_supply",
+        desc = "Withdraw reserve liquidity supply SPL Token account"
+    )]
+    #[account(
+        9,
+        writable,
+        name = "withdraw_reserve_liquidity_fee_receiver",
+        desc = "Withdraw reserve liquidity fee receiver account"
+    )]
+    #[account(
+        10,
+        writable,
+        name = "obligation",
+        desc = "Obligation account - refreshed"
+    )]
+    #[account(11, writable, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        12,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(
+        13,
+        signer,
+        name = "user_transfer_authority",
+        desc = "User transfer authority"
+    )]
+    #[account(14, name = "token_program", desc = "Token program id")]
     LiquidateObligationAndRedeemReserveCollateral {
         /// Amount of liquidity to repay - u64::MAX for up to 100% of borrowed amount
         liquidity_amount: u64,
@@ -432,6 +1050,26 @@ pub enum LendingInstruction {
     ///   3. `[]` Lending market account.
     ///   4. `[]` Derived lending market authority.
     ///   5. `[]` Token program id.
+    #[account(0, writable, name = "reserve", desc = "Reserve account")]
+    #[account(
+        1,
+        writable,
+        name = "reserve_liquidity_fee_receiver",
+        desc = "Reserve liquidity fee receiver account"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "reserve_liquidity_supply",
+        desc = "Reserve liquidity supply SPL Token account"
+    )]
+    #[account(3, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        4,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(5, name = "token_program", desc = "Token program id")]
     RedeemFees,
 
     // 19
@@ -447,6 +1085,27 @@ pub enum LendingInstruction {
     ///   5. `[]` Instructions sysvar.
     ///   6. `[]` Token program id.
     ///   7. `[]` Clock sysvar (optional, will be removed soon).
+    #[account(
+        0,
+        writable,
+        name = "source_liquidity",
+        desc = "Source liquidity token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_liquidity",
+        desc = "Destination liquidity token account"
+    )]
+    #[account(2, writable, name = "reserve", desc = "Reserve account")]
+    #[account(3, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        4,
+        name = "lending_market_authority",
+        desc = "Derived lending market authority"
+    )]
+    #[account(5, name = "instructions_sysvar", desc = "Instructions sysvar")]
+    #[account(6, name = "token_program", desc = "Token program id")]
     FlashBorrowReserveLiquidity {
         /// Amount of liquidity to flash borrow
         liquidity_amount: u64,
@@ -468,6 +1127,35 @@ pub enum LendingInstruction {
     ///   6. `[signer]` User transfer authority ($authority).
     ///   7. `[]` Instructions sysvar.
     ///   8. `[]` Token program id.
+    #[account(
+        0,
+        writable,
+        name = "source_liquidity",
+        desc = "Source liquidity token account"
+    )]
+    #[account(
+        1,
+        writable,
+        name = "destination_liquidity",
+        desc = "Destination liquidity token account"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "reserve_liquidity_fee_receiver",
+        desc = "Flash loan fee receiver account"
+    )]
+    #[account(3, writable, name = "host_fee_receiver", desc = "Host fee receiver")]
+    #[account(4, writable, name = "reserve", desc = "Reserve account")]
+    #[account(5, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        6,
+        signer,
+        name = "user_transfer_authority",
+        desc = "User transfer authority"
+    )]
+    #[account(7, name = "instructions_sysvar", desc = "Instructions sysvar")]
+    #[account(8, name = "token_program", desc = "Token program id")]
     FlashRepayReserveLiquidity {
         /// Amount of liquidity to flash repay
         liquidity_amount: u64,
@@ -483,6 +1171,20 @@ pub enum LendingInstruction {
     ///  1. `[writable]` Reserve account - refreshed.
     ///  2. `[]` Lending Market account.
     ///  3. `[signer]` Lending Market owner.
+    #[account(
+        0,
+        writable,
+        name = "obligation",
+        desc = "Obligation account - refreshed"
+    )]
+    #[account(1, writable, name = "reserve", desc = "Reserve account - refreshed")]
+    #[account(2, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        3,
+        signer,
+        name = "lending_market_owner",
+        desc = "Lending market owner"
+    )]
     ForgiveDebt {
         /// Amount of debt to forgive
         liquidity_amount: u64,
@@ -497,7 +1199,29 @@ pub enum LendingInstruction {
     /// 2. `[writable]` Lending market metadata account.
     /// Must be a pda with seeds [lending_market, "MetaData"]
     /// 3. `[]` System program
+    #[account(0, name = "lending_market", desc = "Lending market account")]
+    #[account(
+        1,
+        writable,
+        signer,
+        name = "lending_market_owner",
+        desc = "Lending market owner"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "lending_market_metadata",
+        desc = "Lending market metadata account"
+    )]
+    #[account(3, name = "system_program", desc = "System program")]
     UpdateMarketMetadata,
+
+    // 23
+    /// Returns the program version and the features it supports as a packed
+    /// `version::ProgramVersion` in the return data
+    ///
+    /// Accounts expected by this instruction: none
+    GetVersion,
 }
 
 impl LendingInstruction {
@@ -713,6 +1437,7 @@ impl LendingInstruction {
                 Self::ForgiveDebt { liquidity_amount }
             }
             22 => Self::UpdateMarketMetadata,
+            23 => Self::GetVersion,
             _ => {
                 msg!("Instruction cannot be unpacked");
                 return Err(LendingError::InstructionUnpackError.into());
@@ -962,6 +1687,9 @@ impl LendingInstruction {
             }
             // special handling for this instruction, bc the instruction is too big to deserialize
             Self::UpdateMarketMetadata => {}
+            Self::GetVersion => {
+                buf.push(23);
+            }
         }
         buf
     }
@@ -1039,10 +1767,8 @@ pub fn init_reserve(
     lending_market_owner_pubkey: Pubkey,
     user_transfer_authority_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     let accounts = vec![
         AccountMeta::new(source_liquidity_pubkey, false),
         AccountMeta::new(destination_collateral_pubkey, false),
@@ -1105,10 +1831,8 @@ pub fn deposit_reserve_liquidity(
     lending_market_pubkey: Pubkey,
     user_transfer_authority_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     Instruction {
         program_id,
         accounts: vec![
@@ -1139,10 +1863,8 @@ pub fn redeem_reserve_collateral(
     lending_market_pubkey: Pubkey,
     user_transfer_authority_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     Instruction {
         program_id,
         accounts: vec![
@@ -1248,10 +1970,8 @@ pub fn deposit_reserve_liquidity_and_obligation_collateral(
     reserve_liquidity_switchboard_oracle_pubkey: Pubkey,
     user_transfer_authority_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     Instruction {
         program_id,
         accounts: vec![
@@ -1293,10 +2013,8 @@ pub fn withdraw_obligation_collateral_and_redeem_reserve_collateral(
     obligation_owner_pubkey: Pubkey,
     user_transfer_authority_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     Instruction {
         program_id,
         accounts: vec![
@@ -1332,10 +2050,8 @@ pub fn withdraw_obligation_collateral(
     lending_market_pubkey: Pubkey,
     obligation_owner_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     Instruction {
         program_id,
         accounts: vec![
@@ -1366,10 +2082,8 @@ pub fn borrow_obligation_liquidity(
     obligation_owner_pubkey: Pubkey,
     host_fee_receiver_pubkey: Option<Pubkey>,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     let mut accounts = vec![
         AccountMeta::new(source_liquidity_pubkey, false),
         AccountMeta::new(destination_liquidity_pubkey, false),
@@ -1433,10 +2147,8 @@ pub fn liquidate_obligation(
     lending_market_pubkey: Pubkey,
     user_transfer_authority_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     Instruction {
         program_id,
         accounts: vec![
@@ -1469,10 +2181,8 @@ pub fn update_reserve_config(
     pyth_price_pubkey: Pubkey,
     switchboard_feed_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     let accounts = vec![
         AccountMeta::new(reserve_pubkey, false),
         AccountMeta::new_readonly(lending_market_pubkey, false),
@@ -1512,10 +2222,8 @@ pub fn liquidate_obligation_and_redeem_reserve_collateral(
     lending_market_pubkey: Pubkey,
     user_transfer_authority_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     Instruction {
         program_id,
         accounts: vec![
@@ -1550,10 +2258,8 @@ pub fn redeem_fees(
     reserve_supply_liquidity_pubkey: Pubkey,
     lending_market_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
     let accounts = vec![
         AccountMeta::new(reserve_pubkey, false),
         AccountMeta::new(reserve_liquidity_fee_receiver_pubkey, false),
@@ -1579,10 +2285,8 @@ pub fn flash_borrow_reserve_liquidity(
     reserve_pubkey: Pubkey,
     lending_market_pubkey: Pubkey,
 ) -> Instruction {
-    let (lending_market_authority_pubkey, _bump_seed) = Pubkey::find_program_address(
-        &[&lending_market_pubkey.to_bytes()[..PUBKEY_BYTES]],
-        &program_id,
-    );
+    let (lending_market_authority_pubkey, _bump_seed) =
+        find_lending_market_authority_address(&lending_market_pubkey, &program_id);
 
     Instruction {
         program_id,
@@ -1662,13 +2366,8 @@ pub fn update_market_metadata(
     lending_market_pubkey: Pubkey,
     lending_market_owner: Pubkey,
 ) -> Instruction {
-    let (lending_market_metadata_pubkey, bump_seed) = Pubkey::find_program_address(
-        &[
-            &lending_market_pubkey.to_bytes()[..PUBKEY_BYTES],
-            b"MetaData",
-        ],
-        &program_id,
-    );
+    let (lending_market_metadata_pubkey, bump_seed) =
+        find_lending_market_metadata_address(&lending_market_pubkey, &program_id);
 
     metadata.bump_seed = bump_seed;
 
@@ -1688,6 +2387,15 @@ pub fn update_market_metadata(
     }
 }
 
+/// Creates a `GetVersion` instruction
+pub fn get_version(program_id: Pubkey) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![],
+        data: LendingInstruction::GetVersion.pack(),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1983,6 +2691,15 @@ mod test {
                 let unpacked = LendingInstruction::unpack(&packed).unwrap();
                 assert_eq!(instruction, unpacked);
             }
+
+            // get version
+            {
+                let instruction = LendingInstruction::GetVersion;
+
+                let packed = instruction.pack();
+                let unpacked = LendingInstruction::unpack(&packed).unwrap();
+                assert_eq!(instruction, unpacked);
+            }
         }
     }
 }
@@ -2,11 +2,17 @@
 
 //! A lending program for the Solana blockchain.
 
+pub mod builder;
 pub mod error;
+pub mod events;
+pub mod idl;
 pub mod instruction;
 pub mod math;
 pub mod oracles;
 pub mod state;
+pub mod version;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
 
 // Export current sdk types for downstream users building with a different sdk version
 pub use solana_program;
@@ -66,7 +66,7 @@ pub fn get_pyth_price(
     Ok((market_price?, ema_price))
 }
 
-fn pyth_price_to_decimal(pyth_price: &Price) -> Result<Decimal, ProgramError> {
+pub fn pyth_price_to_decimal(pyth_price: &Price) -> Result<Decimal, ProgramError> {
     let price: u64 = pyth_price.price.try_into().map_err(|_| {
         msg!("Oracle price cannot be negative");
         LendingError::InvalidOracleConfig
@@ -80,6 +80,13 @@ impl IsInitialized for LendingMarket {
     }
 }
 
+/// Byte offset of the owner in a packed lending market, for `getProgramAccounts` filters
+pub const LENDING_MARKET_OWNER_OFFSET: usize = 2;
+/// Byte offset of the quote currency in a packed lending market
+pub const LENDING_MARKET_QUOTE_CURRENCY_OFFSET: usize = 34;
+/// Byte offset of the risk authority in a packed lending market
+pub const LENDING_MARKET_RISK_AUTHORITY_OFFSET: usize = 250;
+
 const LENDING_MARKET_LEN: usize = 290; // 1 + 1 + 32 + 32 + 32 + 32 + 32 + 56 + 32 + 40
 impl Pack for LendingMarket {
     const LEN: usize = LENDING_MARKET_LEN;
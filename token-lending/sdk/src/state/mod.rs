@@ -6,6 +6,7 @@ mod lending_market_metadata;
 mod obligation;
 mod rate_limiter;
 mod reserve;
+mod view;
 
 pub use last_update::*;
 pub use lending_market::*;
@@ -13,6 +14,7 @@ pub use lending_market_metadata::*;
 pub use obligation::*;
 pub use rate_limiter::*;
 pub use reserve::*;
+pub use view::*;
 
 use crate::math::{Decimal, WAD};
 use solana_program::{msg, program_error::ProgramError};
@@ -173,6 +173,24 @@ impl Obligation {
             .try_sub(self.borrowed_value_upper_bound)
     }
 
+    /// Check the obligation can be liquidated as of its last refresh: it has deposits left to
+    /// seize and borrows to repay, and its borrowed value has reached its unhealthy borrow value
+    pub fn check_liquidatable(&self) -> ProgramResult {
+        if self.deposited_value == Decimal::zero() {
+            msg!("Obligation deposited value is zero");
+            return Err(LendingError::ObligationDepositsZero.into());
+        }
+        if self.borrowed_value == Decimal::zero() {
+            msg!("Obligation borrowed value is zero");
+            return Err(LendingError::ObligationBorrowsZero.into());
+        }
+        if self.borrowed_value < self.unhealthy_borrow_value {
+            msg!("Obligation is healthy and cannot be liquidated");
+            return Err(LendingError::ObligationHealthy.into());
+        }
+        Ok(())
+    }
+
     /// Calculate the maximum liquidation amount for a given liquidity
     pub fn max_liquidation_amount(
         &self,
@@ -408,6 +426,11 @@ impl ObligationLiquidity {
     }
 }
 
+/// Byte offset of the lending market in a packed obligation, for `getProgramAccounts` filters
+pub const OBLIGATION_LENDING_MARKET_OFFSET: usize = 10;
+/// Byte offset of the owner in a packed obligation
+pub const OBLIGATION_OWNER_OFFSET: usize = 42;
+
 const OBLIGATION_COLLATERAL_LEN: usize = 88; // 32 + 8 + 16 + 32
 const OBLIGATION_LIQUIDITY_LEN: usize = 112; // 32 + 16 + 16 + 16 + 32
 const OBLIGATION_LEN: usize = 1300; // 1 + 8 + 1 + 32 + 32 + 16 + 16 + 16 + 16 + 64 + 1 + 1 + (88 * 1) + (112 * 9)
@@ -868,6 +891,31 @@ mod test {
         );
     }
 
+    #[test]
+    fn check_liquidatable() {
+        let mut obligation = Obligation::default();
+        assert_eq!(
+            obligation.check_liquidatable(),
+            Err(LendingError::ObligationDepositsZero.into())
+        );
+
+        obligation.deposited_value = Decimal::from(200u64);
+        assert_eq!(
+            obligation.check_liquidatable(),
+            Err(LendingError::ObligationBorrowsZero.into())
+        );
+
+        obligation.borrowed_value = Decimal::from(50u64);
+        obligation.unhealthy_borrow_value = Decimal::from(100u64);
+        assert_eq!(
+            obligation.check_liquidatable(),
+            Err(LendingError::ObligationHealthy.into())
+        );
+
+        obligation.borrowed_value = Decimal::from(100u64);
+        assert_eq!(obligation.check_liquidatable(), Ok(()));
+    }
+
     #[derive(Debug, Clone)]
     struct MaxWithdrawAmountTestCase {
         obligation: Obligation,
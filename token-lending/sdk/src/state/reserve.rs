@@ -91,29 +91,23 @@ impl Reserve {
         &self,
         quote_amount: Decimal,
     ) -> Result<Decimal, ProgramError> {
-        // quote amount / max(market price, smoothed price) * 10**decimals
-        quote_amount
-            .try_mul(Decimal::from(
-                (10u128)
-                    .checked_pow(self.liquidity.mint_decimals as u32)
-                    .ok_or(LendingError::MathOverflow)?,
-            ))?
-            .try_div(max(
+        liquidity_amount_at_price(
+            quote_amount,
+            max(
                 self.liquidity.smoothed_market_price,
                 self.liquidity.market_price,
-            ))
+            ),
+            self.liquidity.mint_decimals,
+        )
     }
 
     /// find current market value of tokens
     pub fn market_value(&self, liquidity_amount: Decimal) -> Result<Decimal, ProgramError> {
-        self.liquidity
-            .market_price
-            .try_mul(liquidity_amount)?
-            .try_div(Decimal::from(
-                (10u128)
-                    .checked_pow(self.liquidity.mint_decimals as u32)
-                    .ok_or(LendingError::MathOverflow)?,
-            ))
+        market_value_at_price(
+            self.liquidity.market_price,
+            self.liquidity.mint_decimals,
+            liquidity_amount,
+        )
     }
 
     /// find the current upper bound market value of tokens.
@@ -127,13 +121,11 @@ impl Reserve {
             self.liquidity.smoothed_market_price,
         );
 
-        price_upper_bound
-            .try_mul(liquidity_amount)?
-            .try_div(Decimal::from(
-                (10u128)
-                    .checked_pow(self.liquidity.mint_decimals as u32)
-                    .ok_or(LendingError::MathOverflow)?,
-            ))
+        market_value_at_price(
+            price_upper_bound,
+            self.liquidity.mint_decimals,
+            liquidity_amount,
+        )
     }
 
     /// find the current lower bound market value of tokens.
@@ -147,13 +139,11 @@ impl Reserve {
             self.liquidity.smoothed_market_price,
         );
 
-        price_lower_bound
-            .try_mul(liquidity_amount)?
-            .try_div(Decimal::from(
-                (10u128)
-                    .checked_pow(self.liquidity.mint_decimals as u32)
-                    .ok_or(LendingError::MathOverflow)?,
-            ))
+        market_value_at_price(
+            price_lower_bound,
+            self.liquidity.mint_decimals,
+            liquidity_amount,
+        )
     }
 
     /// Record deposited liquidity and return amount of collateral tokens to mint
@@ -259,59 +249,21 @@ impl Reserve {
         max_borrow_value: Decimal,
         remaining_reserve_borrow: Decimal,
     ) -> Result<CalculateBorrowResult, ProgramError> {
-        // @TODO: add lookup table https://git.io/JOCYq
-        let decimals = 10u64
-            .checked_pow(self.liquidity.mint_decimals as u32)
-            .ok_or(LendingError::MathOverflow)?;
-        if amount_to_borrow == u64::MAX {
-            let borrow_amount = max_borrow_value
-                .try_mul(decimals)?
-                .try_div(max(
+        borrow_at_price(
+            ReserveBorrowParams {
+                mint_decimals: self.liquidity.mint_decimals,
+                price_upper_bound: max(
                     self.liquidity.market_price,
                     self.liquidity.smoothed_market_price,
-                ))?
-                .try_div(self.borrow_weight())?
-                .min(remaining_reserve_borrow)
-                .min(self.liquidity.available_amount.into());
-            let (borrow_fee, host_fee) = self
-                .config
-                .fees
-                .calculate_borrow_fees(borrow_amount, FeeCalculation::Inclusive)?;
-            let receive_amount = borrow_amount
-                .try_floor_u64()?
-                .checked_sub(borrow_fee)
-                .ok_or(LendingError::MathOverflow)?;
-
-            Ok(CalculateBorrowResult {
-                borrow_amount,
-                receive_amount,
-                borrow_fee,
-                host_fee,
-            })
-        } else {
-            let receive_amount = amount_to_borrow;
-            let borrow_amount = Decimal::from(receive_amount);
-            let (borrow_fee, host_fee) = self
-                .config
-                .fees
-                .calculate_borrow_fees(borrow_amount, FeeCalculation::Exclusive)?;
-
-            let borrow_amount = borrow_amount.try_add(borrow_fee.into())?;
-            let borrow_value = self
-                .market_value_upper_bound(borrow_amount)?
-                .try_mul(self.borrow_weight())?;
-            if borrow_value > max_borrow_value {
-                msg!("Borrow value cannot exceed maximum borrow value");
-                return Err(LendingError::BorrowTooLarge.into());
-            }
-
-            Ok(CalculateBorrowResult {
-                borrow_amount,
-                receive_amount,
-                borrow_fee,
-                host_fee,
-            })
-        }
+                ),
+                available_amount: self.liquidity.available_amount,
+                borrow_weight: self.borrow_weight(),
+                fees: &self.config.fees,
+            },
+            amount_to_borrow,
+            max_borrow_value,
+            remaining_reserve_borrow,
+        )
     }
 
     /// Repay liquidity up to the borrowed amount
@@ -336,51 +288,12 @@ impl Reserve {
     /// Calculate bonus as a percentage
     /// the value will be in range [0, MAX_BONUS_PCT]
     pub fn calculate_bonus(&self, obligation: &Obligation) -> Result<Decimal, ProgramError> {
-        if obligation.borrowed_value < obligation.unhealthy_borrow_value {
-            msg!("Obligation is healthy so a liquidation bonus can't be calculated");
-            return Err(LendingError::ObligationHealthy.into());
-        }
-
-        let liquidation_bonus = Decimal::from_percent(self.config.liquidation_bonus);
-        let max_liquidation_bonus = Decimal::from_percent(self.config.max_liquidation_bonus);
-        let protocol_liquidation_fee = Decimal::from_deca_bps(self.config.protocol_liquidation_fee);
-
-        // could also return the average of liquidation bonus and max liquidation bonus here, but
-        // i don't think it matters
-        if obligation.unhealthy_borrow_value == obligation.super_unhealthy_borrow_value {
-            return Ok(min(
-                liquidation_bonus.try_add(protocol_liquidation_fee)?,
-                Decimal::from_percent(MAX_BONUS_PCT),
-            ));
-        }
-
-        // safety:
-        // - super_unhealthy_borrow value > unhealthy borrow value because we verify
-        // the ge condition in Reserve::unpack and then verify that they're not equal from check
-        // above
-        // - borrowed_value is >= unhealthy_borrow_value bc of the check above
-        // => weight is always between 0 and 1
-        let weight = min(
-            obligation
-                .borrowed_value
-                .try_sub(obligation.unhealthy_borrow_value)?
-                .try_div(
-                    obligation
-                        .super_unhealthy_borrow_value
-                        .try_sub(obligation.unhealthy_borrow_value)?,
-                )
-                // the division above can potentially overflow if super_unhealthy_borrow_value and
-                // unhealthy_borrow_value are really close to each other. in that case, we want the
-                // weight to be one.
-                .unwrap_or_else(|_| Decimal::one()),
-            Decimal::one(),
-        );
-
-        let bonus = liquidation_bonus
-            .try_add(weight.try_mul(max_liquidation_bonus.try_sub(liquidation_bonus)?)?)?
-            .try_add(protocol_liquidation_fee)?;
-
-        Ok(min(bonus, Decimal::from_percent(MAX_BONUS_PCT)))
+        liquidation_bonus_rate(
+            self.config.liquidation_bonus,
+            self.config.max_liquidation_bonus,
+            self.config.protocol_liquidation_fee,
+            obligation,
+        )
     }
 
     /// Liquidate some or all of an unhealthy obligation
@@ -392,98 +305,13 @@ impl Reserve {
         collateral: &ObligationCollateral,
     ) -> Result<CalculateLiquidationResult, ProgramError> {
         let bonus_rate = self.calculate_bonus(obligation)?.try_add(Decimal::one())?;
-
-        let max_amount = if amount_to_liquidate == u64::MAX {
-            liquidity.borrowed_amount_wads
-        } else {
-            Decimal::from(amount_to_liquidate).min(liquidity.borrowed_amount_wads)
-        };
-
-        let settle_amount;
-        let repay_amount;
-        let withdraw_amount;
-
-        // do a full liquidation if the market value of the borrow is less than one.
-        if liquidity.market_value <= Decimal::one() {
-            let liquidation_value = liquidity.market_value.try_mul(bonus_rate)?;
-            match liquidation_value.cmp(&collateral.market_value) {
-                Ordering::Greater => {
-                    let repay_pct = collateral.market_value.try_div(liquidation_value)?;
-                    settle_amount = liquidity.borrowed_amount_wads.try_mul(repay_pct)?;
-                    repay_amount = settle_amount.try_ceil_u64()?;
-                    withdraw_amount = collateral.deposited_amount;
-                }
-                Ordering::Equal => {
-                    settle_amount = liquidity.borrowed_amount_wads;
-                    repay_amount = settle_amount.try_ceil_u64()?;
-                    withdraw_amount = collateral.deposited_amount;
-                }
-                Ordering::Less => {
-                    let withdraw_pct = liquidation_value.try_div(collateral.market_value)?;
-
-                    settle_amount = liquidity.borrowed_amount_wads;
-                    repay_amount = settle_amount.try_ceil_u64()?;
-                    if repay_amount == 0 {
-                        msg!("repay amount is zero");
-                        return Err(LendingError::LiquidationTooSmall.into());
-                    }
-
-                    withdraw_amount = max(
-                        Decimal::from(collateral.deposited_amount)
-                            .try_mul(withdraw_pct)?
-                            .try_floor_u64()?,
-                        // if withdraw_amount gets floored to zero and repay amount is non-zero,
-                        // we set the withdraw_amount to 1. We do this so dust obligations get
-                        // cleaned up.
-                        //
-                        // safety: technically this gives the liquidator more of a bonus, but this
-                        // can happen at most once per ObligationLiquidity so I don't think this
-                        // can be exploited to cause bad debt or anything.
-                        1,
-                    );
-                }
-            }
-        } else {
-            // partial liquidation
-            // calculate settle_amount and withdraw_amount, repay_amount is settle_amount rounded
-            let liquidation_amount = obligation
-                .max_liquidation_amount(liquidity)?
-                .min(max_amount);
-            let liquidation_pct = liquidation_amount.try_div(liquidity.borrowed_amount_wads)?;
-            let liquidation_value = liquidity
-                .market_value
-                .try_mul(liquidation_pct)?
-                .try_mul(bonus_rate)?;
-
-            match liquidation_value.cmp(&collateral.market_value) {
-                Ordering::Greater => {
-                    let repay_pct = collateral.market_value.try_div(liquidation_value)?;
-                    settle_amount = liquidation_amount.try_mul(repay_pct)?;
-                    repay_amount = settle_amount.try_ceil_u64()?;
-                    withdraw_amount = collateral.deposited_amount;
-                }
-                Ordering::Equal => {
-                    settle_amount = liquidation_amount;
-                    repay_amount = settle_amount.try_ceil_u64()?;
-                    withdraw_amount = collateral.deposited_amount;
-                }
-                Ordering::Less => {
-                    let withdraw_pct = liquidation_value.try_div(collateral.market_value)?;
-                    settle_amount = liquidation_amount;
-                    repay_amount = settle_amount.try_ceil_u64()?;
-                    withdraw_amount = Decimal::from(collateral.deposited_amount)
-                        .try_mul(withdraw_pct)?
-                        .try_floor_u64()?;
-                }
-            }
-        }
-
-        Ok(CalculateLiquidationResult {
-            settle_amount,
-            repay_amount,
-            withdraw_amount,
+        liquidation_at_bonus_rate(
             bonus_rate,
-        })
+            amount_to_liquidate,
+            obligation,
+            liquidity,
+            collateral,
+        )
     }
 
     /// Calculate protocol cut of liquidation bonus always at least 1 lamport
@@ -495,16 +323,11 @@ impl Reserve {
         amount_liquidated: u64,
         bonus_rate: Decimal,
     ) -> Result<u64, ProgramError> {
-        let amount_liquidated_wads = Decimal::from(amount_liquidated);
-        let nonbonus_amount = amount_liquidated_wads.try_div(bonus_rate)?;
-        // After deploying must update all reserves to set liquidation fee then redeploy with this line instead of hardcode
-        let protocol_fee = std::cmp::max(
-            nonbonus_amount
-                .try_mul(Decimal::from_deca_bps(self.config.protocol_liquidation_fee))?
-                .try_ceil_u64()?,
-            1,
-        );
-        Ok(protocol_fee)
+        protocol_liquidation_fee_at(
+            self.config.protocol_liquidation_fee,
+            amount_liquidated,
+            bonus_rate,
+        )
     }
 
     /// Calculate protocol fee redemption accounting for availible liquidity and accumulated fees
@@ -798,17 +621,301 @@ impl ReserveCollateral {
         &self,
         total_liquidity: Decimal,
     ) -> Result<CollateralExchangeRate, ProgramError> {
-        let rate = if self.mint_total_supply == 0 || total_liquidity == Decimal::zero() {
-            Rate::from_scaled_val(INITIAL_COLLATERAL_RATE)
-        } else {
-            let mint_total_supply = Decimal::from(self.mint_total_supply);
-            Rate::try_from(mint_total_supply.try_div(total_liquidity)?)?
-        };
+        collateral_exchange_rate(self.mint_total_supply, total_liquidity)
+    }
+}
+
+/// Exchange rate of a collateral mint with `mint_total_supply` tokens backed by `total_liquidity`
+pub(super) fn collateral_exchange_rate(
+    mint_total_supply: u64,
+    total_liquidity: Decimal,
+) -> Result<CollateralExchangeRate, ProgramError> {
+    let rate = if mint_total_supply == 0 || total_liquidity == Decimal::zero() {
+        Rate::from_scaled_val(INITIAL_COLLATERAL_RATE)
+    } else {
+        let mint_total_supply = Decimal::from(mint_total_supply);
+        Rate::try_from(mint_total_supply.try_div(total_liquidity)?)?
+    };
+
+    Ok(CollateralExchangeRate(rate))
+}
+
+/// Market value of `liquidity_amount` tokens of a mint with `mint_decimals` decimals at `price`
+pub(super) fn market_value_at_price(
+    price: Decimal,
+    mint_decimals: u8,
+    liquidity_amount: Decimal,
+) -> Result<Decimal, ProgramError> {
+    price.try_mul(liquidity_amount)?.try_div(Decimal::from(
+        (10u128)
+            .checked_pow(mint_decimals as u32)
+            .ok_or(LendingError::MathOverflow)?,
+    ))
+}
+
+/// Liquidity amount worth `quote_amount` at `price`, for a mint with `mint_decimals` decimals
+pub(super) fn liquidity_amount_at_price(
+    quote_amount: Decimal,
+    price: Decimal,
+    mint_decimals: u8,
+) -> Result<Decimal, ProgramError> {
+    // quote amount / price * 10**decimals
+    quote_amount
+        .try_mul(Decimal::from(
+            (10u128)
+                .checked_pow(mint_decimals as u32)
+                .ok_or(LendingError::MathOverflow)?,
+        ))?
+        .try_div(price)
+}
+
+/// What [calculate_borrow](struct.Reserve.html#method.calculate_borrow) reads from a reserve
+pub(super) struct ReserveBorrowParams<'a> {
+    /// Reserve liquidity mint decimals
+    pub mint_decimals: u8,
+    /// Max of the market price and the smoothed market price
+    pub price_upper_bound: Decimal,
+    /// Reserve liquidity available
+    pub available_amount: u64,
+    /// Reserve borrow weight
+    pub borrow_weight: Decimal,
+    /// Reserve fees
+    pub fees: &'a ReserveFees,
+}
+
+/// Borrow liquidity from a reserve up to a maximum market value
+pub(super) fn borrow_at_price(
+    reserve: ReserveBorrowParams,
+    amount_to_borrow: u64,
+    max_borrow_value: Decimal,
+    remaining_reserve_borrow: Decimal,
+) -> Result<CalculateBorrowResult, ProgramError> {
+    // @TODO: add lookup table https://git.io/JOCYq
+    let decimals = 10u64
+        .checked_pow(reserve.mint_decimals as u32)
+        .ok_or(LendingError::MathOverflow)?;
+    if amount_to_borrow == u64::MAX {
+        let borrow_amount = max_borrow_value
+            .try_mul(decimals)?
+            .try_div(reserve.price_upper_bound)?
+            .try_div(reserve.borrow_weight)?
+            .min(remaining_reserve_borrow)
+            .min(reserve.available_amount.into());
+        let (borrow_fee, host_fee) = reserve
+            .fees
+            .calculate_borrow_fees(borrow_amount, FeeCalculation::Inclusive)?;
+        let receive_amount = borrow_amount
+            .try_floor_u64()?
+            .checked_sub(borrow_fee)
+            .ok_or(LendingError::MathOverflow)?;
+
+        Ok(CalculateBorrowResult {
+            borrow_amount,
+            receive_amount,
+            borrow_fee,
+            host_fee,
+        })
+    } else {
+        let receive_amount = amount_to_borrow;
+        let borrow_amount = Decimal::from(receive_amount);
+        let (borrow_fee, host_fee) = reserve
+            .fees
+            .calculate_borrow_fees(borrow_amount, FeeCalculation::Exclusive)?;
+
+        let borrow_amount = borrow_amount.try_add(borrow_fee.into())?;
+        let borrow_value = market_value_at_price(
+            reserve.price_upper_bound,
+            reserve.mint_decimals,
+            borrow_amount,
+        )?
+        .try_mul(reserve.borrow_weight)?;
+        if borrow_value > max_borrow_value {
+            msg!("Borrow value cannot exceed maximum borrow value");
+            return Err(LendingError::BorrowTooLarge.into());
+        }
 
-        Ok(CollateralExchangeRate(rate))
+        Ok(CalculateBorrowResult {
+            borrow_amount,
+            receive_amount,
+            borrow_fee,
+            host_fee,
+        })
     }
 }
 
+/// Liquidation bonus as a percentage for a reserve's bonus config, in range [0, MAX_BONUS_PCT]
+pub(super) fn liquidation_bonus_rate(
+    liquidation_bonus: u8,
+    max_liquidation_bonus: u8,
+    protocol_liquidation_fee: u8,
+    obligation: &Obligation,
+) -> Result<Decimal, ProgramError> {
+    if obligation.borrowed_value < obligation.unhealthy_borrow_value {
+        msg!("Obligation is healthy so a liquidation bonus can't be calculated");
+        return Err(LendingError::ObligationHealthy.into());
+    }
+
+    let liquidation_bonus = Decimal::from_percent(liquidation_bonus);
+    let max_liquidation_bonus = Decimal::from_percent(max_liquidation_bonus);
+    let protocol_liquidation_fee = Decimal::from_deca_bps(protocol_liquidation_fee);
+
+    // could also return the average of liquidation bonus and max liquidation bonus here, but
+    // i don't think it matters
+    if obligation.unhealthy_borrow_value == obligation.super_unhealthy_borrow_value {
+        return Ok(min(
+            liquidation_bonus.try_add(protocol_liquidation_fee)?,
+            Decimal::from_percent(MAX_BONUS_PCT),
+        ));
+    }
+
+    // safety:
+    // - super_unhealthy_borrow value > unhealthy borrow value because we verify
+    // the ge condition in Reserve::unpack and then verify that they're not equal from check
+    // above
+    // - borrowed_value is >= unhealthy_borrow_value bc of the check above
+    // => weight is always between 0 and 1
+    let weight = min(
+        obligation
+            .borrowed_value
+            .try_sub(obligation.unhealthy_borrow_value)?
+            .try_div(
+                obligation
+                    .super_unhealthy_borrow_value
+                    .try_sub(obligation.unhealthy_borrow_value)?,
+            )
+            // the division above can potentially overflow if super_unhealthy_borrow_value and
+            // unhealthy_borrow_value are really close to each other. in that case, we want the
+            // weight to be one.
+            .unwrap_or_else(|_| Decimal::one()),
+        Decimal::one(),
+    );
+
+    let bonus = liquidation_bonus
+        .try_add(weight.try_mul(max_liquidation_bonus.try_sub(liquidation_bonus)?)?)?
+        .try_add(protocol_liquidation_fee)?;
+
+    Ok(min(bonus, Decimal::from_percent(MAX_BONUS_PCT)))
+}
+
+/// Liquidate some or all of an unhealthy obligation at `bonus_rate`
+pub(super) fn liquidation_at_bonus_rate(
+    bonus_rate: Decimal,
+    amount_to_liquidate: u64,
+    obligation: &Obligation,
+    liquidity: &ObligationLiquidity,
+    collateral: &ObligationCollateral,
+) -> Result<CalculateLiquidationResult, ProgramError> {
+    let max_amount = if amount_to_liquidate == u64::MAX {
+        liquidity.borrowed_amount_wads
+    } else {
+        Decimal::from(amount_to_liquidate).min(liquidity.borrowed_amount_wads)
+    };
+
+    let settle_amount;
+    let repay_amount;
+    let withdraw_amount;
+
+    // do a full liquidation if the market value of the borrow is less than one.
+    if liquidity.market_value <= Decimal::one() {
+        let liquidation_value = liquidity.market_value.try_mul(bonus_rate)?;
+        match liquidation_value.cmp(&collateral.market_value) {
+            Ordering::Greater => {
+                let repay_pct = collateral.market_value.try_div(liquidation_value)?;
+                settle_amount = liquidity.borrowed_amount_wads.try_mul(repay_pct)?;
+                repay_amount = settle_amount.try_ceil_u64()?;
+                withdraw_amount = collateral.deposited_amount;
+            }
+            Ordering::Equal => {
+                settle_amount = liquidity.borrowed_amount_wads;
+                repay_amount = settle_amount.try_ceil_u64()?;
+                withdraw_amount = collateral.deposited_amount;
+            }
+            Ordering::Less => {
+                let withdraw_pct = liquidation_value.try_div(collateral.market_value)?;
+
+                settle_amount = liquidity.borrowed_amount_wads;
+                repay_amount = settle_amount.try_ceil_u64()?;
+                if repay_amount == 0 {
+                    msg!("repay amount is zero");
+                    return Err(LendingError::LiquidationTooSmall.into());
+                }
+
+                withdraw_amount = max(
+                    Decimal::from(collateral.deposited_amount)
+                        .try_mul(withdraw_pct)?
+                        .try_floor_u64()?,
+                    // if withdraw_amount gets floored to zero and repay amount is non-zero,
+                    // we set the withdraw_amount to 1. We do this so dust obligations get
+                    // cleaned up.
+                    //
+                    // safety: technically this gives the liquidator more of a bonus, but this
+                    // can happen at most once per ObligationLiquidity so I don't think this
+                    // can be exploited to cause bad debt or anything.
+                    1,
+                );
+            }
+        }
+    } else {
+        // partial liquidation
+        // calculate settle_amount and withdraw_amount, repay_amount is settle_amount rounded
+        let liquidation_amount = obligation
+            .max_liquidation_amount(liquidity)?
+            .min(max_amount);
+        let liquidation_pct = liquidation_amount.try_div(liquidity.borrowed_amount_wads)?;
+        let liquidation_value = liquidity
+            .market_value
+            .try_mul(liquidation_pct)?
+            .try_mul(bonus_rate)?;
+
+        match liquidation_value.cmp(&collateral.market_value) {
+            Ordering::Greater => {
+                let repay_pct = collateral.market_value.try_div(liquidation_value)?;
+                settle_amount = liquidation_amount.try_mul(repay_pct)?;
+                repay_amount = settle_amount.try_ceil_u64()?;
+                withdraw_amount = collateral.deposited_amount;
+            }
+            Ordering::Equal => {
+                settle_amount = liquidation_amount;
+                repay_amount = settle_amount.try_ceil_u64()?;
+                withdraw_amount = collateral.deposited_amount;
+            }
+            Ordering::Less => {
+                let withdraw_pct = liquidation_value.try_div(collateral.market_value)?;
+                settle_amount = liquidation_amount;
+                repay_amount = settle_amount.try_ceil_u64()?;
+                withdraw_amount = Decimal::from(collateral.deposited_amount)
+                    .try_mul(withdraw_pct)?
+                    .try_floor_u64()?;
+            }
+        }
+    }
+
+    Ok(CalculateLiquidationResult {
+        settle_amount,
+        repay_amount,
+        withdraw_amount,
+        bonus_rate,
+    })
+}
+
+/// Protocol cut of `amount_liquidated` at `bonus_rate`, always at least 1 lamport
+pub(super) fn protocol_liquidation_fee_at(
+    protocol_liquidation_fee: u8,
+    amount_liquidated: u64,
+    bonus_rate: Decimal,
+) -> Result<u64, ProgramError> {
+    let amount_liquidated_wads = Decimal::from(amount_liquidated);
+    let nonbonus_amount = amount_liquidated_wads.try_div(bonus_rate)?;
+    // After deploying must update all reserves to set liquidation fee then redeploy with this line instead of hardcode
+    let protocol_fee = std::cmp::max(
+        nonbonus_amount
+            .try_mul(Decimal::from_deca_bps(protocol_liquidation_fee))?
+            .try_ceil_u64()?,
+        1,
+    );
+    Ok(protocol_fee)
+}
+
 /// Create a new reserve collateral
 pub struct NewReserveCollateralParams {
     /// Reserve collateral mint address
@@ -1124,6 +1231,23 @@ impl IsInitialized for Reserve {
     }
 }
 
+/// Byte offset of the lending market in a packed reserve, for `getProgramAccounts` filters
+pub const RESERVE_LENDING_MARKET_OFFSET: usize = 10;
+/// Byte offset of the liquidity mint in a packed reserve
+pub const RESERVE_LIQUIDITY_MINT_OFFSET: usize = 42;
+/// Byte offset of the liquidity supply token account in a packed reserve
+pub const RESERVE_LIQUIDITY_SUPPLY_OFFSET: usize = 75;
+/// Byte offset of the Pyth price account in a packed reserve
+pub const RESERVE_PYTH_ORACLE_OFFSET: usize = 107;
+/// Byte offset of the Switchboard feed in a packed reserve
+pub const RESERVE_SWITCHBOARD_ORACLE_OFFSET: usize = 139;
+/// Byte offset of the collateral mint in a packed reserve
+pub const RESERVE_COLLATERAL_MINT_OFFSET: usize = 227;
+/// Byte offset of the collateral supply token account in a packed reserve
+pub const RESERVE_COLLATERAL_SUPPLY_OFFSET: usize = 267;
+/// Byte offset of the liquidity fee receiver in a packed reserve
+pub const RESERVE_FEE_RECEIVER_OFFSET: usize = 339;
+
 const RESERVE_LEN: usize = 619; // 1 + 8 + 1 + 32 + 32 + 1 + 32 + 32 + 32 + 8 + 16 + 16 + 16 + 32 + 8 + 32 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 8 + 8 + 1 + 8 + 8 + 32 + 1 + 1 + 16 + 230
 impl Pack for Reserve {
     const LEN: usize = RESERVE_LEN;
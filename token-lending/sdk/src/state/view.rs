@@ -0,0 +1,627 @@
+use super::*;
+use crate::math::{Decimal, Rate, TryAdd, TrySub};
+use bytemuck::{Pod, Zeroable};
+use num_traits::FromPrimitive;
+use solana_program::{
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::{Pubkey, PUBKEY_BYTES},
+};
+use static_assertions::const_assert_eq;
+use std::{
+    cmp::{max, min},
+    mem::size_of,
+};
+
+/// View over a packed [Reserve](struct.Reserve.html), laid out byte for byte like `Pack`.
+/// Reading or writing a few fields through it avoids unpacking and repacking the whole account,
+/// which matters when an instruction reads many reserves, like refreshing an obligation.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct ReserveView {
+    version: u8,
+    last_update_slot: [u8; 8],
+    last_update_stale: [u8; 1],
+    lending_market: [u8; PUBKEY_BYTES],
+    liquidity_mint_pubkey: [u8; PUBKEY_BYTES],
+    liquidity_mint_decimals: u8,
+    liquidity_supply_pubkey: [u8; PUBKEY_BYTES],
+    liquidity_pyth_oracle_pubkey: [u8; PUBKEY_BYTES],
+    liquidity_switchboard_oracle_pubkey: [u8; PUBKEY_BYTES],
+    liquidity_available_amount: [u8; 8],
+    liquidity_borrowed_amount_wads: [u8; 16],
+    liquidity_cumulative_borrow_rate_wads: [u8; 16],
+    liquidity_market_price: [u8; 16],
+    collateral_mint_pubkey: [u8; PUBKEY_BYTES],
+    collateral_mint_total_supply: [u8; 8],
+    collateral_supply_pubkey: [u8; PUBKEY_BYTES],
+    config_optimal_utilization_rate: u8,
+    config_loan_to_value_ratio: u8,
+    config_liquidation_bonus: u8,
+    config_liquidation_threshold: u8,
+    config_min_borrow_rate: u8,
+    config_optimal_borrow_rate: u8,
+    config_max_borrow_rate: u8,
+    config_fees_borrow_fee_wad: [u8; 8],
+    config_fees_flash_loan_fee_wad: [u8; 8],
+    config_fees_host_fee_percentage: u8,
+    config_deposit_limit: [u8; 8],
+    config_borrow_limit: [u8; 8],
+    config_fee_receiver: [u8; PUBKEY_BYTES],
+    config_protocol_liquidation_fee: u8,
+    config_protocol_take_rate: u8,
+    liquidity_accumulated_protocol_fees_wads: [u8; 16],
+    rate_limiter: [u8; RATE_LIMITER_LEN],
+    config_added_borrow_weight_bps: [u8; 8],
+    liquidity_smoothed_market_price: [u8; 16],
+    config_reserve_type: u8,
+    config_max_utilization_rate: u8,
+    config_super_max_borrow_rate: [u8; 8],
+    config_max_liquidation_bonus: u8,
+    config_max_liquidation_threshold: u8,
+    _padding: [[u8; 46]; 3],
+}
+
+const_assert_eq!(size_of::<ReserveView>(), Reserve::LEN);
+unsafe impl Zeroable for ReserveView {}
+unsafe impl Pod for ReserveView {}
+
+impl ReserveView {
+    /// View packed reserve data, with the same length and version checks as `Reserve::unpack`
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        if data.len() != Reserve::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let view: &Self = bytemuck::from_bytes(data);
+        view.check_version()?;
+        Ok(view)
+    }
+
+    /// Same as `load`, for writing the fields that have setters
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if data.len() != Reserve::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let view: &mut Self = bytemuck::from_bytes_mut(data);
+        view.check_version()?;
+        Ok(view)
+    }
+
+    fn check_version(&self) -> ProgramResult {
+        if self.version == UNINITIALIZED_VERSION {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if self.version > PROGRAM_VERSION {
+            msg!("Reserve version does not match lending program version");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    /// Last slot when supply and rates updated
+    pub fn last_update(&self) -> Result<LastUpdate, ProgramError> {
+        Ok(LastUpdate {
+            slot: u64::from_le_bytes(self.last_update_slot),
+            stale: unpack_bool(&self.last_update_stale)?,
+        })
+    }
+
+    /// Set the reserve as stale, like `LastUpdate::mark_stale`
+    pub fn mark_stale(&mut self) {
+        self.last_update_stale = [true as u8];
+    }
+
+    /// Lending market address
+    pub fn lending_market(&self) -> Pubkey {
+        Pubkey::new_from_array(self.lending_market)
+    }
+
+    /// Reserve liquidity supply address
+    pub fn liquidity_supply_pubkey(&self) -> Pubkey {
+        Pubkey::new_from_array(self.liquidity_supply_pubkey)
+    }
+
+    /// Reserve collateral supply address
+    pub fn collateral_supply_pubkey(&self) -> Pubkey {
+        Pubkey::new_from_array(self.collateral_supply_pubkey)
+    }
+
+    /// Reserve liquidity mint decimals
+    pub fn mint_decimals(&self) -> u8 {
+        self.liquidity_mint_decimals
+    }
+
+    /// Reserve liquidity available
+    pub fn available_amount(&self) -> u64 {
+        u64::from_le_bytes(self.liquidity_available_amount)
+    }
+
+    /// Reserve liquidity borrowed
+    pub fn borrowed_amount_wads(&self) -> Decimal {
+        unpack_decimal(&self.liquidity_borrowed_amount_wads)
+    }
+
+    /// Same as `ReserveLiquidity::borrow`
+    pub fn borrow(&mut self, borrow_decimal: Decimal) -> ProgramResult {
+        let mut liquidity = ReserveLiquidity {
+            available_amount: self.available_amount(),
+            borrowed_amount_wads: self.borrowed_amount_wads(),
+            ..ReserveLiquidity::default()
+        };
+        liquidity.borrow(borrow_decimal)?;
+        self.liquidity_available_amount = liquidity.available_amount.to_le_bytes();
+        pack_decimal(
+            liquidity.borrowed_amount_wads,
+            &mut self.liquidity_borrowed_amount_wads,
+        );
+        Ok(())
+    }
+
+    /// Reserve liquidity cumulative borrow rate
+    pub fn cumulative_borrow_rate_wads(&self) -> Decimal {
+        unpack_decimal(&self.liquidity_cumulative_borrow_rate_wads)
+    }
+
+    /// Reserve liquidity accumulated protocol fees
+    pub fn accumulated_protocol_fees_wads(&self) -> Decimal {
+        unpack_decimal(&self.liquidity_accumulated_protocol_fees_wads)
+    }
+
+    /// Reserve liquidity market price in quote currency
+    pub fn market_price(&self) -> Decimal {
+        unpack_decimal(&self.liquidity_market_price)
+    }
+
+    /// Smoothed reserve liquidity market price in quote currency
+    pub fn smoothed_market_price(&self) -> Decimal {
+        unpack_decimal(&self.liquidity_smoothed_market_price)
+    }
+
+    /// Reserve collateral mint supply
+    pub fn collateral_mint_total_supply(&self) -> u64 {
+        u64::from_le_bytes(self.collateral_mint_total_supply)
+    }
+
+    /// Target ratio of the value of borrows to deposits, as a percentage
+    pub fn loan_to_value_ratio(&self) -> u8 {
+        self.config_loan_to_value_ratio
+    }
+
+    /// Loan to value ratio at which an obligation can be liquidated, as a percentage
+    pub fn liquidation_threshold(&self) -> u8 {
+        self.config_liquidation_threshold
+    }
+
+    /// Loan to value ratio at which an obligation can be liquidated for the maximum bonus, as a
+    /// percentage. Never below the liquidation threshold, like `Reserve::unpack`.
+    pub fn max_liquidation_threshold(&self) -> u8 {
+        max(
+            self.config_liquidation_threshold,
+            self.config_max_liquidation_threshold,
+        )
+    }
+
+    /// Bonus a liquidator gets when repaying part of an unhealthy obligation, as a percentage
+    pub fn liquidation_bonus(&self) -> u8 {
+        self.config_liquidation_bonus
+    }
+
+    /// Maximum bonus a liquidator gets, as a percentage. Never below the liquidation bonus, like
+    /// `Reserve::unpack`.
+    pub fn max_liquidation_bonus(&self) -> u8 {
+        max(
+            self.config_liquidation_bonus,
+            self.config_max_liquidation_bonus,
+        )
+    }
+
+    /// Protocol cut of liquidations in deca bps, capped like `Reserve::unpack`
+    pub fn protocol_liquidation_fee(&self) -> u8 {
+        min(
+            self.config_protocol_liquidation_fee,
+            MAX_PROTOCOL_LIQUIDATION_FEE_DECA_BPS,
+        )
+    }
+
+    /// Reserve fees
+    pub fn fees(&self) -> ReserveFees {
+        ReserveFees {
+            borrow_fee_wad: u64::from_le_bytes(self.config_fees_borrow_fee_wad),
+            flash_loan_fee_wad: u64::from_le_bytes(self.config_fees_flash_loan_fee_wad),
+            host_fee_percentage: self.config_fees_host_fee_percentage,
+        }
+    }
+
+    /// Maximum amount of liquidity that can be borrowed from the reserve
+    pub fn borrow_limit(&self) -> u64 {
+        u64::from_le_bytes(self.config_borrow_limit)
+    }
+
+    /// Outflow rate limiter, denominated in tokens
+    pub fn rate_limiter(&self) -> Result<RateLimiter, ProgramError> {
+        RateLimiter::unpack_from_slice(&self.rate_limiter)
+    }
+
+    /// Replace the outflow rate limiter
+    pub fn set_rate_limiter(&mut self, rate_limiter: &RateLimiter) {
+        rate_limiter.pack_into_slice(&mut self.rate_limiter);
+    }
+
+    /// Added borrow weight in basis points
+    pub fn added_borrow_weight_bps(&self) -> u64 {
+        u64::from_le_bytes(self.config_added_borrow_weight_bps)
+    }
+
+    /// Type of the reserve
+    pub fn reserve_type(&self) -> Result<ReserveType, ProgramError> {
+        ReserveType::from_u8(self.config_reserve_type).ok_or(ProgramError::InvalidAccountData)
+    }
+
+    /// Liquidity fee receiver address
+    pub fn fee_receiver(&self) -> Pubkey {
+        Pubkey::new_from_array(self.config_fee_receiver)
+    }
+
+    /// Same as `Reserve::borrow_weight`
+    pub fn borrow_weight(&self) -> Decimal {
+        Decimal::one()
+            .try_add(Decimal::from_bps(self.added_borrow_weight_bps()))
+            .unwrap()
+    }
+
+    /// Same as `Reserve::loan_to_value_ratio`
+    pub fn loan_to_value_rate(&self) -> Rate {
+        Rate::from_percent(self.loan_to_value_ratio())
+    }
+
+    /// Same as `Reserve::collateral_exchange_rate`
+    pub fn collateral_exchange_rate(&self) -> Result<CollateralExchangeRate, ProgramError> {
+        let total_liquidity = Decimal::from(self.available_amount())
+            .try_add(self.borrowed_amount_wads())?
+            .try_sub(self.accumulated_protocol_fees_wads())?;
+        collateral_exchange_rate(self.collateral_mint_total_supply(), total_liquidity)
+    }
+
+    /// Same as `Reserve::market_value`
+    pub fn market_value(&self, liquidity_amount: Decimal) -> Result<Decimal, ProgramError> {
+        market_value_at_price(self.market_price(), self.mint_decimals(), liquidity_amount)
+    }
+
+    /// Same as `Reserve::market_value_upper_bound`
+    pub fn market_value_upper_bound(
+        &self,
+        liquidity_amount: Decimal,
+    ) -> Result<Decimal, ProgramError> {
+        let price_upper_bound = max(self.market_price(), self.smoothed_market_price());
+        market_value_at_price(price_upper_bound, self.mint_decimals(), liquidity_amount)
+    }
+
+    /// Same as `Reserve::usd_to_liquidity_amount_lower_bound`
+    pub fn usd_to_liquidity_amount_lower_bound(
+        &self,
+        quote_amount: Decimal,
+    ) -> Result<Decimal, ProgramError> {
+        liquidity_amount_at_price(
+            quote_amount,
+            max(self.smoothed_market_price(), self.market_price()),
+            self.mint_decimals(),
+        )
+    }
+
+    /// Same as `Reserve::calculate_borrow`
+    pub fn calculate_borrow(
+        &self,
+        amount_to_borrow: u64,
+        max_borrow_value: Decimal,
+        remaining_reserve_borrow: Decimal,
+    ) -> Result<CalculateBorrowResult, ProgramError> {
+        borrow_at_price(
+            ReserveBorrowParams {
+                mint_decimals: self.mint_decimals(),
+                price_upper_bound: max(self.market_price(), self.smoothed_market_price()),
+                available_amount: self.available_amount(),
+                borrow_weight: self.borrow_weight(),
+                fees: &self.fees(),
+            },
+            amount_to_borrow,
+            max_borrow_value,
+            remaining_reserve_borrow,
+        )
+    }
+
+    /// Same as `Reserve::calculate_bonus`
+    pub fn calculate_bonus(&self, obligation: &Obligation) -> Result<Decimal, ProgramError> {
+        liquidation_bonus_rate(
+            self.liquidation_bonus(),
+            self.max_liquidation_bonus(),
+            self.protocol_liquidation_fee(),
+            obligation,
+        )
+    }
+
+    /// Same as `Reserve::calculate_liquidation`
+    pub fn calculate_liquidation(
+        &self,
+        amount_to_liquidate: u64,
+        obligation: &Obligation,
+        liquidity: &ObligationLiquidity,
+        collateral: &ObligationCollateral,
+    ) -> Result<CalculateLiquidationResult, ProgramError> {
+        let bonus_rate = self.calculate_bonus(obligation)?.try_add(Decimal::one())?;
+        liquidation_at_bonus_rate(
+            bonus_rate,
+            amount_to_liquidate,
+            obligation,
+            liquidity,
+            collateral,
+        )
+    }
+
+    /// Same as `Reserve::calculate_protocol_liquidation_fee`
+    pub fn calculate_protocol_liquidation_fee(
+        &self,
+        amount_liquidated: u64,
+        bonus_rate: Decimal,
+    ) -> Result<u64, ProgramError> {
+        protocol_liquidation_fee_at(
+            self.protocol_liquidation_fee(),
+            amount_liquidated,
+            bonus_rate,
+        )
+    }
+
+    /// Same as `Reserve::market_value_lower_bound`
+    pub fn market_value_lower_bound(
+        &self,
+        liquidity_amount: Decimal,
+    ) -> Result<Decimal, ProgramError> {
+        let price_lower_bound = min(self.market_price(), self.smoothed_market_price());
+        market_value_at_price(price_lower_bound, self.mint_decimals(), liquidity_amount)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{error::LendingError, math::WAD};
+    use rand::Rng;
+
+    fn rand_decimal() -> Decimal {
+        Decimal::from_scaled_val(rand::thread_rng().gen())
+    }
+
+    #[test]
+    fn reserve_view_matches_unpack() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let liquidation_bonus: u8 = rng.gen_range(0..50);
+            let liquidation_threshold: u8 = rng.gen();
+            let reserve = Reserve {
+                version: PROGRAM_VERSION,
+                last_update: LastUpdate {
+                    slot: rng.gen(),
+                    stale: rng.gen(),
+                },
+                lending_market: Pubkey::new_unique(),
+                liquidity: ReserveLiquidity {
+                    mint_decimals: rng.gen_range(0..20),
+                    available_amount: rng.gen(),
+                    borrowed_amount_wads: Decimal::from_scaled_val(rng.gen::<u64>() as u128),
+                    cumulative_borrow_rate_wads: rand_decimal(),
+                    accumulated_protocol_fees_wads: Decimal::from_scaled_val(
+                        rng.gen::<u32>() as u128
+                    ),
+                    market_price: Decimal::from_scaled_val(rng.gen::<u64>() as u128),
+                    smoothed_market_price: Decimal::from_scaled_val(rng.gen::<u64>() as u128),
+                    supply_pubkey: Pubkey::new_unique(),
+                    ..ReserveLiquidity::default()
+                },
+                collateral: ReserveCollateral {
+                    mint_total_supply: rng.gen(),
+                    supply_pubkey: Pubkey::new_unique(),
+                    ..ReserveCollateral::default()
+                },
+                rate_limiter: rand_rate_limiter(),
+                config: ReserveConfig {
+                    loan_to_value_ratio: rng.gen(),
+                    liquidation_bonus,
+                    max_liquidation_bonus: max(liquidation_bonus, rng.gen()),
+                    liquidation_threshold,
+                    max_liquidation_threshold: max(liquidation_threshold, rng.gen()),
+                    protocol_liquidation_fee: rng
+                        .gen_range(0..=MAX_PROTOCOL_LIQUIDATION_FEE_DECA_BPS),
+                    fees: ReserveFees {
+                        borrow_fee_wad: rng.gen_range(0..WAD / 10),
+                        flash_loan_fee_wad: rng.gen(),
+                        host_fee_percentage: rng.gen_range(0..=100),
+                    },
+                    borrow_limit: rng.gen(),
+                    added_borrow_weight_bps: rng.gen_range(0..100_000),
+                    fee_receiver: Pubkey::new_unique(),
+                    reserve_type: ReserveType::from_u8(rng.gen::<u8>() % 2).unwrap(),
+                    ..ReserveConfig::default()
+                },
+            };
+
+            let mut packed = [0u8; Reserve::LEN];
+            Reserve::pack(reserve.clone(), &mut packed).unwrap();
+            let view = ReserveView::load(&packed).unwrap();
+
+            assert_eq!(view.last_update().unwrap(), reserve.last_update);
+            assert_eq!(view.lending_market(), reserve.lending_market);
+            assert_eq!(
+                view.liquidity_supply_pubkey(),
+                reserve.liquidity.supply_pubkey
+            );
+            assert_eq!(
+                view.collateral_supply_pubkey(),
+                reserve.collateral.supply_pubkey
+            );
+            assert_eq!(view.fees(), reserve.config.fees);
+            assert_eq!(view.borrow_limit(), reserve.config.borrow_limit);
+            assert_eq!(view.rate_limiter().unwrap(), reserve.rate_limiter);
+            assert_eq!(
+                view.max_liquidation_bonus(),
+                reserve.config.max_liquidation_bonus
+            );
+            assert_eq!(view.available_amount(), reserve.liquidity.available_amount);
+            assert_eq!(
+                view.cumulative_borrow_rate_wads(),
+                reserve.liquidity.cumulative_borrow_rate_wads
+            );
+            assert_eq!(view.loan_to_value_rate(), reserve.loan_to_value_ratio());
+            assert_eq!(
+                view.max_liquidation_threshold(),
+                reserve.config.max_liquidation_threshold
+            );
+            assert_eq!(view.reserve_type().unwrap(), reserve.config.reserve_type);
+            assert_eq!(view.fee_receiver(), reserve.config.fee_receiver);
+            assert_eq!(view.borrow_weight(), reserve.borrow_weight());
+            let collateral_amount = rng.gen::<u32>() as u64;
+            assert_eq!(
+                view.collateral_exchange_rate()
+                    .and_then(|rate| rate.collateral_to_liquidity(collateral_amount)),
+                reserve
+                    .collateral_exchange_rate()
+                    .and_then(|rate| rate.collateral_to_liquidity(collateral_amount))
+            );
+            let amount = Decimal::from(rng.gen::<u32>() as u64);
+            assert_eq!(view.market_value(amount), reserve.market_value(amount));
+            assert_eq!(
+                view.market_value_lower_bound(amount),
+                reserve.market_value_lower_bound(amount)
+            );
+            assert_eq!(
+                view.market_value_upper_bound(amount),
+                reserve.market_value_upper_bound(amount)
+            );
+            assert_eq!(
+                view.usd_to_liquidity_amount_lower_bound(amount),
+                reserve.usd_to_liquidity_amount_lower_bound(amount)
+            );
+            let max_borrow_value = Decimal::from(rng.gen::<u32>() as u64);
+            for amount_to_borrow in [rng.gen::<u32>() as u64, u64::MAX] {
+                assert_eq!(
+                    view.calculate_borrow(amount_to_borrow, max_borrow_value, amount),
+                    reserve.calculate_borrow(amount_to_borrow, max_borrow_value, amount)
+                );
+            }
+            let amount_liquidated = rng.gen::<u32>() as u64;
+            let bonus_rate = Decimal::from_percent(100 + liquidation_bonus);
+            assert_eq!(
+                view.calculate_protocol_liquidation_fee(amount_liquidated, bonus_rate),
+                reserve.calculate_protocol_liquidation_fee(amount_liquidated, bonus_rate)
+            );
+        }
+    }
+
+    #[test]
+    fn reserve_view_liquidation_matches_unpack() {
+        let reserve = Reserve {
+            version: PROGRAM_VERSION,
+            config: ReserveConfig {
+                liquidation_bonus: 5,
+                max_liquidation_bonus: 10,
+                protocol_liquidation_fee: 10,
+                ..ReserveConfig::default()
+            },
+            ..Reserve::default()
+        };
+        let liquidity = ObligationLiquidity {
+            borrowed_amount_wads: Decimal::from(100u64),
+            market_value: Decimal::from(100u64),
+            ..ObligationLiquidity::default()
+        };
+        let collateral = ObligationCollateral {
+            deposited_amount: 1000,
+            market_value: Decimal::from(120u64),
+            ..ObligationCollateral::default()
+        };
+        let obligation = Obligation {
+            borrowed_value: Decimal::from(100u64),
+            unhealthy_borrow_value: Decimal::from(90u64),
+            super_unhealthy_borrow_value: Decimal::from(110u64),
+            deposits: vec![collateral.clone()],
+            borrows: vec![liquidity.clone()],
+            ..Obligation::default()
+        };
+
+        let mut packed = [0u8; Reserve::LEN];
+        Reserve::pack(reserve.clone(), &mut packed).unwrap();
+        let view = ReserveView::load(&packed).unwrap();
+
+        assert_eq!(
+            view.calculate_bonus(&obligation),
+            reserve.calculate_bonus(&obligation)
+        );
+        for amount_to_liquidate in [10, u64::MAX] {
+            assert_eq!(
+                view.calculate_liquidation(
+                    amount_to_liquidate,
+                    &obligation,
+                    &liquidity,
+                    &collateral
+                ),
+                reserve.calculate_liquidation(
+                    amount_to_liquidate,
+                    &obligation,
+                    &liquidity,
+                    &collateral
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn reserve_view_writes_match_pack() {
+        let mut reserve = Reserve {
+            version: PROGRAM_VERSION,
+            last_update: LastUpdate {
+                slot: 10,
+                stale: false,
+            },
+            liquidity: ReserveLiquidity {
+                available_amount: 1000,
+                borrowed_amount_wads: Decimal::from(500u64),
+                ..ReserveLiquidity::default()
+            },
+            rate_limiter: rand_rate_limiter(),
+            ..Reserve::default()
+        };
+        let mut packed = [0u8; Reserve::LEN];
+        Reserve::pack(reserve.clone(), &mut packed).unwrap();
+
+        let borrow_amount = Decimal::from_scaled_val(100 * WAD as u128 + 1);
+        let rate_limiter = rand_rate_limiter();
+        let view = ReserveView::load_mut(&mut packed).unwrap();
+        view.borrow(borrow_amount).unwrap();
+        view.set_rate_limiter(&rate_limiter);
+        view.mark_stale();
+        assert_eq!(
+            view.borrow(Decimal::from(1000u64)),
+            Err(LendingError::InsufficientLiquidity.into())
+        );
+
+        reserve.liquidity.borrow(borrow_amount).unwrap();
+        reserve.rate_limiter = rate_limiter;
+        reserve.last_update.mark_stale();
+        assert_eq!(Reserve::unpack(&packed).unwrap(), reserve);
+    }
+
+    #[test]
+    fn views_check_version() {
+        assert_eq!(
+            ReserveView::load(&[0u8; Reserve::LEN]).err(),
+            Some(ProgramError::UninitializedAccount)
+        );
+
+        let mut packed = [0u8; Reserve::LEN];
+        packed[0] = PROGRAM_VERSION + 1;
+        assert_eq!(
+            ReserveView::load(&packed).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Version and capabilities of a deployed lending program, returned by the `GetVersion`
+//! instruction
+
+use crate::error::LendingError;
+use solana_program::program_error::ProgramError;
+
+/// Flash borrows and repays
+pub const FEATURE_FLASH_LOANS: u64 = 1 << 0;
+/// Outflow rate limits on lending markets and reserves
+pub const FEATURE_RATE_LIMITS: u64 = 1 << 1;
+/// Lending market metadata accounts
+pub const FEATURE_MARKET_METADATA: u64 = 1 << 2;
+/// Debt forgiveness by the lending market owner
+pub const FEATURE_FORGIVE_DEBT: u64 = 1 << 3;
+/// Isolated tier reserves and borrow weights
+pub const FEATURE_ISOLATED_RESERVES: u64 = 1 << 4;
+/// Liquidations logged as event records
+pub const FEATURE_LIQUIDATION_EVENTS: u64 = 1 << 5;
+
+/// Every feature bit this version of the sdk knows, with its name
+pub const FEATURES: [(u64, &str); 6] = [
+    (FEATURE_FLASH_LOANS, "flash_loans"),
+    (FEATURE_RATE_LIMITS, "rate_limits"),
+    (FEATURE_MARKET_METADATA, "market_metadata"),
+    (FEATURE_FORGIVE_DEBT, "forgive_debt"),
+    (FEATURE_ISOLATED_RESERVES, "isolated_reserves"),
+    (FEATURE_LIQUIDATION_EVENTS, "liquidation_events"),
+];
+
+/// Version and feature bitmap of a program. Newer versions may append fields to the return
+/// data, which [unpack](#method.unpack) ignores, and define new feature bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProgramVersion {
+    /// Major version of the program crate
+    pub major: u16,
+    /// Minor version of the program crate
+    pub minor: u16,
+    /// Patch version of the program crate
+    pub patch: u16,
+    /// `FEATURE_*` bits of the capabilities the program supports
+    pub features: u64,
+}
+
+impl ProgramVersion {
+    /// Length of the packed version
+    pub const LEN: usize = 14;
+
+    /// True if the program supports every feature in `features`
+    pub fn supports(&self, features: u64) -> bool {
+        self.features & features == features
+    }
+
+    /// Names of the known features the program supports
+    pub fn feature_names(&self) -> Vec<&'static str> {
+        FEATURES
+            .iter()
+            .filter(|(feature, _)| self.supports(*feature))
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// Packs the version into return data
+    pub fn pack(&self) -> [u8; Self::LEN] {
+        let mut data = [0; Self::LEN];
+        data[0..2].copy_from_slice(&self.major.to_le_bytes());
+        data[2..4].copy_from_slice(&self.minor.to_le_bytes());
+        data[4..6].copy_from_slice(&self.patch.to_le_bytes());
+        data[6..14].copy_from_slice(&self.features.to_le_bytes());
+        data
+    }
+
+    /// Unpacks a version from return data. The runtime drops trailing zero bytes from return
+    /// data, so missing bytes are read as zeros.
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.is_empty() {
+            return Err(LendingError::InstructionUnpackError.into());
+        }
+        let mut padded = [0; Self::LEN];
+        let len = data.len().min(Self::LEN);
+        padded[..len].copy_from_slice(&data[..len]);
+        let u16_at = |offset: usize| u16::from_le_bytes([padded[offset], padded[offset + 1]]);
+        let mut features = [0; 8];
+        features.copy_from_slice(&padded[6..14]);
+        Ok(Self {
+            major: u16_at(0),
+            minor: u16_at(2),
+            patch: u16_at(4),
+            features: u64::from_le_bytes(features),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pack_unpack() {
+        let version = ProgramVersion {
+            major: 2,
+            minor: 0,
+            patch: 2,
+            features: FEATURE_FLASH_LOANS | FEATURE_FORGIVE_DEBT,
+        };
+        let mut data = version.pack().to_vec();
+        assert_eq!(ProgramVersion::unpack(&data), Ok(version));
+        assert!(version.supports(FEATURE_FLASH_LOANS));
+        assert!(!version.supports(FEATURE_FLASH_LOANS | FEATURE_RATE_LIMITS));
+        assert_eq!(version.feature_names(), vec!["flash_loans", "forgive_debt"]);
+
+        // fields appended by a newer version are ignored
+        data.extend_from_slice(&[7; 4]);
+        assert_eq!(ProgramVersion::unpack(&data), Ok(version));
+
+        // as returned by the runtime, without trailing zeros
+        let trimmed = &data[..ProgramVersion::LEN - 7];
+        assert_eq!(ProgramVersion::unpack(trimmed), Ok(version));
+        assert!(ProgramVersion::unpack(&[]).is_err());
+    }
+}
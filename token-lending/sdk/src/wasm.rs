@@ -0,0 +1,376 @@
+//! JavaScript bindings for decoding lending accounts, built with the `wasm-bindgen` feature.
+//!
+//! Decoding and health math run the same Rust code as the program, so frontends never drift
+//! from on-chain results. Decimals are returned as strings and `u64` amounts as `BigInt`s, so no
+//! precision is lost.
+
+use crate::{
+    math::{Decimal, TryDiv},
+    state::{LastUpdate, Obligation, Reserve},
+};
+use js_sys::{Array, BigInt, Object, Reflect};
+use solana_program::{program_error::ProgramError, program_pack::Pack, pubkey::Pubkey};
+use wasm_bindgen::prelude::*;
+
+/// Decode reserve account data
+#[wasm_bindgen(js_name = decodeReserve)]
+pub fn decode_reserve(data: &[u8]) -> Result<JsValue, JsValue> {
+    let reserve = Reserve::unpack(data).map_err(to_js_error)?;
+
+    let liquidity = Object::new();
+    set(
+        &liquidity,
+        "mintPubkey",
+        pubkey(&reserve.liquidity.mint_pubkey),
+    )?;
+    set(
+        &liquidity,
+        "mintDecimals",
+        reserve.liquidity.mint_decimals.into(),
+    )?;
+    set(
+        &liquidity,
+        "supplyPubkey",
+        pubkey(&reserve.liquidity.supply_pubkey),
+    )?;
+    set(
+        &liquidity,
+        "pythOracle",
+        pubkey(&reserve.liquidity.pyth_oracle_pubkey),
+    )?;
+    set(
+        &liquidity,
+        "switchboardOracle",
+        pubkey(&reserve.liquidity.switchboard_oracle_pubkey),
+    )?;
+    set(
+        &liquidity,
+        "availableAmount",
+        amount(reserve.liquidity.available_amount),
+    )?;
+    set(
+        &liquidity,
+        "borrowedAmountWads",
+        decimal(reserve.liquidity.borrowed_amount_wads),
+    )?;
+    set(
+        &liquidity,
+        "cumulativeBorrowRateWads",
+        decimal(reserve.liquidity.cumulative_borrow_rate_wads),
+    )?;
+    set(
+        &liquidity,
+        "accumulatedProtocolFeesWads",
+        decimal(reserve.liquidity.accumulated_protocol_fees_wads),
+    )?;
+    set(
+        &liquidity,
+        "marketPrice",
+        decimal(reserve.liquidity.market_price),
+    )?;
+    set(
+        &liquidity,
+        "smoothedMarketPrice",
+        decimal(reserve.liquidity.smoothed_market_price),
+    )?;
+
+    let collateral = Object::new();
+    set(
+        &collateral,
+        "mintPubkey",
+        pubkey(&reserve.collateral.mint_pubkey),
+    )?;
+    set(
+        &collateral,
+        "mintTotalSupply",
+        amount(reserve.collateral.mint_total_supply),
+    )?;
+    set(
+        &collateral,
+        "supplyPubkey",
+        pubkey(&reserve.collateral.supply_pubkey),
+    )?;
+
+    let reserve_config = &reserve.config;
+    let config = Object::new();
+    set(
+        &config,
+        "optimalUtilizationRate",
+        reserve_config.optimal_utilization_rate.into(),
+    )?;
+    set(
+        &config,
+        "maxUtilizationRate",
+        reserve_config.max_utilization_rate.into(),
+    )?;
+    set(
+        &config,
+        "loanToValueRatio",
+        reserve_config.loan_to_value_ratio.into(),
+    )?;
+    set(
+        &config,
+        "liquidationBonus",
+        reserve_config.liquidation_bonus.into(),
+    )?;
+    set(
+        &config,
+        "maxLiquidationBonus",
+        reserve_config.max_liquidation_bonus.into(),
+    )?;
+    set(
+        &config,
+        "liquidationThreshold",
+        reserve_config.liquidation_threshold.into(),
+    )?;
+    set(
+        &config,
+        "maxLiquidationThreshold",
+        reserve_config.max_liquidation_threshold.into(),
+    )?;
+    set(
+        &config,
+        "minBorrowRate",
+        reserve_config.min_borrow_rate.into(),
+    )?;
+    set(
+        &config,
+        "optimalBorrowRate",
+        reserve_config.optimal_borrow_rate.into(),
+    )?;
+    set(
+        &config,
+        "maxBorrowRate",
+        reserve_config.max_borrow_rate.into(),
+    )?;
+    set(
+        &config,
+        "superMaxBorrowRate",
+        amount(reserve_config.super_max_borrow_rate),
+    )?;
+    set(
+        &config,
+        "borrowFeeWad",
+        amount(reserve_config.fees.borrow_fee_wad),
+    )?;
+    set(
+        &config,
+        "flashLoanFeeWad",
+        amount(reserve_config.fees.flash_loan_fee_wad),
+    )?;
+    set(
+        &config,
+        "hostFeePercentage",
+        reserve_config.fees.host_fee_percentage.into(),
+    )?;
+    set(
+        &config,
+        "depositLimit",
+        amount(reserve_config.deposit_limit),
+    )?;
+    set(&config, "borrowLimit", amount(reserve_config.borrow_limit))?;
+    set(&config, "feeReceiver", pubkey(&reserve_config.fee_receiver))?;
+    set(
+        &config,
+        "protocolLiquidationFee",
+        reserve_config.protocol_liquidation_fee.into(),
+    )?;
+    set(
+        &config,
+        "protocolTakeRate",
+        reserve_config.protocol_take_rate.into(),
+    )?;
+    set(
+        &config,
+        "addedBorrowWeightBps",
+        amount(reserve_config.added_borrow_weight_bps),
+    )?;
+    set(
+        &config,
+        "reserveType",
+        format!("{:?}", reserve_config.reserve_type).into(),
+    )?;
+
+    let object = Object::new();
+    set(&object, "version", reserve.version.into())?;
+    set(&object, "lastUpdate", last_update(&reserve.last_update)?)?;
+    set(&object, "lendingMarket", pubkey(&reserve.lending_market))?;
+    set(&object, "liquidity", liquidity.into())?;
+    set(&object, "collateral", collateral.into())?;
+    set(&object, "config", config.into())?;
+    Ok(object.into())
+}
+
+/// Decode obligation account data
+#[wasm_bindgen(js_name = decodeObligation)]
+pub fn decode_obligation(data: &[u8]) -> Result<JsValue, JsValue> {
+    let obligation = Obligation::unpack(data).map_err(to_js_error)?;
+
+    let deposits = Array::new();
+    for collateral in &obligation.deposits {
+        let deposit = Object::new();
+        set(
+            &deposit,
+            "depositReserve",
+            pubkey(&collateral.deposit_reserve),
+        )?;
+        set(
+            &deposit,
+            "depositedAmount",
+            amount(collateral.deposited_amount),
+        )?;
+        set(&deposit, "marketValue", decimal(collateral.market_value))?;
+        deposits.push(&deposit);
+    }
+
+    let borrows = Array::new();
+    for liquidity in &obligation.borrows {
+        let borrow = Object::new();
+        set(&borrow, "borrowReserve", pubkey(&liquidity.borrow_reserve))?;
+        set(
+            &borrow,
+            "cumulativeBorrowRateWads",
+            decimal(liquidity.cumulative_borrow_rate_wads),
+        )?;
+        set(
+            &borrow,
+            "borrowedAmountWads",
+            decimal(liquidity.borrowed_amount_wads),
+        )?;
+        set(&borrow, "marketValue", decimal(liquidity.market_value))?;
+        borrows.push(&borrow);
+    }
+
+    let object = Object::new();
+    set(&object, "version", obligation.version.into())?;
+    set(&object, "lastUpdate", last_update(&obligation.last_update)?)?;
+    set(&object, "lendingMarket", pubkey(&obligation.lending_market))?;
+    set(&object, "owner", pubkey(&obligation.owner))?;
+    set(&object, "deposits", deposits.into())?;
+    set(&object, "borrows", borrows.into())?;
+    set(
+        &object,
+        "depositedValue",
+        decimal(obligation.deposited_value),
+    )?;
+    set(&object, "borrowedValue", decimal(obligation.borrowed_value))?;
+    set(
+        &object,
+        "borrowedValueUpperBound",
+        decimal(obligation.borrowed_value_upper_bound),
+    )?;
+    set(
+        &object,
+        "allowedBorrowValue",
+        decimal(obligation.allowed_borrow_value),
+    )?;
+    set(
+        &object,
+        "unhealthyBorrowValue",
+        decimal(obligation.unhealthy_borrow_value),
+    )?;
+    set(
+        &object,
+        "superUnhealthyBorrowValue",
+        decimal(obligation.super_unhealthy_borrow_value),
+    )?;
+    set(
+        &object,
+        "borrowingIsolatedAsset",
+        obligation.borrowing_isolated_asset.into(),
+    )?;
+    Ok(object.into())
+}
+
+/// Health factor of an obligation as of its last refresh: unhealthy borrow value over borrowed
+/// value. Below 1 the obligation can be liquidated. `undefined` when nothing is borrowed.
+#[wasm_bindgen(js_name = obligationHealthFactor)]
+pub fn obligation_health_factor(data: &[u8]) -> Result<Option<String>, JsValue> {
+    let obligation = Obligation::unpack(data).map_err(to_js_error)?;
+    health_factor(&obligation)
+        .map(|factor| factor.map(|factor| factor.to_string()))
+        .map_err(to_js_error)
+}
+
+/// Borrowed value over deposited value of an obligation as of its last refresh
+#[wasm_bindgen(js_name = obligationLoanToValue)]
+pub fn obligation_loan_to_value(data: &[u8]) -> Result<String, JsValue> {
+    let obligation = Obligation::unpack(data).map_err(to_js_error)?;
+    obligation
+        .loan_to_value()
+        .map(|ltv| ltv.to_string())
+        .map_err(to_js_error)
+}
+
+/// True if the obligation can be liquidated as of its last refresh, with the same check as
+/// `LiquidateObligation`
+#[wasm_bindgen(js_name = isObligationLiquidatable)]
+pub fn is_obligation_liquidatable(data: &[u8]) -> Result<bool, JsValue> {
+    let obligation = Obligation::unpack(data).map_err(to_js_error)?;
+    Ok(obligation.check_liquidatable().is_ok())
+}
+
+fn health_factor(obligation: &Obligation) -> Result<Option<Decimal>, ProgramError> {
+    if obligation.borrowed_value == Decimal::zero() {
+        return Ok(None);
+    }
+    obligation
+        .unhealthy_borrow_value
+        .try_div(obligation.borrowed_value)
+        .map(Some)
+}
+
+fn last_update(last_update: &LastUpdate) -> Result<JsValue, JsValue> {
+    let object = Object::new();
+    set(&object, "slot", amount(last_update.slot))?;
+    set(&object, "stale", last_update.stale.into())?;
+    Ok(object.into())
+}
+
+fn set(object: &Object, key: &str, value: JsValue) -> Result<(), JsValue> {
+    Reflect::set(object, &key.into(), &value).map(|_| ())
+}
+
+fn pubkey(pubkey: &Pubkey) -> JsValue {
+    pubkey.to_string().into()
+}
+
+fn amount(amount: u64) -> JsValue {
+    BigInt::from(amount).into()
+}
+
+fn decimal(decimal: Decimal) -> JsValue {
+    decimal.to_string().into()
+}
+
+fn to_js_error(err: ProgramError) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_health() {
+        let mut obligation = Obligation::default();
+        assert_eq!(health_factor(&obligation), Ok(None));
+        assert!(obligation.check_liquidatable().is_err());
+
+        obligation.deposited_value = Decimal::from(200u64);
+        obligation.borrowed_value = Decimal::from(50u64);
+        obligation.unhealthy_borrow_value = Decimal::from(100u64);
+        assert_eq!(health_factor(&obligation), Ok(Some(Decimal::from(2u64))));
+        assert!(obligation.check_liquidatable().is_err());
+
+        obligation.borrowed_value = Decimal::from(100u64);
+        assert_eq!(health_factor(&obligation), Ok(Some(Decimal::one())));
+        assert!(obligation.check_liquidatable().is_ok());
+
+        // bad debt: nothing left to seize
+        obligation.deposited_value = Decimal::zero();
+        obligation.unhealthy_borrow_value = Decimal::zero();
+        assert_eq!(health_factor(&obligation), Ok(Some(Decimal::zero())));
+        assert!(obligation.check_liquidatable().is_err());
+    }
+}
@@ -0,0 +1,345 @@
+#![deny(missing_docs)]
+
+//! Proptest strategies generating valid Solend lending state.
+//!
+//! Generated values satisfy the invariants the program maintains: configs pass
+//! `validate_reserve_config`, reserves hold consistent liquidity and collateral supplies, and
+//! obligations stay within `MAX_OBLIGATION_RESERVES` with cached values that add up. Property
+//! tests can take their inputs from here and override the fields they care about:
+//!
+//! ```
+//! use proptest::{prelude::*, test_runner::TestRunner};
+//! use solend_strategies::reserve;
+//!
+//! TestRunner::default()
+//!     .run(&reserve(), |reserve| {
+//!         prop_assert!(reserve.collateral_exchange_rate().is_ok());
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use proptest::{collection, prelude::*};
+use solana_program::pubkey::Pubkey;
+use solend_sdk::{
+    math::{Decimal, Rate, TryAdd, TryMul, WAD},
+    state::{
+        LastUpdate, Obligation, ObligationCollateral, ObligationLiquidity, RateLimiter,
+        RateLimiterConfig, Reserve, ReserveCollateral, ReserveConfig, ReserveFees,
+        ReserveLiquidity, ReserveType, MAX_BONUS_PCT, MAX_OBLIGATION_RESERVES,
+        MAX_PROTOCOL_LIQUIDATION_FEE_DECA_BPS, PROGRAM_VERSION,
+    },
+};
+
+/// Largest total liquidity of a generated reserve, low enough that interest and collateral
+/// math on it can't overflow
+pub const MAX_LIQUIDITY: u64 = u64::MAX / 5;
+
+/// Largest generated price, in dollars
+pub const MAX_PRICE: u64 = 1_000_000;
+
+/// Largest generated market value of a single obligation deposit or borrow, in dollars
+pub const MAX_MARKET_VALUE: u64 = 1_000_000_000_000;
+
+/// Largest generated added borrow weight, a 10x borrow weight
+pub const MAX_ADDED_BORROW_WEIGHT_BPS: u64 = 90_000;
+
+/// Any pubkey
+pub fn pubkey() -> impl Strategy<Value = Pubkey> {
+    any::<[u8; 32]>().prop_map(Pubkey::new_from_array)
+}
+
+/// `len` values in `0..=max`, in non-decreasing order
+pub fn sorted_u8s(len: usize, max: u8) -> impl Strategy<Value = Vec<u8>> {
+    collection::vec(0..=max, len).prop_map(|mut values| {
+        values.sort_unstable();
+        values
+    })
+}
+
+/// A positive price of at most `MAX_PRICE` dollars
+pub fn price() -> impl Strategy<Value = Decimal> {
+    (1..=MAX_PRICE as u128 * WAD as u128).prop_map(Decimal::from_scaled_val)
+}
+
+/// Reserve fees that pass `validate_reserve_config`
+pub fn reserve_fees() -> impl Strategy<Value = ReserveFees> {
+    (0..WAD, 0..WAD, 0..=100u8).prop_map(
+        |(borrow_fee_wad, flash_loan_fee_wad, host_fee_percentage)| ReserveFees {
+            borrow_fee_wad,
+            flash_loan_fee_wad,
+            host_fee_percentage,
+        },
+    )
+}
+
+prop_compose! {
+    // everything but the fields bounded by others, which reserve_config picks afterwards
+    fn reserve_config_base()(
+        utilization_rates in sorted_u8s(2, 100),
+        thresholds in sorted_u8s(3, 100),
+        bonuses in sorted_u8s(2, MAX_BONUS_PCT),
+        borrow_rates in sorted_u8s(3, u8::MAX),
+        fees in reserve_fees(),
+        (deposit_limit, borrow_limit) in (any::<u64>(), any::<u64>()),
+        fee_receiver in pubkey(),
+        protocol_take_rate in 0..=100u8,
+        added_borrow_weight_bps in 0..=MAX_ADDED_BORROW_WEIGHT_BPS,
+        isolated: bool,
+    ) -> ReserveConfig {
+        let (reserve_type, loan_to_value_ratio, liquidation_threshold) = if isolated {
+            (ReserveType::Isolated, 0, 0)
+        } else {
+            // the loan to value ratio must be below 100%
+            (ReserveType::Regular, thresholds[0].min(99), thresholds[1])
+        };
+        ReserveConfig {
+            optimal_utilization_rate: utilization_rates[0],
+            max_utilization_rate: utilization_rates[1],
+            loan_to_value_ratio,
+            liquidation_bonus: bonuses[0],
+            max_liquidation_bonus: bonuses[1],
+            liquidation_threshold,
+            max_liquidation_threshold: thresholds[2],
+            min_borrow_rate: borrow_rates[0],
+            optimal_borrow_rate: borrow_rates[1],
+            max_borrow_rate: borrow_rates[2],
+            super_max_borrow_rate: borrow_rates[2] as u64,
+            fees,
+            deposit_limit,
+            borrow_limit,
+            fee_receiver,
+            protocol_liquidation_fee: 0,
+            protocol_take_rate,
+            added_borrow_weight_bps,
+            reserve_type,
+        }
+    }
+}
+
+/// A reserve config that passes `validate_reserve_config`, for both regular and isolated reserves
+pub fn reserve_config() -> impl Strategy<Value = ReserveConfig> {
+    reserve_config_base().prop_flat_map(|config| {
+        // the max bonus and protocol fee together are capped at MAX_BONUS_PCT
+        let max_protocol_liquidation_fee =
+            (MAX_BONUS_PCT - config.max_liquidation_bonus) as u64 * 10;
+        (
+            Just(config),
+            config.max_borrow_rate as u64..=u64::MAX,
+            0..=(MAX_PROTOCOL_LIQUIDATION_FEE_DECA_BPS as u64).min(max_protocol_liquidation_fee),
+        )
+            .prop_map(
+                |(config, super_max_borrow_rate, protocol_liquidation_fee)| ReserveConfig {
+                    super_max_borrow_rate,
+                    protocol_liquidation_fee: protocol_liquidation_fee as u8,
+                    ..config
+                },
+            )
+    })
+}
+
+prop_compose! {
+    /// An initialized reserve with a valid config. Up to `MAX_LIQUIDITY` is supplied, any share
+    /// of it borrowed, protocol fees are a share of the borrows and collateral is minted at 1% to
+    /// 255% of the liquidity. Prices are positive and mints have at most 9 decimals.
+    pub fn reserve()(
+        keys in prop::array::uniform7(pubkey()),
+        slot: u64,
+        mint_decimals in 0..=9u8,
+        total_liquidity in 0..=MAX_LIQUIDITY,
+        borrowed_share in 0..=WAD,
+        protocol_fees_percent in 0..=100u8,
+        cumulative_borrow_rate in WAD as u128..=WAD as u128 * 100,
+        (market_price, smoothed_market_price) in (price(), price()),
+        collateral_percent in 1..=u8::MAX,
+        config in reserve_config(),
+    ) -> Reserve {
+        let [
+            lending_market,
+            liquidity_mint,
+            liquidity_supply,
+            pyth_oracle,
+            switchboard_oracle,
+            collateral_mint,
+            collateral_supply,
+        ] = keys;
+
+        let borrowed_amount_wads = Decimal::from(total_liquidity)
+            .try_mul(Rate::from_scaled_val(borrowed_share))
+            .unwrap();
+        let liquidity = ReserveLiquidity {
+            mint_pubkey: liquidity_mint,
+            mint_decimals,
+            supply_pubkey: liquidity_supply,
+            pyth_oracle_pubkey: pyth_oracle,
+            switchboard_oracle_pubkey: switchboard_oracle,
+            available_amount: total_liquidity - borrowed_amount_wads.try_round_u64().unwrap(),
+            borrowed_amount_wads,
+            cumulative_borrow_rate_wads: Decimal::from_scaled_val(cumulative_borrow_rate),
+            accumulated_protocol_fees_wads: borrowed_amount_wads
+                .try_mul(Rate::from_percent(protocol_fees_percent))
+                .unwrap(),
+            market_price,
+            smoothed_market_price,
+        };
+        let mint_total_supply = liquidity
+            .total_supply()
+            .unwrap()
+            .try_mul(Rate::from_percent(collateral_percent))
+            .unwrap()
+            .try_floor_u64()
+            .unwrap();
+
+        Reserve {
+            version: PROGRAM_VERSION,
+            last_update: LastUpdate::new(slot),
+            lending_market,
+            liquidity,
+            collateral: ReserveCollateral {
+                mint_pubkey: collateral_mint,
+                mint_total_supply,
+                supply_pubkey: collateral_supply,
+            },
+            config,
+            rate_limiter: RateLimiter::new(RateLimiterConfig::default(), slot),
+        }
+    }
+}
+
+/// A market value of at most `MAX_MARKET_VALUE` dollars
+fn market_value() -> impl Strategy<Value = Decimal> {
+    (0..=MAX_MARKET_VALUE as u128 * WAD as u128).prop_map(Decimal::from_scaled_val)
+}
+
+/// An obligation deposit in a reserve
+pub fn obligation_collateral(
+    deposit_reserve: Pubkey,
+) -> impl Strategy<Value = ObligationCollateral> {
+    (any::<u64>(), market_value()).prop_map(move |(deposited_amount, market_value)| {
+        ObligationCollateral {
+            deposit_reserve,
+            deposited_amount,
+            market_value,
+        }
+    })
+}
+
+/// An obligation borrow from a reserve
+pub fn obligation_liquidity(borrow_reserve: Pubkey) -> impl Strategy<Value = ObligationLiquidity> {
+    (
+        WAD as u128..=WAD as u128 * 100,
+        0..=MAX_LIQUIDITY as u128 * WAD as u128,
+        market_value(),
+    )
+        .prop_map(
+            move |(cumulative_borrow_rate, borrowed_amount, market_value)| ObligationLiquidity {
+                borrow_reserve,
+                cumulative_borrow_rate_wads: Decimal::from_scaled_val(cumulative_borrow_rate),
+                borrowed_amount_wads: Decimal::from_scaled_val(borrowed_amount),
+                market_value,
+            },
+        )
+}
+
+fn sum(values: impl Iterator<Item = Decimal>) -> Decimal {
+    values.fold(Decimal::zero(), |total, value| {
+        total.try_add(value).unwrap()
+    })
+}
+
+/// Deposits and borrows of an obligation, each unique by reserve, with at most
+/// `MAX_OBLIGATION_RESERVES` in total and at least one deposit if there are any borrows
+pub fn obligation_positions(
+) -> impl Strategy<Value = (Vec<ObligationCollateral>, Vec<ObligationLiquidity>)> {
+    collection::hash_set(pubkey(), 0..=MAX_OBLIGATION_RESERVES)
+        .prop_flat_map(|reserves| {
+            let reserves: Vec<Pubkey> = reserves.into_iter().collect();
+            // borrows need at least one deposit to back them
+            (reserves.len().min(1)..=reserves.len(), Just(reserves))
+        })
+        .prop_flat_map(|(deposit_count, reserves)| {
+            let (deposits, borrows) = reserves.split_at(deposit_count);
+            (
+                deposits
+                    .iter()
+                    .map(|reserve| obligation_collateral(*reserve))
+                    .collect::<Vec<_>>(),
+                borrows
+                    .iter()
+                    .map(|reserve| obligation_liquidity(*reserve))
+                    .collect::<Vec<_>>(),
+            )
+        })
+}
+
+prop_compose! {
+    /// An initialized obligation with at most `MAX_OBLIGATION_RESERVES` deposits and borrows.
+    /// Deposited and borrowed values are the sums of their market values, and the allowed,
+    /// unhealthy and super unhealthy borrow values are non-decreasing shares of the deposited
+    /// value. It may be healthy or not.
+    pub fn obligation()(
+        (deposits, borrows) in obligation_positions(),
+        (lending_market, owner) in (pubkey(), pubkey()),
+        slot: u64,
+        thresholds in sorted_u8s(3, 100),
+    ) -> Obligation {
+        let deposited_value = sum(deposits.iter().map(|deposit| deposit.market_value));
+        let borrowed_value = sum(borrows.iter().map(|borrow| borrow.market_value));
+        let share = |percent: u8| deposited_value.try_mul(Rate::from_percent(percent)).unwrap();
+
+        Obligation {
+            version: PROGRAM_VERSION,
+            last_update: LastUpdate::new(slot),
+            lending_market,
+            owner,
+            allowed_borrow_value: share(thresholds[0]),
+            unhealthy_borrow_value: share(thresholds[1]),
+            super_unhealthy_borrow_value: share(thresholds[2]),
+            deposited_value,
+            borrowed_value,
+            borrowed_value_upper_bound: borrowed_value,
+            deposits,
+            borrows,
+            borrowing_isolated_asset: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_program::program_pack::Pack;
+    use solend_sdk::state::validate_reserve_config;
+
+    proptest! {
+        #[test]
+        fn reserve_configs_are_valid(config in reserve_config()) {
+            prop_assert_eq!(validate_reserve_config(config), Ok(()));
+        }
+
+        #[test]
+        fn reserves_are_consistent(reserve in reserve()) {
+            prop_assert_eq!(validate_reserve_config(reserve.config), Ok(()));
+            prop_assert!(reserve.collateral_exchange_rate().is_ok());
+
+            let mut packed = [0u8; Reserve::LEN];
+            Reserve::pack(reserve.clone(), &mut packed).unwrap();
+            prop_assert_eq!(Reserve::unpack(&packed).unwrap(), reserve);
+        }
+
+        #[test]
+        fn obligations_are_consistent(obligation in obligation()) {
+            prop_assert!(
+                obligation.deposits.len() + obligation.borrows.len() <= MAX_OBLIGATION_RESERVES
+            );
+            prop_assert!(obligation.allowed_borrow_value <= obligation.unhealthy_borrow_value);
+            prop_assert!(
+                obligation.unhealthy_borrow_value <= obligation.super_unhealthy_borrow_value
+            );
+
+            let mut packed = [0u8; Obligation::LEN];
+            Obligation::pack(obligation.clone(), &mut packed).unwrap();
+            prop_assert_eq!(Obligation::unpack(&packed).unwrap(), obligation);
+        }
+    }
+}
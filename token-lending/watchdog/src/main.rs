@@ -0,0 +1,160 @@
+use {
+    clap::{crate_description, crate_name, crate_version, value_t, App, Arg},
+    serde_json::{json, Value},
+    solana_clap_utils::{
+        input_parsers::pubkey_of,
+        input_validators::{is_parsable, is_pubkey, is_url, is_valid_percentage},
+    },
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::commitment_config::CommitmentConfig,
+    solend_client::monitor::{check_invariants, Thresholds},
+    std::{process::exit, thread, time::Duration},
+};
+
+/// Where alerts are sent besides stdout
+struct Alerter {
+    webhook_url: Option<String>,
+    http_client: reqwest::blocking::Client,
+}
+
+impl Alerter {
+    /// Prints each alert as a line of JSON and posts them to the webhook in one request
+    fn emit(&self, alerts: &[Value]) {
+        for alert in alerts {
+            println!("{}", alert);
+        }
+        if let (Some(webhook_url), false) = (&self.webhook_url, alerts.is_empty()) {
+            let result = self
+                .http_client
+                .post(webhook_url)
+                .header("Content-Type", "application/json")
+                .body(json!({ "alerts": alerts }).to_string())
+                .send()
+                .and_then(|response| response.error_for_status());
+            if let Err(err) = result {
+                eprintln!("error: webhook: {}", err);
+            }
+        }
+    }
+}
+
+fn main() {
+    let default_lending_program_id: &str = &solend_sdk::solend_mainnet::id().to_string();
+
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .arg({
+            let arg = Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .help("Configuration file to use");
+            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
+                arg.default_value(config_file)
+            } else {
+                arg
+            }
+        })
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .long("url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(is_url)
+                .help("JSON RPC URL for the cluster.  Default from the configuration file."),
+        )
+        .arg(
+            Arg::with_name("lending_program_id")
+                .long("program")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .required(true)
+                .default_value(default_lending_program_id)
+                .help("Lending program ID"),
+        )
+        .arg(
+            Arg::with_name("lending_market")
+                .long("market")
+                .validator(is_pubkey)
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .help("Only monitor the reserves of this lending market"),
+        )
+        .arg(
+            Arg::with_name("interval")
+                .long("interval")
+                .validator(is_parsable::<u64>)
+                .value_name("SECONDS")
+                .takes_value(true)
+                .default_value("60")
+                .help("Seconds between checks"),
+        )
+        .arg(
+            Arg::with_name("rate_limiter_threshold")
+                .long("rate-limiter-threshold")
+                .validator(is_valid_percentage)
+                .value_name("PERCENT")
+                .takes_value(true)
+                .default_value("80")
+                .help("Alert once a rate limiter's outflow reaches this percentage of its max"),
+        )
+        .arg(
+            Arg::with_name("webhook_url")
+                .long("webhook")
+                .validator(is_url)
+                .value_name("URL")
+                .takes_value(true)
+                .help("Also POST alerts to this URL as a JSON object with an `alerts` array"),
+        )
+        .arg(
+            Arg::with_name("once")
+                .long("once")
+                .takes_value(false)
+                .help("Check once and exit, with status 1 if any alert was raised"),
+        )
+        .get_matches();
+
+    let cli_config = if let Some(config_file) = matches.value_of("config_file") {
+        solana_cli_config::Config::load(config_file).unwrap_or_default()
+    } else {
+        solana_cli_config::Config::default()
+    };
+    let json_rpc_url = value_t!(matches, "json_rpc_url", String).unwrap_or(cli_config.json_rpc_url);
+    let lending_program_id = pubkey_of(&matches, "lending_program_id").unwrap();
+    let lending_market = pubkey_of(&matches, "lending_market");
+    let interval = Duration::from_secs(value_t!(matches, "interval", u64).unwrap());
+    let thresholds = Thresholds {
+        rate_limiter_percent: value_t!(matches, "rate_limiter_threshold", u8).unwrap(),
+    };
+    let once = matches.is_present("once");
+    let rpc_client = RpcClient::new_with_commitment(json_rpc_url, CommitmentConfig::confirmed());
+    let alerter = Alerter {
+        webhook_url: value_t!(matches, "webhook_url", String).ok(),
+        http_client: reqwest::blocking::Client::new(),
+    };
+
+    loop {
+        let alerts = match check_invariants(
+            &rpc_client,
+            &lending_program_id,
+            lending_market.as_ref(),
+            &thresholds,
+        ) {
+            Ok(alerts) => alerts.iter().map(|alert| alert.to_json()).collect(),
+            // a watchdog that can't read the cluster is blind, which is worth an alert of its own
+            Err(err) => vec![json!({
+                "kind": "check_failed",
+                "message": err.to_string(),
+            })],
+        };
+        alerter.emit(&alerts);
+
+        if once {
+            exit(if alerts.is_empty() { 0 } else { 1 });
+        }
+        thread::sleep(interval);
+    }
+}